@@ -1,15 +1,43 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use hddl_analyzer::{find_warning, Rule};
 
 #[derive(Parser)]
 pub struct CLIArgs {
     #[command(subcommand)]
-    pub command: Commands
+    pub command: Commands,
+    /// When to use colors in the output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, global = true)]
+    pub color: ColorChoice,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum MetadataFormat {
+    Text,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    Verify(HDDLInfo),
-    Metadata(HDDLInfo)
+    Verify(VerifyArgs),
+    Metadata(MetadataArgs),
+    Ground(GroundArgs),
+    ValidatePlan(ValidatePlanArgs),
+    Simulate(SimulateArgs),
+    Fmt(FmtArgs),
 }
 
 #[derive(Parser)]
@@ -18,4 +46,93 @@ pub struct HDDLInfo {
     pub domain_path: String,
     #[arg(short, long)]
     pub problem_path: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub input: HDDLInfo,
+    /// Maximum number of reported errors and warnings
+    #[arg(long)]
+    pub max_diagnostics: Option<usize>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// Do not report a warning (e.g., W003 or NoPrimitiveRefinement)
+    #[arg(long, value_name = "CODE", value_parser = parse_warning)]
+    pub allow: Vec<&'static Rule>,
+    /// Report a warning, even if it is allowed in the input files
+    #[arg(long, value_name = "CODE", value_parser = parse_warning)]
+    pub warn: Vec<&'static Rule>,
+    /// Report a warning as an error
+    #[arg(long, value_name = "CODE", value_parser = parse_warning)]
+    pub deny: Vec<&'static Rule>,
+}
+
+#[derive(Parser)]
+pub struct MetadataArgs {
+    #[command(flatten)]
+    pub input: HDDLInfo,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = MetadataFormat::Text)]
+    pub format: MetadataFormat,
+}
+
+#[derive(Parser)]
+pub struct GroundArgs {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = MetadataFormat::Text)]
+    pub format: MetadataFormat,
+    /// Only print the number of ground actions, compound tasks and methods
+    #[arg(long)]
+    pub summary: bool,
+}
+
+#[derive(Parser)]
+pub struct ValidatePlanArgs {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    /// Plan in the IPC format
+    #[arg(index = 2)]
+    pub plan_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = MetadataFormat::Text)]
+    pub format: MetadataFormat,
+}
+
+#[derive(Parser)]
+pub struct SimulateArgs {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    /// Plan in the IPC format (or a sequence of actions)
+    #[arg(index = 2)]
+    pub plan_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// Print the state after each step
+    #[arg(long)]
+    pub trace: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = MetadataFormat::Text)]
+    pub format: MetadataFormat,
+}
+
+#[derive(Parser)]
+pub struct FmtArgs {
+    /// Domain and problem files to format (in place)
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+    /// Only report the files that are not formatted, without changing them
+    #[arg(long)]
+    pub check: bool,
+}
+
+fn parse_warning(code: &str) -> Result<&'static Rule, String> {
+    find_warning(code).ok_or(format!("'{}' is not a warning code", code))
 }
\ No newline at end of file
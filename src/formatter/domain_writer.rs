@@ -0,0 +1,168 @@
+use super::*;
+
+// a definition of the domain body (in the source, these may be interleaved)
+enum Definition<'d, 'a> {
+    Task(&'d Task<'a>),
+    Method(&'d Method<'a>),
+    Action(&'d Action<'a>),
+}
+
+impl<'a> Writer<'a> {
+    pub fn write_domain(mut self, domain: &DomainAST) -> String {
+        self.open(self.header_anchor(), &format!("(define (domain {})", domain.name));
+        let mut requirements: Vec<String> = domain.requirements.iter().map(|req| req.to_string()).collect();
+        requirements.extend(domain.unknown_requirements.iter().map(|(name, _)| name.clone()));
+        if !requirements.is_empty() {
+            let anchor = domain
+                .requirement_positions
+                .values()
+                .flatten()
+                .chain(domain.unknown_requirements.iter().map(|(_, position)| position))
+                .map(|position| position.offset)
+                .min();
+            self.line(anchor, &format!("(:requirements {})", requirements.join(" ")));
+        }
+        self.write_symbols(":types", domain.types.as_deref().unwrap_or_default());
+        self.write_symbols(":constants", domain.constants.as_deref().unwrap_or_default());
+        self.write_declarations(":predicates", &domain.predicates, "");
+        self.write_declarations(":functions", &domain.functions, " - number");
+        // tasks, methods, and actions are kept in their order in the source
+        let mut definitions: Vec<(Option<usize>, Definition)> = vec![];
+        definitions.extend(domain.compound_tasks.iter().map(|task| (self.offset(task.name), Definition::Task(task))));
+        definitions.extend(domain.methods.iter().map(|method| (self.offset(method.name.name), Definition::Method(method))));
+        definitions.extend(domain.actions.iter().map(|action| (self.offset(action.name), Definition::Action(action))));
+        definitions.sort_by_key(|(offset, _)| *offset);
+        for (_, definition) in definitions.iter() {
+            self.blank_line();
+            match definition {
+                Definition::Task(task) => self.write_task(task),
+                Definition::Method(method) => self.write_method(method),
+                Definition::Action(action) => self.write_action(action),
+            }
+        }
+        self.finish()
+    }
+
+    // writes a block of (typed) symbols, e.g., "(:types truck - vehicle)", with a
+    // line for each type
+    pub(super) fn write_symbols(&mut self, keyword: &str, symbols: &[Symbol]) {
+        if symbols.is_empty() {
+            return;
+        }
+        let lists = self.typed_lists(symbols);
+        self.open(lists[0].0, &format!("({}", keyword));
+        for (anchor, list) in lists.iter() {
+            self.line(*anchor, list);
+        }
+        self.close(")");
+    }
+
+    fn write_declarations(&mut self, keyword: &str, declarations: &[Predicate], suffix: &str) {
+        if declarations.is_empty() {
+            return;
+        }
+        self.open(self.offset(declarations[0].name), &format!("({}", keyword));
+        for declaration in declarations.iter() {
+            let text = format!("({}){}", self.signature(declaration.name, &declaration.variables), suffix);
+            self.line(self.offset(declaration.name), &text);
+        }
+        self.close(")");
+    }
+
+    fn write_task(&mut self, task: &Task) {
+        let text = format!("(:task {} :parameters ({}))", task.name, self.parameters(&task.parameters));
+        self.line(self.offset(task.name), &text);
+    }
+
+    fn write_method(&mut self, method: &Method) {
+        self.open(self.offset(method.name.name), &format!("(:method {}", method.name.name));
+        self.write_parameters(&method.params);
+        let task = format!(":task ({})", self.signature(method.task.name, &method.task_terms));
+        self.line(self.offset(method.task.name), &task);
+        if let Some(precondition) = &method.precondition {
+            self.write_formula(":precondition ", precondition, "", false);
+        }
+        self.write_htn(&method.tn);
+        self.close(")");
+    }
+
+    fn write_action(&mut self, action: &Action) {
+        let keyword = match action.is_durative() {
+            true => ":durative-action",
+            false => ":action",
+        };
+        self.open(self.offset(action.name), &format!("({} {}", keyword, action.name));
+        self.write_parameters(&action.parameters);
+        if let Some(duration) = &action.duration {
+            self.write_formula(":duration ", duration, "", false);
+        }
+        if let Some(precondition) = &action.preconditions {
+            let keyword = match action.is_durative() {
+                true => ":condition ",
+                false => ":precondition ",
+            };
+            self.write_formula(keyword, precondition, "", false);
+        }
+        if let Some(effect) = &action.effects {
+            self.write_formula(":effect ", effect, "", true);
+        }
+        self.close(")");
+    }
+
+    pub(super) fn write_parameters(&mut self, parameters: &[Symbol]) {
+        let anchor = parameters.first().and_then(|param| self.offset(param.name));
+        self.line(anchor, &format!(":parameters ({})", self.parameters(parameters)));
+    }
+
+    // writes the subtasks of "tn", followed by their orderings and constraints (if any)
+    pub(super) fn write_htn(&mut self, tn: &HTN) {
+        let keyword = match tn.orderings {
+            TaskOrdering::Total => ":ordered-subtasks",
+            TaskOrdering::Partial(_) => ":subtasks",
+        };
+        if tn.subtasks.is_empty() {
+            self.line(None, &format!("{} ()", keyword));
+        } else {
+            self.open(None, &format!("{} (and", keyword));
+            for subtask in tn.subtasks.iter() {
+                let task = format!("({})", self.signature(subtask.task.name, &subtask.terms));
+                match &subtask.id {
+                    Some(id) => self.line(self.offset(id.name), &format!("({} {})", id.name, task)),
+                    None => self.line(self.offset(subtask.task.name), &task),
+                }
+            }
+            self.close(")");
+        }
+        if let TaskOrdering::Partial(orderings) = &tn.orderings {
+            if !orderings.is_empty() {
+                self.open(None, ":ordering (and");
+                for (first, second) in orderings.iter() {
+                    self.line(self.offset(first), &format!("(< {} {})", first, second));
+                }
+                self.close(")");
+            }
+        }
+        if let Some(constraints) = tn.constraints.as_ref().filter(|constraints| !constraints.is_empty()) {
+            self.open(None, ":constraints (and");
+            for constraint in constraints.iter() {
+                let (a, b) = match constraint {
+                    Constraint::Equal(a, b) | Constraint::NotEqual(a, b) => (a, b),
+                };
+                let equality = format!("(= {} {})", self.term(a), self.term(b));
+                match constraint {
+                    Constraint::Equal(..) => self.line(self.offset(a), &equality),
+                    Constraint::NotEqual(..) => self.line(self.offset(a), &format!("(not {})", equality)),
+                }
+            }
+            self.close(")");
+        }
+    }
+
+    // e.g., "drive ?v ?a ?b" (or "drive ?v - vehicle ?a ?b - location" in declarations)
+    fn signature(&self, name: &str, terms: &[Symbol]) -> String {
+        match terms.is_empty() {
+            true => name.to_string(),
+            false => format!("{} {}", name, self.parameters(terms)),
+        }
+    }
+}
\ No newline at end of file
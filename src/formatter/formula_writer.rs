@@ -0,0 +1,153 @@
+use super::*;
+
+impl<'a> Writer<'a> {
+    // writes "formula" with "prefix" before its first line, and "suffix" after its
+    // last one. Conjunctions (and disjunctions) are written with one operand per line.
+    // Implications are written as "when" in effects, and as "imply" otherwise.
+    pub(super) fn write_formula(&mut self, prefix: &str, formula: &Formula, suffix: &str, is_effect: bool) {
+        if let Some(text) = self.inline_formula(formula, is_effect) {
+            let anchor = self.formula_anchor(formula);
+            self.line(anchor, &format!("{}{}{}", prefix, text, suffix));
+            return;
+        }
+        match formula {
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                let connective = match formula {
+                    Formula::And(_) => "and",
+                    Formula::Or(_) => "or",
+                    _ => "oneof",
+                };
+                self.open(None, &format!("{}({}", prefix, connective));
+                for f in fs.iter() {
+                    self.write_formula("", f, "", is_effect);
+                }
+                self.close(&format!("){}", suffix));
+            }
+            Formula::Not(f) => {
+                self.write_formula(&format!("{}(not ", prefix), f, &format!("){}", suffix), is_effect);
+            }
+            Formula::Timed(time, f) => {
+                self.write_formula(&format!("{}({} ", prefix, time), f, &format!("){}", suffix), is_effect);
+            }
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+                let quantifier = match formula {
+                    Formula::ForAll(..) => "forall",
+                    _ => "exists",
+                };
+                let prefix = format!("{}({} ({}) ", prefix, quantifier, self.parameters(vars));
+                self.write_formula(&prefix, f, &format!("){}", suffix), is_effect);
+            }
+            Formula::Imply(ps, qs) => {
+                self.open(None, &format!("{}({}", prefix, implication(is_effect)));
+                for operands in [ps, qs] {
+                    match &operands[..] {
+                        [f] => self.write_formula("", f, "", is_effect),
+                        _ => self.write_formula("", &Formula::And(operands.clone()), "", is_effect),
+                    }
+                }
+                self.close(&format!("){}", suffix));
+            }
+            _ => unreachable!("the other formulas are written in a single line"),
+        }
+    }
+
+    // the formula in a single line, unless it has a (non-empty) conjunction or disjunction
+    fn inline_formula(&self, formula: &Formula, is_effect: bool) -> Option<String> {
+        match formula {
+            Formula::Empty => Some("()".to_string()),
+            Formula::Atom(predicate) => Some(self.atom(predicate)),
+            Formula::Not(f) => Some(format!("(not {})", self.inline_formula(f, is_effect)?)),
+            Formula::And(fs) if fs.is_empty() => Some("(and)".to_string()),
+            Formula::Or(fs) if fs.is_empty() => Some("(or)".to_string()),
+            Formula::Xor(fs) if fs.is_empty() => Some("(oneof)".to_string()),
+            Formula::And(_) | Formula::Or(_) | Formula::Xor(_) => None,
+            Formula::Imply(ps, qs) => match (&ps[..], &qs[..]) {
+                ([p], [q]) => Some(format!(
+                    "({} {} {})",
+                    implication(is_effect),
+                    self.inline_formula(p, is_effect)?,
+                    self.inline_formula(q, is_effect)?
+                )),
+                _ => None,
+            },
+            Formula::ForAll(vars, f) => Some(format!(
+                "(forall ({}) {})",
+                self.parameters(vars),
+                self.inline_formula(f, is_effect)?
+            )),
+            Formula::Exists(vars, f) => Some(format!(
+                "(exists ({}) {})",
+                self.parameters(vars),
+                self.inline_formula(f, is_effect)?
+            )),
+            Formula::Equals(a, b) => Some(format!("(= {} {})", self.term(a.name), self.term(b.name))),
+            Formula::Compare(comparison, lhs, rhs) => Some(format!(
+                "({} {} {})",
+                comparison,
+                self.numeric_expression(lhs),
+                self.numeric_expression(rhs)
+            )),
+            Formula::Assign(assignment, function, value) => Some(format!(
+                "({} {} {})",
+                assignment,
+                self.atom(function),
+                self.numeric_expression(value)
+            )),
+            Formula::Timed(time, f) => Some(format!("({} {})", time, self.inline_formula(f, is_effect)?)),
+        }
+    }
+
+    // e.g., "(at ?v depot)"
+    pub(super) fn atom(&self, predicate: &Predicate) -> String {
+        let mut text = format!("({}", predicate.name);
+        for var in predicate.variables.iter() {
+            text.push(' ');
+            text.push_str(&self.term(var.name));
+        }
+        text.push(')');
+        text
+    }
+
+    pub(super) fn numeric_expression(&self, expression: &NumericExpression) -> String {
+        match expression {
+            NumericExpression::Number(number) => number.to_string(),
+            NumericExpression::Duration => "?duration".to_string(),
+            NumericExpression::Function(function) => self.atom(function),
+            NumericExpression::Operation(operation, operands) => {
+                let mut text = format!("({}", operation);
+                for operand in operands.iter() {
+                    text.push(' ');
+                    text.push_str(&self.numeric_expression(operand));
+                }
+                text.push(')');
+                text
+            }
+        }
+    }
+
+    // the offset of the first element of the formula in the source
+    pub(super) fn formula_anchor(&self, formula: &Formula) -> Option<usize> {
+        match formula {
+            Formula::Empty => None,
+            Formula::Atom(predicate) => self.offset(predicate.name),
+            Formula::Not(f) | Formula::Timed(_, f) => self.formula_anchor(f),
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => fs.first().and_then(|f| self.formula_anchor(f)),
+            Formula::Imply(ps, qs) => ps.iter().chain(qs.iter()).find_map(|f| self.formula_anchor(f)),
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => match vars.first() {
+                Some(var) => self.offset(var.name),
+                None => self.formula_anchor(f),
+            },
+            Formula::Equals(a, _) => self.offset(a.name),
+            Formula::Compare(..) | Formula::Assign(..) => {
+                formula.get_functions().first().and_then(|function| self.offset(function.name))
+            }
+        }
+    }
+}
+
+fn implication(is_effect: bool) -> &'static str {
+    match is_effect {
+        true => "when",
+        false => "imply",
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+mod writer;
+mod domain_writer;
+mod problem_writer;
+mod formula_writer;
+#[cfg(test)]
+mod tests;
+
+use crate::lexical_analyzer::*;
+use crate::syntactic_analyzer::*;
+
+pub use writer::Writer;
\ No newline at end of file
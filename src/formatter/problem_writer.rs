@@ -0,0 +1,59 @@
+use super::*;
+
+impl<'a> Writer<'a> {
+    pub fn write_problem(mut self, problem: &ProblemAST) -> String {
+        self.open(self.header_anchor(), &format!("(define (problem {})", problem.name));
+        self.line(Some(problem.domain_name_pos.offset), &format!("(:domain {})", problem.domain_name));
+        let mut requirements: Vec<String> = problem.requirements.iter().map(|req| req.to_string()).collect();
+        requirements.extend(problem.unknown_requirements.iter().map(|(name, _)| name.clone()));
+        if !requirements.is_empty() {
+            let anchor = problem.unknown_requirements.iter().map(|(_, position)| position.offset).min();
+            self.line(anchor, &format!("(:requirements {})", requirements.join(" ")));
+        }
+        self.write_symbols(":objects", &problem.objects);
+        if let Some(init_tn) = &problem.init_tn {
+            let anchor = match (&init_tn.parameters, init_tn.tn.subtasks.first()) {
+                (Some(parameters), _) if !parameters.is_empty() => self.offset(parameters[0].name),
+                (_, Some(subtask)) => self.offset(subtask.id.as_ref().unwrap_or(&subtask.task).name),
+                _ => None,
+            };
+            self.open(anchor, "(:htn");
+            if let Some(parameters) = &init_tn.parameters {
+                self.write_parameters(parameters);
+            }
+            self.write_htn(&init_tn.tn);
+            self.close(")");
+        }
+        if problem.init_state.is_empty() && problem.init_values.is_empty() {
+            self.line(None, "(:init)");
+        } else {
+            let anchor = match (problem.init_state.first(), problem.init_values.first()) {
+                (Some(fact), _) => self.offset(fact.name),
+                (None, Some(value)) => self.offset(value.function.name),
+                (None, None) => None,
+            };
+            self.open(anchor, "(:init");
+            for fact in problem.init_state.iter() {
+                self.line(self.offset(fact.name), &self.atom(fact));
+            }
+            for value in problem.init_values.iter() {
+                let text = format!("(= {} {})", self.atom(&value.function), value.value);
+                self.line(self.offset(value.function.name), &text);
+            }
+            self.close(")");
+        }
+        if let Some(goal) = &problem.goal {
+            self.write_formula("(:goal ", goal, ")", false);
+        }
+        if let Some(metric) = &problem.metric {
+            let anchor = metric.expression.get_functions().first().and_then(|function| self.offset(function.name));
+            let text = format!(
+                "(:metric {} {})",
+                metric.optimization,
+                self.numeric_expression(&metric.expression)
+            );
+            self.line(anchor, &text);
+        }
+        self.finish()
+    }
+}
\ No newline at end of file
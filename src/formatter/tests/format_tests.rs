@@ -0,0 +1,134 @@
+use super::*;
+
+fn format(program: &str) -> String {
+    match crate::HDDLAnalyzer::format(&program.as_bytes().to_vec()) {
+        Ok(formatted) => formatted,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[test]
+pub fn domain_format_test() {
+    let program = "(define (domain transport) (:requirements :typing :hierarchy)
+        (:types location vehicle - object)
+        (:predicates (at ?v - vehicle ?l - location) (road ?a ?b - location))
+        (:task get-to :parameters (?v - vehicle ?l - location))
+        (:method m_drive :parameters (?v - vehicle ?a ?b - location) :task (get-to ?v ?b)
+            :ordered-tasks (and (drive ?v ?a ?b)))
+        (:action drive :parameters (?v - vehicle ?a ?b - location)
+            :precondition (and (at ?v ?a) (road ?a ?b))
+            :effect (and (not (at ?v ?a)) (at ?v ?b))))";
+    let expected = "(define (domain transport)
+  (:requirements :typing :hierarchy)
+  (:types
+    location vehicle - object
+  )
+  (:predicates
+    (at ?v - vehicle ?l - location)
+    (road ?a ?b - location)
+  )
+
+  (:task get-to :parameters (?v - vehicle ?l - location))
+
+  (:method m_drive
+    :parameters (?v - vehicle ?a ?b - location)
+    :task (get-to ?v ?b)
+    :ordered-subtasks (and
+      (drive ?v ?a ?b)
+    )
+  )
+
+  (:action drive
+    :parameters (?v - vehicle ?a ?b - location)
+    :precondition (and
+      (at ?v ?a)
+      (road ?a ?b)
+    )
+    :effect (and
+      (not (at ?v ?a))
+      (at ?v ?b)
+    )
+  )
+)
+";
+    let formatted = format(program);
+    assert_eq!(formatted, expected);
+    // formatting is idempotent
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn comment_preservation_test() {
+    let program = "; a domain
+(define (domain d)
+    (:predicates (p ?x) ; unary
+        (q))
+    ; the only task
+    (:task t :parameters ())
+) ; end
+";
+    let expected = "; a domain
+(define (domain d)
+  (:predicates
+    (p ?x) ; unary
+    (q)
+  )
+
+  ; the only task
+  (:task t :parameters ())
+) ; end
+";
+    let formatted = format(program);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn unknown_requirement_format_test() {
+    // unknown requirements are reported by "verify", but kept by the formatter
+    let program = "(define (domain d) (:requirements :foo-bar :typing) ; custom
+    (:predicates (at ?x)))";
+    let expected = "(define (domain d)
+  (:requirements :typing :foo-bar) ; custom
+  (:predicates
+    (at ?x)
+  )
+)
+";
+    let formatted = format(program);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn problem_format_test() {
+    let program = "(define (problem p) (:domain transport)
+        (:objects a b - location t1 - vehicle)
+        (:htn :subtasks (and (task0 (get-to t1 b))) :ordering ())
+        (:init (road a b) (at t1 a) (= (road-length a b) 3))
+        (:goal (at t1 b))
+        (:metric minimize (total-cost)))";
+    let expected = "(define (problem p)
+  (:domain transport)
+  (:objects
+    a b - location
+    t1 - vehicle
+  )
+  (:htn
+    :subtasks (and
+      (task0 (get-to t1 b))
+    )
+  )
+  (:init
+    (road a b)
+    (at t1 a)
+    (= (road-length a b) 3)
+  )
+  (:goal (at t1 b))
+  (:metric minimize (total-cost))
+)
+";
+    let formatted = format(program);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
\ No newline at end of file
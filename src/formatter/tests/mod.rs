@@ -0,0 +1,3 @@
+mod format_tests;
+
+use super::*;
\ No newline at end of file
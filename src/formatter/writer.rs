@@ -0,0 +1,151 @@
+use super::*;
+
+const INDENT: &str = "  ";
+
+// writes a syntax tree back to HDDL with a canonical layout. The comments of the
+// source are kept before the definition (or line) that follows them, or at the end
+// of the line that they trail.
+pub struct Writer<'a> {
+    program: &'a [u8],
+    // the comments of the source (in order), and the first one that is not written yet
+    comments: Vec<Trivia<'a>>,
+    next_comment: usize,
+    // the offsets of the first and last tokens of the source
+    start: usize,
+    end: usize,
+    line_starts: Vec<usize>,
+    lines: Vec<String>,
+    indent: usize,
+    // the last line that a source element is written to, and the line of that element in the source
+    last_anchor: Option<(usize, usize)>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(program: &'a [u8], comments: Vec<Trivia<'a>>, start: usize, end: usize) -> Writer<'a> {
+        let mut line_starts = vec![0];
+        for (offset, character) in program.iter().enumerate() {
+            if *character == b'\n' {
+                line_starts.push(offset + 1);
+            }
+        }
+        Writer {
+            program,
+            comments,
+            next_comment: 0,
+            start,
+            end,
+            line_starts,
+            lines: vec![],
+            indent: 0,
+            last_anchor: None,
+        }
+    }
+
+    // the header of the file is anchored to its first token
+    pub(super) fn header_anchor(&self) -> Option<usize> {
+        Some(self.start)
+    }
+
+    // writes "text" as a new line. "anchor" is the offset of the source element that
+    // the line starts with (if any), so that the comments before it are written first.
+    pub(super) fn line(&mut self, anchor: Option<usize>, text: &str) {
+        if let Some(offset) = anchor {
+            self.flush_comments(offset);
+        }
+        self.push(text);
+        if let Some(offset) = anchor {
+            let line = self.line_starts.partition_point(|start| *start <= offset);
+            self.last_anchor = Some((self.lines.len() - 1, line));
+        }
+    }
+
+    // writes "text" as a new line, and indents the lines after it
+    pub(super) fn open(&mut self, anchor: Option<usize>, text: &str) {
+        self.line(anchor, text);
+        self.indent += 1;
+    }
+
+    // ends the indentation of the last "open", and writes "text" as a new line
+    pub(super) fn close(&mut self, text: &str) {
+        self.indent -= 1;
+        self.push(text);
+    }
+
+    pub(super) fn blank_line(&mut self) {
+        self.lines.push(String::new());
+    }
+
+    // closes the definition, along with the remaining comments
+    pub(super) fn finish(mut self) -> String {
+        self.flush_comments(self.end);
+        self.indent -= 1;
+        self.line(Some(self.end), ")");
+        self.flush_comments(self.program.len());
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    // the offset of "name" in the source (names are slices of the source)
+    pub(super) fn offset(&self, name: &str) -> Option<usize> {
+        let start = self.program.as_ptr() as usize;
+        let address = name.as_ptr() as usize;
+        match address >= start && address < start + self.program.len() {
+            true => Some(address - start),
+            false => None,
+        }
+    }
+
+    // variables are lexed without their '?', so they are told apart from constants
+    // by the character before them in the source
+    pub(super) fn term(&self, name: &str) -> String {
+        match self.offset(name) {
+            Some(offset) if offset > 0 && self.program[offset - 1] == b'?' => format!("?{}", name),
+            _ => name.to_string(),
+        }
+    }
+
+    // groups the consecutive symbols of the same type, e.g., "?a ?b - location",
+    // along with the offset of their first symbol
+    pub(super) fn typed_lists(&self, symbols: &[Symbol]) -> Vec<(Option<usize>, String)> {
+        symbols
+            .chunk_by(|a, b| a.symbol_type == b.symbol_type)
+            .map(|group| {
+                let mut list: Vec<String> = group.iter().map(|symbol| self.term(symbol.name)).collect();
+                if let Some(symbol_type) = &group[0].symbol_type {
+                    list.push(format!("- {}", symbol_type));
+                }
+                (self.offset(group[0].name), list.join(" "))
+            })
+            .collect()
+    }
+
+    // e.g., "?a ?b - location ?v - vehicle"
+    pub(super) fn parameters(&self, symbols: &[Symbol]) -> String {
+        let lists: Vec<String> = self.typed_lists(symbols).into_iter().map(|(_, list)| list).collect();
+        lists.join(" ")
+    }
+
+    // writes the comments before "offset"
+    fn flush_comments(&mut self, offset: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment).copied() {
+            if comment.position.offset >= offset {
+                break;
+            }
+            self.next_comment += 1;
+            let text = comment.text.trim_end();
+            match self.last_anchor {
+                // the comment trails the last line
+                Some((index, line)) if line == comment.position.line as usize => {
+                    self.lines[index].push(' ');
+                    self.lines[index].push_str(text);
+                }
+                _ => self.push(text),
+            }
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        self.lines.push(format!("{}{}", INDENT.repeat(self.indent), text));
+    }
+}
\ No newline at end of file
@@ -2,8 +2,10 @@ mod tokenizer;
 mod token_types;
 mod tests;
 mod token_pos;
+mod trivia;
 
 pub use token_types::*;
 pub use tokenizer::LexicalAnalyzer;
 pub use crate::output::{LexicalError, LexicalErrorType};
-pub use token_pos::*;
\ No newline at end of file
+pub use token_pos::*;
+pub use trivia::*;
\ No newline at end of file
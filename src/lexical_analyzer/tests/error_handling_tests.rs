@@ -104,4 +104,28 @@ mod tests{
             _ => panic!("error not detected")
         }
     }
+
+    #[test]
+    pub fn invalid_identifier_recovery_test() {
+        let program = String::from("(at ?x@ b$c) ; comment").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let _ = lexer.get_token();
+        let _ = lexer.get_token();
+        match lexer.get_token() {
+            Err(x) => assert_eq!(x.lexeme, "x@"),
+            _ => panic!("error not detected")
+        }
+        match lexer.get_token() {
+            Err(x) => assert_eq!(x.lexeme, "b$c"),
+            _ => panic!("error not detected")
+        }
+        match lexer.get_token() {
+            Ok(Token::Punctuator(PunctuationType::RParentheses)) => {},
+            _ => panic!("lexer did not skip the invalid identifiers")
+        }
+        match lexer.get_token() {
+            Ok(Token::EOF) => {},
+            _ => panic!("comment at the end of file is not skipped")
+        }
+    }
 }
\ No newline at end of file
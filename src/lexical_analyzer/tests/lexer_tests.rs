@@ -260,4 +260,147 @@ mod lexer_test {
             assert_eq!(peek.unwrap(), actual.unwrap());
         }
     }
+
+    #[test]
+    pub fn token_span_test() {
+        let program = String::from("(at ?x) ; comment\n  (hold ?x ?y)").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        for _ in 0..4 {
+            let _ = lexer.get_token();
+        }
+        let _ = lexer.lookahead();
+        let pos = lexer.get_last_token_position();
+        assert_eq!((pos.line, pos.column, pos.offset, pos.end), (2, 3, 20, 21));
+        let _ = lexer.get_token();
+        match lexer.get_token() {
+            Ok(Token::Identifier("hold")) => {},
+            _ => panic!("wrong token")
+        }
+        let pos = lexer.get_last_token_position();
+        assert_eq!((pos.line, pos.column, pos.offset, pos.end), (2, 4, 21, 25));
+        let _ = lexer.get_token();
+        match lexer.get_token() {
+            Ok(Token::Identifier("y")) => {},
+            _ => panic!("wrong token")
+        }
+        let pos = lexer.get_last_token_position();
+        assert_eq!(&program[pos.offset..pos.end], "?y".as_bytes());
+        assert_eq!(pos.column, 12);
+    }
+
+    #[test]
+    pub fn comment_collection_test() {
+        let program = String::from("; first\n(at ?x) ; second\n  (hold ?x)").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        while !matches!(lexer.get_token(), Ok(Token::EOF)) {}
+        let comments = lexer.get_comments();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].1, "; first");
+        assert_eq!(comments[1].1, "; second");
+        let pos = comments[1].0;
+        assert_eq!((pos.line, pos.column), (2, 9));
+    }
+
+    #[test]
+    pub fn lossless_tokens_test() {
+        let source = "; first\n(at ?x) ; second\n\t(hold ?x)  \n";
+        let program = String::from(source).into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let tokens = lexer.tokenize_lossless();
+        let text: String = tokens.iter().map(|token| token.to_string()).collect();
+        assert_eq!(text, source);
+        assert_eq!(tokens[0].leading.len(), 2);
+        assert_eq!(tokens[0].leading[0].kind, TriviaKind::Comment);
+        assert_eq!(tokens[0].leading[1].kind, TriviaKind::Newline);
+        // the comment after "(at ?x)" trails its ')'
+        assert_eq!(tokens[3].text, ")");
+        let trailing: Vec<&str> = tokens[3].trailing.iter().map(|trivia| trivia.text).collect();
+        assert_eq!(trailing, vec![" ", "; second"]);
+        assert_eq!(tokens[3].trailing[1].position.line, 2);
+        assert_eq!(tokens[4].leading[1].text, "\t");
+        assert_eq!(tokens[4].position.line, 3);
+        // the end of file holds the newline after the last token
+        let eof = tokens.last().unwrap();
+        assert_eq!(eof.token, Token::EOF);
+        assert_eq!(eof.leading[0].kind, TriviaKind::Newline);
+    }
+
+    #[test]
+    pub fn lossless_unknown_tokens_test() {
+        let source = "(:requirements :foo-bar :typing) ?x$y\n";
+        let program = String::from(source).into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let tokens = lexer.tokenize_lossless();
+        let text: String = tokens.iter().map(|token| token.to_string()).collect();
+        assert_eq!(text, source);
+        // lexing resumes after the invalid lexemes
+        assert_eq!(tokens[2].token, Token::Unknown(":foo-bar"));
+        assert_eq!((tokens[2].position.column, tokens[2].position.end), (16, 23));
+        assert_eq!(tokens[3].token, Token::Requirement(RequirementType::TypedObjects));
+        assert_eq!(tokens[5].token, Token::Unknown("?x$y"));
+        assert_eq!(tokens[6].token, Token::EOF);
+    }
+
+    #[test]
+    pub fn numeric_tokens_test() {
+        let program = String::from("(increase (total-cost) 2.5) (= (road-length a b) 7) (* 3 x1)").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let mut tokens = vec![];
+        loop {
+            match lexer.get_token() {
+                Ok(Token::EOF) => break,
+                Ok(token) => tokens.push(token),
+                Err(error) => panic!("{:?}", error),
+            }
+        }
+        assert_eq!(tokens[1], Token::Operator(OperationType::Increase));
+        assert_eq!(tokens[5], Token::Number(2.5));
+        assert_eq!(tokens[8], Token::Operator(OperationType::Equal));
+        assert_eq!(tokens[14], Token::Number(7.0));
+        assert_eq!(tokens[17], Token::Operator(OperationType::Times));
+        assert_eq!(tokens[18], Token::Number(3.0));
+        assert_eq!(tokens[19], Token::Identifier("x1"));
+    }
+    #[test]
+    pub fn requirement_catalogue_test() {
+        let requirements = [
+            RequirementType::Strips,
+            RequirementType::TypedObjects,
+            RequirementType::NegativePreconditions,
+            RequirementType::DisjunctivePreconditions,
+            RequirementType::Equality,
+            RequirementType::ExistentialPreconditions,
+            RequirementType::UniversalPreconditions,
+            RequirementType::QuantifiedPreconditions,
+            RequirementType::ConditionalEffects,
+            RequirementType::Fluents,
+            RequirementType::NumericFluents,
+            RequirementType::ObjectFluents,
+            RequirementType::Adl,
+            RequirementType::DurativeActions,
+            RequirementType::DurationInequalities,
+            RequirementType::ContinuousEffects,
+            RequirementType::DerivedPredicates,
+            RequirementType::TimedInitialLiterals,
+            RequirementType::Preferences,
+            RequirementType::ActionCosts,
+            RequirementType::Hierarchy,
+            RequirementType::MethodPreconditions,
+        ];
+        let program = requirements
+            .iter()
+            .map(|requirement| requirement.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+            .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        for requirement in requirements {
+            assert_eq!(lexer.get_token().unwrap(), Token::Requirement(requirement));
+        }
+        // composite requirements expand to their components
+        let adl = RequirementType::Adl.expand();
+        assert!(adl.contains(&RequirementType::ConditionalEffects));
+        assert!(adl.contains(&RequirementType::ExistentialPreconditions));
+        assert!(!adl.contains(&RequirementType::Hierarchy));
+    }
 }
\ No newline at end of file
@@ -1,6 +1,19 @@
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+// location of a token in the source file
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct TokenPosition {
     pub line: u32,
+    // 1-based, counted in bytes from the start of the line
+    pub column: u32,
+    // byte offsets of the first character, and the one after the last
+    pub offset: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for TokenPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
 }
\ No newline at end of file
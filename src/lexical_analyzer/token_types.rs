@@ -1,12 +1,16 @@
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub enum Token<'a> {
     Keyword(KeywordName),
     Identifier(&'a str),
+    Number(f64),
     Operator(OperationType),
     Punctuator(PunctuationType),
     Requirement(RequirementType),
+    // a lexeme with a lexical error (only produced by "tokenize_lossless")
+    Unknown(&'a str),
     EOF
 }
 
@@ -15,9 +19,11 @@ impl <'a> fmt::Display for Token<'a> {
         match self {
             Token::Keyword(keyword) => write!(fmt, "Keyword {}", keyword),
             Token::Identifier(id) => write!(fmt, "Identifier {}", id),
+            Token::Number(number) => write!(fmt, "Number {}", number),
             Token::Operator(op) => write!(fmt, "{}", op),
             Token::Punctuator(punc) => write!(fmt, "{}", punc),
             Token::Requirement(req) => write!(fmt, "Requirement {}", req),
+            Token::Unknown(lexeme) => write!(fmt, "Unknown {}", lexeme),
             Token::EOF => write!(fmt, "End of file"),
         }
     }
@@ -56,6 +62,16 @@ pub enum OperationType {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    // Arithmetic ("-" is a punctuator)
+    Plus,
+    Times,
+    Divide,
+    // Numeric Effects
+    Assign,
+    Increase,
+    Decrease,
+    ScaleUp,
+    ScaleDown,
 }
 
 impl fmt::Display for OperationType {
@@ -73,18 +89,45 @@ impl fmt::Display for OperationType {
             OperationType::GreaterThan => write!(fmt, ">"),
             OperationType::LessThanOrEqual => write!(fmt, "<="),
             OperationType::GreaterThanOrEqual => write!(fmt, ">="),
+            OperationType::Plus => write!(fmt, "+"),
+            OperationType::Times => write!(fmt, "*"),
+            OperationType::Divide => write!(fmt, "/"),
+            OperationType::Assign => write!(fmt, "assign"),
+            OperationType::Increase => write!(fmt, "increase"),
+            OperationType::Decrease => write!(fmt, "decrease"),
+            OperationType::ScaleUp => write!(fmt, "scale-up"),
+            OperationType::ScaleDown => write!(fmt, "scale-down"),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize)]
 pub enum RequirementType {
+    // HDDL
     MethodPreconditions,
     Hierarchy,
+    // PDDL
+    Strips,
     TypedObjects,
     NegativePreconditions,
+    DisjunctivePreconditions,
+    ExistentialPreconditions,
     UniversalPreconditions,
-    Equality
+    QuantifiedPreconditions,
+    ConditionalEffects,
+    Equality,
+    Adl,
+    Fluents,
+    NumericFluents,
+    ObjectFluents,
+    ActionCosts,
+    DurativeActions,
+    DurationInequalities,
+    ContinuousEffects,
+    DerivedPredicates,
+    TimedInitialLiterals,
+    Preferences,
+    Constraints,
 }
 
 impl fmt::Display for RequirementType {
@@ -92,15 +135,64 @@ impl fmt::Display for RequirementType {
         let requirement = match self {
             RequirementType::MethodPreconditions => ":method-preconditions",
             RequirementType::Hierarchy => ":hierarchy",
+            RequirementType::Strips => ":strips",
             RequirementType::TypedObjects => ":typing",
             RequirementType::NegativePreconditions => ":negative-preconditions",
+            RequirementType::DisjunctivePreconditions => ":disjunctive-preconditions",
+            RequirementType::ExistentialPreconditions => ":existential-preconditions",
             RequirementType::UniversalPreconditions => ":universal-preconditions",
+            RequirementType::QuantifiedPreconditions => ":quantified-preconditions",
+            RequirementType::ConditionalEffects => ":conditional-effects",
             RequirementType::Equality => ":equality",
+            RequirementType::Adl => ":adl",
+            RequirementType::Fluents => ":fluents",
+            RequirementType::NumericFluents => ":numeric-fluents",
+            RequirementType::ObjectFluents => ":object-fluents",
+            RequirementType::ActionCosts => ":action-costs",
+            RequirementType::DurativeActions => ":durative-actions",
+            RequirementType::DurationInequalities => ":duration-inequalities",
+            RequirementType::ContinuousEffects => ":continuous-effects",
+            RequirementType::DerivedPredicates => ":derived-predicates",
+            RequirementType::TimedInitialLiterals => ":timed-initial-literals",
+            RequirementType::Preferences => ":preferences",
+            RequirementType::Constraints => ":constraints",
         };
         write!(f, "{}", requirement)
     }
 }
 
+impl RequirementType {
+    // the requirements that declaring this one amounts to (composite
+    // requirements, such as ":adl", expand to their components)
+    pub fn expand(&self) -> Vec<RequirementType> {
+        match self {
+            RequirementType::QuantifiedPreconditions => vec![
+                RequirementType::QuantifiedPreconditions,
+                RequirementType::ExistentialPreconditions,
+                RequirementType::UniversalPreconditions,
+            ],
+            RequirementType::Fluents => vec![
+                RequirementType::Fluents,
+                RequirementType::NumericFluents,
+                RequirementType::ObjectFluents,
+            ],
+            RequirementType::Adl => {
+                let mut requirements = vec![
+                    RequirementType::Adl,
+                    RequirementType::Strips,
+                    RequirementType::TypedObjects,
+                    RequirementType::DisjunctivePreconditions,
+                    RequirementType::Equality,
+                    RequirementType::ConditionalEffects,
+                ];
+                requirements.extend(RequirementType::QuantifiedPreconditions.expand());
+                requirements
+            }
+            requirement => vec![*requirement],
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum KeywordName {
     Define,
@@ -123,7 +215,12 @@ pub enum KeywordName {
     OrderedSubtasks, // either "ordered-tasks" or "ordered-subtasks"
     Ordering,
     Constraints,
-    Goal
+    Goal,
+    Functions,
+    Metric,
+    DurativeAction,
+    Duration,
+    Condition
 }
 
 impl fmt::Display for KeywordName {
@@ -150,6 +247,11 @@ impl fmt::Display for KeywordName {
             KeywordName::Ordering => ":ordering",
             KeywordName::Constraints => ":constraints",
             KeywordName::Goal => ":goal",
+            KeywordName::Functions => ":functions",
+            KeywordName::Metric => ":metric",
+            KeywordName::DurativeAction => ":durative-action",
+            KeywordName::Duration => ":duration",
+            KeywordName::Condition => ":condition",
         };
         write!(f, "{}", keyword)
     }
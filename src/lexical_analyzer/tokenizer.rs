@@ -1,12 +1,17 @@
 
-use std::{cell::Cell, str::from_utf8};
+use std::{cell::{Cell, RefCell}, str::from_utf8};
 
 use super::*;
 
 pub struct LexicalAnalyzer<'a> {
     program: &'a Vec<u8>,
     cursor: Cell<usize>,
+    // current line, and the offset at which it starts
+    line: Cell<u32>,
+    line_start: Cell<usize>,
     last_token_pos: Cell<TokenPosition>,
+    // span of the comments that are skipped so far (including the leading ';')
+    comments: RefCell<Vec<TokenPosition>>,
 }
 
 impl <'a> LexicalAnalyzer <'a> {
@@ -14,9 +19,15 @@ impl <'a> LexicalAnalyzer <'a> {
         LexicalAnalyzer {
             program,
             cursor: Cell::new(0),
+            line: Cell::new(1),
+            line_start: Cell::new(0),
             last_token_pos: Cell::new(TokenPosition {
                 line: 1,
+                column: 1,
+                offset: 0,
+                end: 0,
             }),
+            comments: RefCell::new(vec![]),
         }
     }
     // get the next token without advancing the cursor
@@ -28,48 +39,88 @@ impl <'a> LexicalAnalyzer <'a> {
         self.last_token_pos.get()
     }
 
-    pub fn get_token(&self) -> Result<Token, LexicalError> {
+    pub fn get_comments(&self) -> Vec<(TokenPosition, &'a str)> {
+        self.comments
+            .borrow()
+            .iter()
+            .map(|pos| (*pos, from_utf8(&self.program[pos.offset..pos.end]).unwrap_or_default()))
+            .collect()
+    }
+
+    pub fn get_token(&self) -> Result<Token<'a>, LexicalError> {
         return self.parse(false);
     }
 
-    fn parse(&self, peek: bool) -> Result<Token, LexicalError> {
+    // lexes the rest of the program, and keeps the whitespaces and comments around
+    // each token. The last token is EOF, which holds the trivia at the end of the file.
+    // Lexemes with lexical errors (e.g., unknown requirements) are unknown tokens.
+    pub fn tokenize_lossless(&self) -> Vec<LosslessToken<'a>> {
+        let mut tokens = vec![];
+        loop {
+            let leading = self.read_trivia(false);
+            let result = self.get_token();
+            let position = self.get_last_token_position();
+            let text = self.text(position.offset, position.end);
+            // the cursor is past the invalid lexeme, so lexing resumes after it
+            let token = result.unwrap_or(Token::Unknown(text));
+            let trailing = self.read_trivia(true);
+            let is_eof = token == Token::EOF;
+            tokens.push(LosslessToken {
+                token,
+                text,
+                position,
+                leading,
+                trailing,
+            });
+            if is_eof {
+                return tokens;
+            }
+        }
+    }
+
+    fn parse(&self, peek: bool) -> Result<Token<'a>, LexicalError> {
         self.skip_whitespaces();
-        if self.cursor.get() == self.program.len() {
-            return Ok(Token::EOF);
+        let start = self.cursor.get();
+        let result = self.next_token();
+        let position = TokenPosition {
+            line: self.line.get(),
+            column: (start - self.line_start.get() + 1) as u32,
+            offset: start,
+            end: self.cursor.get(),
+        };
+        self.last_token_pos.set(position);
+        if peek {
+            self.cursor.set(start);
         }
+        result.map_err(|mut error| {
+            error.position = position;
+            error
+        })
+    }
+
+    // reads the token at the cursor, and advances the cursor past it
+    fn next_token(&self) -> Result<Token<'a>, LexicalError> {
         if let Some(char) = self.peek_next_char() {
-            if !peek {
-                self.cursor.set(self.cursor.get() + 1);
-            }
+            self.cursor.set(self.cursor.get() + 1);
             match char {
                 // Punctuations
                 '-' => Ok(Token::Punctuator(PunctuationType::Dash)),
                 '(' => Ok(Token::Punctuator(PunctuationType::LParentheses)),
                 ')' => Ok(Token::Punctuator(PunctuationType::RParentheses)),
                 // Ordering Relations
-                p @ ('<' | '>' | '=') => Ok(Token::Operator(self.ordering_type(&p, peek))),
+                p @ ('<' | '>' | '=') => Ok(Token::Operator(self.ordering_type(&p))),
+                // Arithmetic Operations
+                '+' => Ok(Token::Operator(OperationType::Plus)),
+                '*' => Ok(Token::Operator(OperationType::Times)),
+                '/' => Ok(Token::Operator(OperationType::Divide)),
                 // Variables
                 '?' => {
-                    let mut init_cur_pos = self.cursor.get();
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (var_name, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
-                    }
+                    let var_name = self.read_lexeme(self.cursor.get())?;
                     Ok(Token::Identifier(var_name))
                 }
                 // Keywords (Note that 2 keywords, namely "domain" and "problem", can start without ':' as well)
                 ':' => {
-                    let mut init_cur_pos = self.cursor.get();
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (lexeme, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
-                    }
+                    let lexeme = self.read_lexeme(self.cursor.get())?;
                     match lexeme {
                         // Requirements
                         "negative-preconditions" => Ok(Token::Requirement(
@@ -82,6 +133,23 @@ impl <'a> LexicalAnalyzer <'a> {
                         )),
                         "typing" => Ok(Token::Requirement(RequirementType::TypedObjects)),
                         "universal-preconditions" => Ok(Token::Requirement(RequirementType::UniversalPreconditions)),
+                        "numeric-fluents" => Ok(Token::Requirement(RequirementType::NumericFluents)),
+                        "action-costs" => Ok(Token::Requirement(RequirementType::ActionCosts)),
+                        "durative-actions" => Ok(Token::Requirement(RequirementType::DurativeActions)),
+                        "strips" => Ok(Token::Requirement(RequirementType::Strips)),
+                        "disjunctive-preconditions" => Ok(Token::Requirement(RequirementType::DisjunctivePreconditions)),
+                        "existential-preconditions" => Ok(Token::Requirement(RequirementType::ExistentialPreconditions)),
+                        "quantified-preconditions" => Ok(Token::Requirement(RequirementType::QuantifiedPreconditions)),
+                        "conditional-effects" => Ok(Token::Requirement(RequirementType::ConditionalEffects)),
+                        "adl" => Ok(Token::Requirement(RequirementType::Adl)),
+                        "fluents" => Ok(Token::Requirement(RequirementType::Fluents)),
+                        "object-fluents" => Ok(Token::Requirement(RequirementType::ObjectFluents)),
+                        "duration-inequalities" => Ok(Token::Requirement(RequirementType::DurationInequalities)),
+                        "continuous-effects" => Ok(Token::Requirement(RequirementType::ContinuousEffects)),
+                        "derived-predicates" => Ok(Token::Requirement(RequirementType::DerivedPredicates)),
+                        "timed-initial-literals" => Ok(Token::Requirement(RequirementType::TimedInitialLiterals)),
+                        "preferences" => Ok(Token::Requirement(RequirementType::Preferences)),
+                        // ":constraints" is a keyword of methods as well (see "parse_requirements")
                         // Keywords
                         "requirements" => Ok(Token::Keyword(KeywordName::Requirements)),
                         "objects" => Ok(Token::Keyword(KeywordName::Objects)),
@@ -92,6 +160,9 @@ impl <'a> LexicalAnalyzer <'a> {
                         "htn" => Ok(Token::Keyword(KeywordName::HTN)),
                         "task" => Ok(Token::Keyword(KeywordName::Task)),
                         "action" => Ok(Token::Keyword(KeywordName::Action)),
+                        "durative-action" => Ok(Token::Keyword(KeywordName::DurativeAction)),
+                        "duration" => Ok(Token::Keyword(KeywordName::Duration)),
+                        "condition" => Ok(Token::Keyword(KeywordName::Condition)),
                         "parameters" => Ok(Token::Keyword(KeywordName::Parameters)),
                         "method" => Ok(Token::Keyword(KeywordName::Method)),
                         "precondition" => Ok(Token::Keyword(KeywordName::Precondition)),
@@ -103,34 +174,25 @@ impl <'a> LexicalAnalyzer <'a> {
                         "ordering" | "order" => Ok(Token::Keyword(KeywordName::Ordering)),
                         "constraints" => Ok(Token::Keyword(KeywordName::Constraints)),
                         "goal" => Ok(Token::Keyword(KeywordName::Goal)),
+                        "functions" => Ok(Token::Keyword(KeywordName::Functions)),
+                        "metric" => Ok(Token::Keyword(KeywordName::Metric)),
                         "domain" => return Ok(Token::Keyword(KeywordName::Domain)),
                         "problem" => return Ok(Token::Keyword(KeywordName::Problem)),
                         _ => Err(LexicalError {
                             error_type: LexicalErrorType::InvalidKeyword,
                             lexeme: lexeme.to_string(),
-                            position: self.last_token_pos.get()
+                            // set by the caller
+                            position: TokenPosition::default()
                         }),
                     }
                 }
-                // Comment
-                ';' => {
-                        let mut current = self.program[self.cursor.get()] as char;
-                        while current != '\n' {
-                            self.cursor.set(self.cursor.get() + 1);
-                            current = self.program[self.cursor.get()] as char;
-                        }
-                        return self.parse(peek);
-                }
                 // Other
                 _ => {
-                    let mut init_cur_pos = self.cursor.get() - 1;
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (lexeme, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
+                    // Numbers (note that identifiers may start with a digit as well)
+                    if let Some(number) = self.read_number(self.cursor.get() - 1) {
+                        return Ok(Token::Number(number));
                     }
+                    let lexeme = self.read_lexeme(self.cursor.get() - 1)?;
                     match lexeme {
                         // Remaining Keywords
                         "define" => return Ok(Token::Keyword(KeywordName::Define)),
@@ -148,7 +210,7 @@ impl <'a> LexicalAnalyzer <'a> {
                                         Err(LexicalError {
                                             error_type: LexicalErrorType::InvalidIdentifier,
                                             lexeme: lexeme.to_string(),
-                                            position: self.last_token_pos.get()
+                                            position: TokenPosition::default()
                                         })
                                     }
                                 }
@@ -162,10 +224,10 @@ impl <'a> LexicalAnalyzer <'a> {
         }
     }
 
-    // get next lexeme and new cursor position (to commit peek)
-    fn peek_lexeme(&self, init_cur_pos: usize) -> Result<(&str, usize), LexicalError> {
+    // reads the lexeme starting at "init_cur_pos", and advances the cursor past it
+    // (invalid lexemes are skipped as well, so that lexing can resume after them)
+    fn read_lexeme(&self, init_cur_pos: usize) -> Result<&'a str, LexicalError> {
         let mut cursor_pos = init_cur_pos;
-        let mut next_ch = self.program[cursor_pos] as char;
         let mut is_invalid = false;
         let mut is_valid_character = |c| match c {
             '_' | '-' => true,
@@ -181,23 +243,40 @@ impl <'a> LexicalAnalyzer <'a> {
                 }
             }
         };
-        while is_valid_character(next_ch) {
-            if cursor_pos < self.program.len() - 1 {
-                cursor_pos += 1;
-                next_ch = self.program[cursor_pos] as char;
-            } else {
-                break;
-            }
+        while cursor_pos < self.program.len() && is_valid_character(self.program[cursor_pos] as char) {
+            cursor_pos += 1;
         }
+        self.cursor.set(cursor_pos);
+        let lexeme = from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap();
         if is_invalid {
             return Err(LexicalError {
                 error_type: LexicalErrorType::InvalidIdentifier,
-                lexeme: from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap().to_string(),
-                position: self.last_token_pos.get()
+                lexeme: lexeme.to_string(),
+                // set by the caller
+                position: TokenPosition::default()
             })
         } else {
-            return Ok((from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap(), cursor_pos))
+            return Ok(lexeme)
+        }
+    }
+
+    // reads the number starting at "init_cur_pos" (if any), and advances the cursor past it
+    fn read_number(&self, init_cur_pos: usize) -> Option<f64> {
+        let mut cursor_pos = init_cur_pos;
+        while cursor_pos < self.program.len()
+            && (self.program[cursor_pos].is_ascii_digit() || self.program[cursor_pos] == b'.')
+        {
+            cursor_pos += 1;
+        }
+        if cursor_pos < self.program.len() {
+            let next = self.program[cursor_pos] as char;
+            if !LexicalAnalyzer::is_whitespace(&next) && next != '(' && next != ')' {
+                return None;
+            }
         }
+        let number = from_utf8(&self.program[init_cur_pos..cursor_pos]).ok()?.parse().ok()?;
+        self.cursor.set(cursor_pos);
+        Some(number)
     }
 
     fn peek_next_char(&self) -> Option<char> {
@@ -208,18 +287,86 @@ impl <'a> LexicalAnalyzer <'a> {
         Some(current)
     }
 
+    // skips whitespaces and comments
     fn skip_whitespaces(&self) {
         while self.cursor.get() != self.program.len() {
             let current = self.program[self.cursor.get()] as char;
-            if !LexicalAnalyzer::is_whitespace(&current) {
-                break;
+            if current == ';' {
+                self.skip_comment();
+                continue;
             } else if current == '\n' {
-                self.last_token_pos.set(TokenPosition { 
-                    line: self.last_token_pos.get().line + 1 
-                });
+                self.line.set(self.line.get() + 1);
+                self.line_start.set(self.cursor.get() + 1);
+            } else if !LexicalAnalyzer::is_whitespace(&current) {
+                break;
+            }
+            self.cursor.set(self.cursor.get() + 1);
+        }
+    }
+
+    // reads the whitespaces and comments at the cursor (trailing trivia ends before
+    // the next newline)
+    fn read_trivia(&self, trailing: bool) -> Vec<Trivia<'a>> {
+        let mut trivia = vec![];
+        while let Some(current) = self.peek_next_char() {
+            let start = self.cursor.get();
+            let line = self.line.get();
+            let column = (start - self.line_start.get() + 1) as u32;
+            let kind = match current {
+                ';' => {
+                    self.skip_comment();
+                    TriviaKind::Comment
+                }
+                '\n' if trailing => break,
+                '\n' => {
+                    self.cursor.set(start + 1);
+                    self.line.set(line + 1);
+                    self.line_start.set(start + 1);
+                    TriviaKind::Newline
+                }
+                c if LexicalAnalyzer::is_whitespace(&c) => {
+                    while let Some(' ' | '\t' | '\r') = self.peek_next_char() {
+                        self.cursor.set(self.cursor.get() + 1);
+                    }
+                    TriviaKind::Whitespace
+                }
+                _ => break,
+            };
+            let end = self.cursor.get();
+            trivia.push(Trivia {
+                kind,
+                text: self.text(start, end),
+                position: TokenPosition {
+                    line,
+                    column,
+                    offset: start,
+                    end,
+                },
+            });
+        }
+        trivia
+    }
+
+    fn text(&self, start: usize, end: usize) -> &'a str {
+        from_utf8(&self.program[start..end]).unwrap_or_default()
+    }
+
+    // records the comment at the cursor, and moves the cursor to the end of its line
+    fn skip_comment(&self) {
+        let start = self.cursor.get();
+        while let Some(current) = self.peek_next_char() {
+            if current == '\n' {
+                break;
             }
             self.cursor.set(self.cursor.get() + 1);
         }
+        let position = TokenPosition {
+            line: self.line.get(),
+            column: (start - self.line_start.get() + 1) as u32,
+            offset: start,
+            end: self.cursor.get(),
+        };
+        self.comments.borrow_mut().push(position);
     }
 
     fn is_logical_operator(word: &str) -> Option<OperationType> {
@@ -230,19 +377,23 @@ impl <'a> LexicalAnalyzer <'a> {
             "not" => Some(OperationType::Not),
             "forall" => Some(OperationType::ForAll),
             "exists" => Some(OperationType::Exists),
-            "imply" => Some(OperationType::Implication),
+            "imply" | "when" => Some(OperationType::Implication),
+            // Numeric Effects
+            "assign" => Some(OperationType::Assign),
+            "increase" => Some(OperationType::Increase),
+            "decrease" => Some(OperationType::Decrease),
+            "scale-up" => Some(OperationType::ScaleUp),
+            "scale-down" => Some(OperationType::ScaleDown),
             _ => None,
         }
     }
 
-    fn ordering_type(&self, c: &char, peek: bool) -> OperationType {
+    fn ordering_type(&self, c: &char) -> OperationType {
         match c {
             '<' => {
                 match self.peek_next_char() {
                     Some('=') => {
-                        if !peek {
-                            self.cursor.set(self.cursor.get() + 1);
-                        }
+                        self.cursor.set(self.cursor.get() + 1);
                         OperationType::LessThanOrEqual
                     }
                     _ => OperationType::LessThan,
@@ -251,9 +402,7 @@ impl <'a> LexicalAnalyzer <'a> {
             '>' => {
                 match self.peek_next_char() {
                     Some('=') => {
-                        if !peek {
-                            self.cursor.set(self.cursor.get() + 1);
-                        }
+                        self.cursor.set(self.cursor.get() + 1);
                         OperationType::GreaterThanOrEqual
                     }
                     _ => OperationType::GreaterThan,
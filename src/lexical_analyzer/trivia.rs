@@ -0,0 +1,54 @@
+use std::fmt;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    // spaces, tabs and carriage returns
+    Whitespace,
+    Newline,
+    // including the leading ';'
+    Comment,
+}
+
+// source text between two tokens
+#[derive(Debug, Clone, Copy)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: &'a str,
+    pub position: TokenPosition,
+}
+
+// a token along with the trivia around it. The trailing trivia runs up to the end
+// of the token's line, and the leading trivia covers the rest, so that the text of
+// all tokens (in order) is the whole source.
+#[derive(Debug)]
+pub struct LosslessToken<'a> {
+    pub token: Token<'a>,
+    pub text: &'a str,
+    pub position: TokenPosition,
+    pub leading: Vec<Trivia<'a>>,
+    pub trailing: Vec<Trivia<'a>>,
+}
+
+impl<'a> LosslessToken<'a> {
+    pub fn comments(&self) -> impl Iterator<Item = &Trivia<'a>> {
+        self.leading
+            .iter()
+            .chain(self.trailing.iter())
+            .filter(|trivia| trivia.kind == TriviaKind::Comment)
+    }
+}
+
+impl<'a> fmt::Display for LosslessToken<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for trivia in self.leading.iter() {
+            write!(f, "{}", trivia.text)?;
+        }
+        write!(f, "{}", self.text)?;
+        for trivia in self.trailing.iter() {
+            write!(f, "{}", trivia.text)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
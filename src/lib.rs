@@ -2,46 +2,92 @@ mod lexical_analyzer;
 mod syntactic_analyzer;
 mod semantic_analyzer;
 mod output;
+mod plan_verifier;
+mod formatter;
 
-use crate::lexical_analyzer::TokenPosition;
+use std::collections::HashSet;
+
+use output::RecursionType;
 use lexical_analyzer::LexicalAnalyzer;
-use output::{MetaData, ParsingError};
+pub use output::{
+    diagnostics_to_json, diagnostics_to_sarif, find_warning, ground_model_to_json, metadata_to_json,
+    plan_errors_to_json, simulation_to_json, Diagnostics, GroundInstance, GroundMethod, GroundModel, InputFile,
+    Level, MetaData, ParsingError, PlanError, PlanErrorType, Renderer, Rule, Severity, SimulationReport, Style,
+    TraceEntry,
+};
+pub use lexical_analyzer::{KeywordName, LosslessToken, Token, TokenPosition, Trivia, TriviaKind};
+pub use syntactic_analyzer::{SyntaxElement, SyntaxKind, SyntaxNode};
+use plan_verifier::{Plan, PlanVerifier, Simulator};
+use formatter::Writer;
 use semantic_analyzer::*;
-use syntactic_analyzer::AbstractSyntaxTree;
+use syntactic_analyzer::{AbstractSyntaxTree, DomainAST, ProblemAST};
 
 pub struct HDDLAnalyzer {}
 
 impl HDDLAnalyzer {
-    pub fn verify(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<Vec<output::WarningType>, output::ParsingError> {
+    // verifies the domain (and the problem, if given), and collects all errors
+    // and warnings. "limit" bounds the number of reported diagnostics.
+    pub fn verify(domain: &Vec<u8>, problem: Option<&Vec<u8>>, limit: Option<usize>) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new(limit);
+        HDDLAnalyzer::analyze(domain, problem, &mut diagnostics);
+        diagnostics
+    }
+
+    // same as "verify", but reports to the given diagnostics (e.g., to set the
+    // level of warnings beforehand)
+    pub fn analyze(domain: &Vec<u8>, problem: Option<&Vec<u8>>, diagnostics: &mut Diagnostics) {
         let lexer = LexicalAnalyzer::new(&domain);
         let domain_parser = syntactic_analyzer::Parser::new(lexer);
-        let domain_ast = domain_parser.parse()?;
-        if let AbstractSyntaxTree::Domain(d) = domain_ast {
-            let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d);
-            let symbol_table = domain_semantic_verifier.verify_domain()?;
-            match problem {
-                Some(p) => {
-                    let lexer = LexicalAnalyzer::new(p);
-                    let problem_parser = syntactic_analyzer::Parser::new(lexer);
-                    let problem_ast = problem_parser.parse()?;
-                    match problem_ast {
-                        AbstractSyntaxTree::Problem(p_ast) => {
-                            let problem_semantic_verifier = ProblemSemanticAnalyzer::new(
-                                &p_ast,
-                                symbol_table
-                            );
-                            let warnings= problem_semantic_verifier.verify_problem()?;
-                            Ok(warnings)
-
-                        }
-                        _ => {
-                            panic!("expected problem, found domain")
-                        }
+        let (domain_ast, domain_errors) = domain_parser.parse_with_recovery();
+        let problem_parser = problem.map(|p| {
+            let lexer = LexicalAnalyzer::new(p);
+            syntactic_analyzer::Parser::new(lexer)
+        });
+        let (problem_ast, problem_errors) = match &problem_parser {
+            Some(parser) => parser.parse_with_recovery(),
+            None => (None, vec![]),
+        };
+        // warning levels set in the comments of each file
+        diagnostics.add_directives(InputFile::Domain, &domain_parser.tokenizer.get_comments());
+        if let Some(parser) = &problem_parser {
+            diagnostics.add_directives(InputFile::Problem, &parser.tokenizer.get_comments());
+        }
+        // semantic analysis is only done on complete syntax trees
+        if !domain_errors.is_empty() || !problem_errors.is_empty() {
+            for error in domain_errors {
+                diagnostics.add_error(error);
+            }
+            diagnostics.set_file(InputFile::Problem);
+            for error in problem_errors {
+                diagnostics.add_error(error);
+            }
+            return;
+        }
+        if let Some(AbstractSyntaxTree::Domain(d)) = domain_ast {
+            match problem_ast {
+                Some(AbstractSyntaxTree::Problem(p_ast)) => {
+                    let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d).with_problem(&p_ast);
+                    let symbol_table = domain_semantic_verifier.analyze(diagnostics);
+                    let grounder = Grounder::new(&d, &p_ast, &symbol_table);
+                    diagnostics.set_file(InputFile::Problem);
+                    let problem_semantic_verifier = ProblemSemanticAnalyzer::new(
+                        &p_ast,
+                        symbol_table
+                    );
+                    problem_semantic_verifier.analyze(diagnostics);
+                    // reachability is only analyzed if both files are free of errors
+                    if !diagnostics.has_errors() && !diagnostics.is_full() {
+                        diagnostics.set_file(InputFile::Domain);
+                        check_reachability(&d, &grounder, diagnostics);
                     }
-                },
-                None => Ok(
-                    symbol_table.warnings
-                )
+                }
+                Some(_) => {
+                    panic!("expected problem, found domain")
+                }
+                None => {
+                    let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d);
+                    domain_semantic_verifier.analyze(diagnostics);
+                }
             }
         } else {
             panic!("expected domain, found problem")
@@ -49,25 +95,200 @@ impl HDDLAnalyzer {
     }
 
 
+    // returns the predicates of the domain that no action changes (in declaration order)
+    pub fn get_static_predicates(domain: &Vec<u8>) -> Result<Vec<String>, ParsingError> {
+        let lexer = LexicalAnalyzer::new(domain);
+        let domain_parser = syntactic_analyzer::Parser::new(lexer);
+        let domain_ast = domain_parser.parse()?;
+        match domain_ast {
+            AbstractSyntaxTree::Domain(d) => {
+                let static_predicates = static_predicates(&d);
+                let mut result: Vec<String> = vec![];
+                for predicate in d.predicates.iter() {
+                    if static_predicates.contains(predicate.name) && !result.iter().any(|p| p == predicate.name) {
+                        result.push(predicate.name.to_string());
+                    }
+                }
+                Ok(result)
+            }
+            _ => panic!("expected domain, found problem")
+        }
+    }
+
+    // rewrites a domain (or problem) with a canonical layout, keeping its comments
+    pub fn format(program: &Vec<u8>) -> Result<String, ParsingError> {
+        let parser = syntactic_analyzer::Parser::new(LexicalAnalyzer::new(program));
+        let ast = parser.parse()?;
+        // the comments are taken from the lossless syntax tree
+        let document = HDDLAnalyzer::parse_lossless(program)?;
+        // the first token of the file, and the parenthesis that closes its definition
+        let tokens = document.tokens();
+        let start = tokens[0].position.offset;
+        let end = match document.nodes().next() {
+            Some(definition) => definition.span().1 - 1,
+            None => start,
+        };
+        let writer = Writer::new(program, document.comments(), start, end);
+        match ast {
+            AbstractSyntaxTree::Domain(d) => Ok(writer.write_domain(&d)),
+            AbstractSyntaxTree::Problem(p) => Ok(writer.write_problem(&p)),
+        }
+    }
+
+    // parses a domain (or problem) into a syntax tree that keeps its whitespaces and
+    // comments, so that tools can rewrite parts of it and leave the rest untouched
+    pub fn parse_lossless(program: &Vec<u8>) -> Result<SyntaxNode<'_>, ParsingError> {
+        syntactic_analyzer::Parser::new(LexicalAnalyzer::new(program)).parse_concrete()
+    }
+
     pub fn get_metadata(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<MetaData, ParsingError> {
         let lexer = LexicalAnalyzer::new(&domain);
         let domain_parser = syntactic_analyzer::Parser::new(lexer);
         let domain_ast = domain_parser.parse()?;
         match domain_ast {
             AbstractSyntaxTree::Domain(d) => {
+                let problem_parser = problem.map(|p| {
+                    let lexer = LexicalAnalyzer::new(p);
+                    syntactic_analyzer::Parser::new(lexer)
+                });
+                let problem_ast = match &problem_parser {
+                    Some(parser) => match parser.parse()? {
+                        AbstractSyntaxTree::Problem(p) => Some(p),
+                        _ => panic!("expected problem, found domain")
+                    },
+                    None => None,
+                };
                 let tdg = TDG::new(&d);
                 let nullables = tdg.compute_nullables();
-                let recursion_type= tdg.get_recursion_type(&nullables);
-                Ok(MetaData {
-                    recursion: recursion_type,
-                    nullables: nullables.iter().map(|x| x.to_string()).collect(),
-                    domain_name: String::new(),
-                    n_actions: d.actions.len() as u32,
-                    n_tasks: d.compound_tasks.len() as u32,
-                    n_methods: d.methods.len() as u32
-                })
+                match problem_ast {
+                    None => {
+                        let recursion_type= tdg.get_recursion_type(&nullables);
+                        Ok(MetaData {
+                            recursion: recursion_type,
+                            nullables: nullables.iter().map(|x| x.to_string()).collect(),
+                            domain_name: d.name.clone(),
+                            problem_name: None,
+                            recursive_initial_tasks: vec![],
+                            n_actions: d.actions.len() as u32,
+                            n_tasks: d.compound_tasks.len() as u32,
+                            n_methods: d.methods.len() as u32,
+                            is_temporal: d.actions.iter().any(|action| action.is_durative())
+                        })
+                    }
+                    Some(p) => {
+                        // only the tasks reachable from the initial task network are relevant
+                        // (undefined tasks are reported by the semantic analyzer)
+                        let initial_tasks: Vec<&str> = p
+                            .init_tn
+                            .iter()
+                            .flat_map(|init_tn| init_tn.tn.subtasks.iter())
+                            .map(|subtask| subtask.task.name)
+                            .filter(|task| tdg.contains(task))
+                            .collect();
+                        let mut primitives = HashSet::new();
+                        let mut compounds = HashSet::new();
+                        for task in initial_tasks.iter() {
+                            let reachables = tdg.reachable(task);
+                            primitives.extend(reachables.primitives);
+                            compounds.extend(reachables.compounds);
+                        }
+                        let mut recursive_initial_tasks = vec![];
+                        for task in initial_tasks.iter() {
+                            let recursion_type = tdg.get_recursion_type_from(&nullables, &[task]);
+                            if recursion_type != RecursionType::NonRecursive
+                                && !recursive_initial_tasks.iter().any(|t| t == task)
+                            {
+                                recursive_initial_tasks.push(task.to_string());
+                            }
+                        }
+                        Ok(MetaData {
+                            recursion: tdg.get_recursion_type_from(&nullables, &initial_tasks),
+                            nullables: nullables
+                                .iter()
+                                .filter(|task| compounds.contains(*task))
+                                .map(|x| x.to_string())
+                                .collect(),
+                            domain_name: d.name.clone(),
+                            problem_name: Some(p.name.clone()),
+                            recursive_initial_tasks,
+                            n_actions: primitives.len() as u32,
+                            n_tasks: compounds.len() as u32,
+                            n_methods: d
+                                .methods
+                                .iter()
+                                .filter(|method| compounds.contains(method.task.name))
+                                .count() as u32,
+                            is_temporal: d
+                                .actions
+                                .iter()
+                                .any(|action| action.is_durative() && primitives.contains(action.name))
+                        })
+                    }
+                }
             }
             _ => panic!("expected domain, found problem")
         }
     }
+    // instantiates the domain with the objects of the problem. If either of
+    // them has errors, the diagnostics are returned instead.
+    pub fn ground(domain: &Vec<u8>, problem: &Vec<u8>) -> Result<GroundModel, Box<Diagnostics>> {
+        HDDLAnalyzer::with_analysis(domain, problem, |d, p, symbol_table| {
+            Grounder::new(d, p, symbol_table).ground()
+        })
+    }
+
+    // checks that "plan" (in the IPC format) is a valid solution of the problem, and
+    // returns its errors. If the domain or problem has errors, their diagnostics are
+    // returned instead.
+    pub fn validate_plan(domain: &Vec<u8>, problem: &Vec<u8>, plan: &str) -> Result<Vec<PlanError>, Box<Diagnostics>> {
+        HDDLAnalyzer::with_analysis(domain, problem, |d, p, symbol_table| match Plan::parse(plan) {
+            Ok(plan) => PlanVerifier::new(d, p, symbol_table).verify(&plan),
+            Err(error) => vec![error],
+        })
+    }
+
+    // executes the actions of "plan" (ignoring its hierarchy, if any) from the initial
+    // state, and checks the goal. The report includes every intermediate state if
+    // "trace" is set. If the domain or problem has errors, their diagnostics are
+    // returned instead.
+    pub fn simulate_plan(
+        domain: &Vec<u8>,
+        problem: &Vec<u8>,
+        plan: &str,
+        trace: bool,
+    ) -> Result<SimulationReport, Box<Diagnostics>> {
+        HDDLAnalyzer::with_analysis(domain, problem, |d, p, symbol_table| match Plan::parse(plan) {
+            Ok(plan) => Simulator::new(d, p, symbol_table).simulate(&plan.actions, trace),
+            Err(error) => SimulationReport {
+                executed_steps: 0,
+                total_steps: 0,
+                trace: vec![],
+                errors: vec![error],
+            },
+        })
+    }
+
+    // runs "analysis" on the verified domain and problem, along with the symbol
+    // table of the domain. If either of them has errors, their diagnostics are
+    // returned instead.
+    fn with_analysis<T>(
+        domain: &Vec<u8>,
+        problem: &Vec<u8>,
+        analysis: impl FnOnce(&DomainAST, &ProblemAST, &SymbolTable) -> T,
+    ) -> Result<T, Box<Diagnostics>> {
+        let diagnostics = HDDLAnalyzer::verify(domain, Some(problem), None);
+        if diagnostics.has_errors() {
+            return Err(Box::new(diagnostics));
+        }
+        let domain_parser = syntactic_analyzer::Parser::new(LexicalAnalyzer::new(domain));
+        let problem_parser = syntactic_analyzer::Parser::new(LexicalAnalyzer::new(problem));
+        match (domain_parser.parse(), problem_parser.parse()) {
+            (Ok(AbstractSyntaxTree::Domain(d)), Ok(AbstractSyntaxTree::Problem(p))) => {
+                let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d);
+                let symbol_table = domain_semantic_verifier.analyze(&mut Diagnostics::new(None));
+                Ok(analysis(&d, &p, &symbol_table))
+            }
+            _ => unreachable!("both inputs are verified above")
+        }
+    }
 }
@@ -1,83 +1,335 @@
 mod cli_args;
 
 use clap::Parser;
-use hddl_analyzer::HDDLAnalyzer;
+use hddl_analyzer::{
+    diagnostics_to_json, diagnostics_to_sarif, ground_model_to_json, metadata_to_json, plan_errors_to_json,
+    simulation_to_json, Diagnostics, HDDLAnalyzer, InputFile, Level, Renderer, Severity, Style,
+};
+use std::collections::HashMap;
 use std::fs;
+use std::io::IsTerminal;
 
-use cli_args::{CLIArgs, Commands};
+use cli_args::{CLIArgs, ColorChoice, Commands, MetadataFormat, OutputFormat};
 
 pub fn main() {
-    // ANSI escape color codes
-    let yellow = "\x1b[33m";
-    let green = "\x1b[32m";
-    let red = "\x1b[31m";
-    // ANSI escape code to reset text color
-    let reset = "\x1b[0m";
-
     let args = CLIArgs::parse();
+    let colored = match args.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            std::env::var_os("NO_COLOR").is_none()
+                && std::io::stdout().is_terminal()
+                && std::io::stderr().is_terminal()
+        }
+    };
+    let mut renderer = Renderer::new(colored);
     match args.command {
-        Commands::Metadata(info) => {
-            let domain = fs::read(info.domain_path);
+        Commands::Metadata(args) => {
+            let info = args.input;
+            let domain = fs::read(&info.domain_path);
+            let problem = match &info.problem_path {
+                Some(path) => match fs::read(path) {
+                    Ok(content) => Some(content),
+                    Err(read_error) => {
+                        eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error);
+                        return;
+                    }
+                },
+                None => None,
+            };
             match domain {
-                Ok(domain_content) => match HDDLAnalyzer::get_metadata(&domain_content, None) {
-                    Ok(result) => {
-                        print!("{}", result)
+                Ok(domain_content) => {
+                    let metadata = HDDLAnalyzer::get_metadata(&domain_content, problem.as_ref());
+                    // errors are in the problem only if the domain itself is valid
+                    let error_file = match (&metadata, &problem) {
+                        (Err(_), Some(_)) if HDDLAnalyzer::get_metadata(&domain_content, None).is_ok() => {
+                            InputFile::Problem
+                        }
+                        _ => InputFile::Domain,
+                    };
+                    let mut file_names = HashMap::from([(InputFile::Domain, info.domain_path.as_str())]);
+                    if let Some(path) = &info.problem_path {
+                        file_names.insert(InputFile::Problem, path.as_str());
                     }
-                    Err(error) => {
-                        eprintln!("{}[Error]{} {}", red, reset, error)
+                    match (args.format, metadata) {
+                        (MetadataFormat::Json, Ok(result)) => {
+                            println!("{}", metadata_to_json(&result))
+                        }
+                        (MetadataFormat::Json, Err(error)) => {
+                            let mut diagnostics = Diagnostics::new(None);
+                            diagnostics.set_file(error_file);
+                            diagnostics.add_error(error);
+                            println!("{}", diagnostics_to_json(&diagnostics, &file_names))
+                        }
+                        (MetadataFormat::Text, Ok(result)) => {
+                            print!("{}", result)
+                        }
+                        (MetadataFormat::Text, Err(error)) => {
+                            let content = match error_file {
+                                InputFile::Domain => &domain_content,
+                                InputFile::Problem => problem.as_ref().unwrap(),
+                            };
+                            renderer.add_source(error_file, file_names[&error_file], content);
+                            eprint!("{}", renderer.render_error(error_file, &error))
+                        }
                     }
-                },
+                }
                 Err(read_error) => {
-                    eprintln!("{}[Error]{} {}", red, reset, read_error)
+                    eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error)
                 }
             }
         }
-        Commands::Verify(input) => {
-            let domain = fs::read(input.domain_path);
+        Commands::Verify(args) => {
+            let input = args.input;
+            let domain = fs::read(&input.domain_path);
             match domain {
-                Ok(domain_content) => match input.problem_path {
-                    Some(problem_path) => {
-                        let problem = fs::read(problem_path);
-                        match problem {
-                            Ok(problem_content) => {
-                                let output = HDDLAnalyzer::verify(&domain_content, Some(&problem_content));
-                                match output {
-                                    Ok(warnings) => {
-                                        for warning in warnings {
-                                            println!("{}[Warning]{} {}", yellow, reset, warning);
-                                        }
-                                        println!("{}[Ok]{}", green, reset);
-                                    }
-                                    Err(parsing_error) => {
-                                        eprintln!("{}[Error]{} {}", red, reset, parsing_error)
-                                    }
-                                }
-                            }
+                Ok(domain_content) => {
+                    let problem_content = match &input.problem_path {
+                        Some(problem_path) => match fs::read(problem_path) {
+                            Ok(problem_content) => Some(problem_content),
                             Err(read_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, read_error)
+                                eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error);
+                                return;
                             }
-                        }
+                        },
+                        None => None,
+                    };
+                    let mut diagnostics = Diagnostics::new(args.max_diagnostics);
+                    for rule in args.allow {
+                        diagnostics.set_level(rule, Level::Allow);
+                    }
+                    for rule in args.warn {
+                        diagnostics.set_level(rule, Level::Warn);
+                    }
+                    for rule in args.deny {
+                        diagnostics.set_level(rule, Level::Deny);
+                    }
+                    HDDLAnalyzer::analyze(&domain_content, problem_content.as_ref(), &mut diagnostics);
+                    let mut file_names = HashMap::from([(InputFile::Domain, input.domain_path.as_str())]);
+                    if let Some(problem_path) = &input.problem_path {
+                        file_names.insert(InputFile::Problem, problem_path.as_str());
                     }
-                    None => {
-                        let output = HDDLAnalyzer::verify(&domain_content, None);
-                        match output {
-                            Ok(warnings) => {
-                                for warning in warnings {
-                                    println!("{}[Warning]{} {}", yellow, reset, warning);
-                                }
-                                println!("{}[Ok]{}", green, reset);
+                    match args.format {
+                        OutputFormat::Json => {
+                            println!("{}", diagnostics_to_json(&diagnostics, &file_names));
+                        }
+                        OutputFormat::Sarif => {
+                            let mut sources = HashMap::from([(InputFile::Domain, domain_content.as_slice())]);
+                            if let Some(content) = &problem_content {
+                                sources.insert(InputFile::Problem, content.as_slice());
+                            }
+                            println!("{}", diagnostics_to_sarif(&diagnostics, &file_names, &sources));
+                        }
+                        OutputFormat::Text => {
+                            renderer.add_source(InputFile::Domain, &input.domain_path, &domain_content);
+                            if let (Some(path), Some(content)) = (&input.problem_path, &problem_content) {
+                                renderer.add_source(InputFile::Problem, path, content);
                             }
-                            Err(parsing_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, parsing_error)
+                            print_diagnostics(&renderer, &diagnostics);
+                            if diagnostics.has_errors() {
+                                eprintln!(
+                                    "{} {} error(s), {} warning(s)",
+                                    renderer.paint(Style::Error, "[Failed]"),
+                                    diagnostics.error_count(),
+                                    diagnostics.warning_count()
+                                );
+                            } else {
+                                println!("{}", renderer.paint(Style::Success, "[Ok]"));
                             }
                         }
                     }
-                },
+                    // errors (including denied warnings) fail the run, whatever the output format
+                    if diagnostics.has_errors() {
+                        std::process::exit(1);
+                    }
+                }
                 Err(read_error) => {
-                    eprintln!("{}[Error]{} {}", red, reset, read_error)
+                    eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error)
+                }
+            }
+        }
+        Commands::Ground(args) => {
+            let (domain_content, problem_content) = match (fs::read(&args.domain_path), fs::read(&args.problem_path)) {
+                (Ok(domain_content), Ok(problem_content)) => (domain_content, problem_content),
+                (Err(read_error), _) | (_, Err(read_error)) => {
+                    eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error);
+                    return;
+                }
+            };
+            match HDDLAnalyzer::ground(&domain_content, &problem_content) {
+                Ok(model) => match (args.format, args.summary) {
+                    (MetadataFormat::Json, _) => println!("{}", ground_model_to_json(&model)),
+                    (MetadataFormat::Text, true) => print!("{}", model.summary()),
+                    (MetadataFormat::Text, false) => print!("{}", model),
+                },
+                Err(diagnostics) => {
+                    renderer.add_source(InputFile::Domain, &args.domain_path, &domain_content);
+                    renderer.add_source(InputFile::Problem, &args.problem_path, &problem_content);
+                    print_diagnostics(&renderer, &diagnostics);
+                    eprintln!(
+                        "{} the domain and problem must be free of errors to be grounded",
+                        renderer.paint(Style::Error, "[Failed]")
+                    );
+                    std::process::exit(1);
                 }
             }
         }
+        Commands::ValidatePlan(args) => {
+            let (domain_content, problem_content, plan) = match (
+                fs::read(&args.domain_path),
+                fs::read(&args.problem_path),
+                fs::read_to_string(&args.plan_path),
+            ) {
+                (Ok(domain_content), Ok(problem_content), Ok(plan)) => (domain_content, problem_content, plan),
+                (Err(read_error), _, _) | (_, Err(read_error), _) | (_, _, Err(read_error)) => {
+                    eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error);
+                    std::process::exit(1);
+                }
+            };
+            match HDDLAnalyzer::validate_plan(&domain_content, &problem_content, &plan) {
+                Ok(errors) => {
+                    match args.format {
+                        MetadataFormat::Json => println!("{}", plan_errors_to_json(&errors)),
+                        MetadataFormat::Text => {
+                            for error in errors.iter() {
+                                eprintln!("{} {}: {}", renderer.paint(Style::Error, "[Error]"), args.plan_path, error);
+                            }
+                            if errors.is_empty() {
+                                println!("{}", renderer.paint(Style::Success, "[Ok]"));
+                            } else {
+                                eprintln!("{} {} error(s)", renderer.paint(Style::Error, "[Failed]"), errors.len());
+                            }
+                        }
+                    }
+                    if !errors.is_empty() {
+                        std::process::exit(1);
+                    }
+                }
+                Err(diagnostics) => {
+                    renderer.add_source(InputFile::Domain, &args.domain_path, &domain_content);
+                    renderer.add_source(InputFile::Problem, &args.problem_path, &problem_content);
+                    print_diagnostics(&renderer, &diagnostics);
+                    eprintln!(
+                        "{} the domain and problem must be free of errors to validate a plan",
+                        renderer.paint(Style::Error, "[Failed]")
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Simulate(args) => {
+            let (domain_content, problem_content, plan) = match (
+                fs::read(&args.domain_path),
+                fs::read(&args.problem_path),
+                fs::read_to_string(&args.plan_path),
+            ) {
+                (Ok(domain_content), Ok(problem_content), Ok(plan)) => (domain_content, problem_content, plan),
+                (Err(read_error), _, _) | (_, Err(read_error), _) | (_, _, Err(read_error)) => {
+                    eprintln!("{} {}", renderer.paint(Style::Error, "[Error]"), read_error);
+                    std::process::exit(1);
+                }
+            };
+            match HDDLAnalyzer::simulate_plan(&domain_content, &problem_content, &plan, args.trace) {
+                Ok(report) => {
+                    match args.format {
+                        MetadataFormat::Json => println!("{}", simulation_to_json(&report)),
+                        MetadataFormat::Text => {
+                            print!("{}", report);
+                            for error in report.errors.iter() {
+                                eprintln!("{} {}: {}", renderer.paint(Style::Error, "[Error]"), args.plan_path, error);
+                            }
+                            if report.errors.is_empty() {
+                                println!("{}", renderer.paint(Style::Success, "[Ok]"));
+                            } else {
+                                eprintln!(
+                                    "{} {} error(s)",
+                                    renderer.paint(Style::Error, "[Failed]"),
+                                    report.errors.len()
+                                );
+                            }
+                        }
+                    }
+                    // an inapplicable step or an unmet goal fails the run
+                    if !report.errors.is_empty() {
+                        std::process::exit(1);
+                    }
+                }
+                Err(diagnostics) => {
+                    renderer.add_source(InputFile::Domain, &args.domain_path, &domain_content);
+                    renderer.add_source(InputFile::Problem, &args.problem_path, &problem_content);
+                    print_diagnostics(&renderer, &diagnostics);
+                    eprintln!(
+                        "{} the domain and problem must be free of errors to simulate a plan",
+                        renderer.paint(Style::Error, "[Failed]")
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Fmt(args) => {
+            let mut failures = 0;
+            // the renderer borrows the content of the files, so they are all read beforehand
+            let contents: Vec<_> = args.paths.iter().map(fs::read).collect();
+            for (path, content) in args.paths.iter().zip(contents.iter()) {
+                let content = match content {
+                    Ok(content) => content,
+                    Err(read_error) => {
+                        eprintln!("{} {}: {}", renderer.paint(Style::Error, "[Error]"), path, read_error);
+                        failures += 1;
+                        continue;
+                    }
+                };
+                let formatted = match HDDLAnalyzer::format(content) {
+                    Ok(formatted) => formatted,
+                    Err(error) => {
+                        renderer.add_source(InputFile::Domain, path, content);
+                        eprint!("{}", renderer.render_error(InputFile::Domain, &error));
+                        failures += 1;
+                        continue;
+                    }
+                };
+                if formatted.as_bytes() == content.as_slice() {
+                    continue;
+                }
+                if args.check {
+                    // the first line that differs
+                    let line = String::from_utf8_lossy(content)
+                        .lines()
+                        .zip(formatted.lines())
+                        .take_while(|(original, formatted)| original == formatted)
+                        .count()
+                        + 1;
+                    eprintln!("{} {}:{} is not formatted", renderer.paint(Style::Error, "[Error]"), path, line);
+                    failures += 1;
+                } else if let Err(write_error) = fs::write(path, formatted) {
+                    eprintln!("{} {}: {}", renderer.paint(Style::Error, "[Error]"), path, write_error);
+                    failures += 1;
+                }
+            }
+            if failures == 0 {
+                println!("{}", renderer.paint(Style::Success, "[Ok]"));
+            } else {
+                eprintln!("{} {} file(s)", renderer.paint(Style::Error, "[Failed]"), failures);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+// prints the rendered diagnostics (the sources must be added to "renderer" beforehand)
+fn print_diagnostics(renderer: &Renderer, diagnostics: &Diagnostics) {
+    for (file, severity, warning) in diagnostics.located_warnings() {
+        let rendered = renderer.render_warning(file, severity, warning);
+        match severity {
+            Severity::Error => eprintln!("{}", rendered),
+            Severity::Warning => println!("{}", rendered),
+        }
+    }
+    for (file, error) in diagnostics.located_errors() {
+        eprintln!("{}", renderer.render_error(file, error));
+    }
+    if diagnostics.omitted() != 0 {
+        eprintln!("{} more diagnostics were omitted", diagnostics.omitted());
     }
 }
 
@@ -88,12 +340,10 @@ mod tests {
     fn recursion_type_test_integration() {
         let domain = fs::read("domain.hddl");
         match domain {
-            Ok(domain_content) => match HDDLAnalyzer::verify(&domain_content, None) {
-                Ok(warning) => {
-
-                }
-                _ => panic!(),
-            },
+            Ok(domain_content) => {
+                let diagnostics = HDDLAnalyzer::verify(&domain_content, None, None);
+                assert!(!diagnostics.has_errors());
+            }
             _ => panic!(),
         }
     }
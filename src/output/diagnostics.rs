@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+// the input file that a diagnostic refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputFile {
+    Domain,
+    Problem,
+}
+
+// Collects every error and warning found during the analysis, so that
+// a single run reports all issues instead of stopping at the first one.
+#[derive(Debug)]
+pub struct Diagnostics {
+    pub errors: Vec<ParsingError>,
+    pub warnings: Vec<WarningType>,
+    // the file of each error and warning (in the same order), and the
+    // severity of warnings (denied warnings are reported as errors)
+    error_files: Vec<InputFile>,
+    warning_files: Vec<(InputFile, Severity)>,
+    // the file that new diagnostics are attributed to
+    current_file: InputFile,
+    // levels set by the user, which take precedence over in-file directives
+    levels: HashMap<&'static str, Level>,
+    file_levels: HashMap<(InputFile, &'static str), Level>,
+    // maximum number of reported diagnostics (None means unlimited)
+    limit: Option<usize>,
+    omitted_errors: usize,
+    omitted_warnings: usize,
+    suppressed: usize,
+}
+
+impl Diagnostics {
+    pub fn new(limit: Option<usize>) -> Diagnostics {
+        Diagnostics {
+            errors: vec![],
+            warnings: vec![],
+            error_files: vec![],
+            warning_files: vec![],
+            current_file: InputFile::Domain,
+            levels: HashMap::new(),
+            file_levels: HashMap::new(),
+            limit,
+            omitted_errors: 0,
+            omitted_warnings: 0,
+            suppressed: 0,
+        }
+    }
+
+    pub fn add_error<E: Into<ParsingError>>(&mut self, error: E) {
+        if self.is_full() {
+            self.omitted_errors += 1;
+        } else {
+            self.errors.push(error.into());
+            self.error_files.push(self.current_file);
+        }
+    }
+
+    pub fn add_warning(&mut self, warning: WarningType) {
+        let severity = match self.level(warning.rule()) {
+            Level::Allow => {
+                self.suppressed += 1;
+                return;
+            }
+            Level::Warn => Severity::Warning,
+            Level::Deny => Severity::Error,
+        };
+        if self.is_full() {
+            match severity {
+                Severity::Error => self.omitted_errors += 1,
+                Severity::Warning => self.omitted_warnings += 1,
+            }
+        } else {
+            self.warnings.push(warning);
+            self.warning_files.push((self.current_file, severity));
+        }
+    }
+
+    // attributes the subsequent diagnostics to "file"
+    pub fn set_file(&mut self, file: InputFile) {
+        self.current_file = file;
+    }
+
+    // sets the level of a warning for the whole analysis
+    pub fn set_level(&mut self, rule: &'static Rule, level: Level) {
+        self.levels.insert(rule.code, level);
+    }
+
+    // sets the level of warnings according to the directives in the comments of "file"
+    pub fn add_directives(&mut self, file: InputFile, comments: &[(TokenPosition, &str)]) {
+        for (_, comment) in comments {
+            for (rule, level) in parse_directive(comment) {
+                self.file_levels.insert((file, rule.code), level);
+            }
+        }
+    }
+
+    fn level(&self, rule: &'static Rule) -> Level {
+        match self.levels.get(rule.code) {
+            Some(level) => *level,
+            None => *self
+                .file_levels
+                .get(&(self.current_file, rule.code))
+                .unwrap_or(&Level::Warn),
+        }
+    }
+
+    pub fn located_errors(&self) -> impl Iterator<Item = (InputFile, &ParsingError)> {
+        self.error_files.iter().copied().zip(self.errors.iter())
+    }
+
+    pub fn located_warnings(&self) -> impl Iterator<Item = (InputFile, Severity, &WarningType)> {
+        self.warning_files
+            .iter()
+            .zip(self.warnings.iter())
+            .map(|((file, severity), warning)| (*file, *severity, warning))
+    }
+
+    // true if the limit on the number of reported diagnostics is reached
+    pub fn is_full(&self) -> bool {
+        match self.limit {
+            Some(limit) => self.errors.len() + self.warnings.len() >= limit,
+            None => false,
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    // number of errors, including denied warnings
+    pub fn error_count(&self) -> usize {
+        let denied = self
+            .warning_files
+            .iter()
+            .filter(|(_, severity)| *severity == Severity::Error)
+            .count();
+        self.errors.len() + denied + self.omitted_errors
+    }
+
+    pub fn warning_count(&self) -> usize {
+        let warned = self
+            .warning_files
+            .iter()
+            .filter(|(_, severity)| *severity == Severity::Warning)
+            .count();
+        warned + self.omitted_warnings
+    }
+
+    // number of diagnostics that were found, but not reported due to the limit
+    pub fn omitted(&self) -> usize {
+        self.omitted_errors + self.omitted_warnings
+    }
+
+    // number of warnings that were allowed
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
 use super::*;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 #[derive(Debug)]
 pub enum ParsingError{
@@ -7,6 +8,40 @@ pub enum ParsingError{
     Semantic(SemanticErrorType)
 }
 
+impl ParsingError {
+    pub fn position(&self) -> Option<TokenPosition> {
+        match self {
+            Self::Lexiacal(error) => Some(error.position),
+            Self::Syntactic(error) => Some(error.position),
+            Self::Semantic(error) => error.position(),
+        }
+    }
+
+    pub fn secondary_labels(&self) -> Vec<(TokenPosition, String)> {
+        match self {
+            Self::Semantic(error) => error.secondary_labels(),
+            _ => vec![],
+        }
+    }
+}
+
+// serialized as {"kind": ..., "data": ...}, the same way as semantic errors
+impl Serialize for ParsingError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = match self {
+            Self::Semantic(error) => return error.serialize(serializer),
+            _ => serializer.serialize_struct("ParsingError", 2)?,
+        };
+        state.serialize_field("kind", self.rule().name)?;
+        match self {
+            Self::Lexiacal(error) => state.serialize_field("data", error)?,
+            Self::Syntactic(error) => state.serialize_field("data", error)?,
+            Self::Semantic(_) => unreachable!(),
+        }
+        state.end()
+    }
+}
+
 impl From<LexicalError> for ParsingError {
     fn from(value: LexicalError) -> Self {
         ParsingError::Lexiacal(value)
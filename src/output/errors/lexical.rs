@@ -1,13 +1,14 @@
 use crate::TokenPosition;
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub enum LexicalErrorType {
     InvalidIdentifier,
     InvalidKeyword,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct LexicalError {
     pub error_type: LexicalErrorType,
     pub lexeme: String,
@@ -18,10 +19,10 @@ impl fmt::Display for LexicalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.error_type {
             LexicalErrorType::InvalidIdentifier => {
-                write!(f, "line {}: '{}' is an invalid identifier.", self.position.line, self.lexeme)
+                write!(f, "'{}' is an invalid identifier.", self.lexeme)
             }
             LexicalErrorType::InvalidKeyword => {
-                write!(f, "line {}: '{}' is an invalid keyword.", self.position.line, self.lexeme)
+                write!(f, "'{}' is an invalid keyword.", self.lexeme)
             }
         }
     }
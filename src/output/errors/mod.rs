@@ -2,11 +2,13 @@ mod lexical;
 mod syntactic;
 mod generic;
 mod semantic;
+mod plan;
 
 pub use lexical::*;
 pub use syntactic::*;
 pub use generic::*;
 pub use semantic::*;
+pub use plan::*;
 
 
-use crate::lexical_analyzer::{Token, TokenPosition};
\ No newline at end of file
+use crate::lexical_analyzer::TokenPosition;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "data")]
+pub enum PlanErrorType {
+    // Format Errors
+    SyntaxError { expected: String, found: String },
+    DuplicateId(usize),
+    UndefinedId(usize),
+    // Undefined Entities
+    UndefinedAction(String),
+    UndefinedTask(String),
+    UndefinedMethod(String),
+    UndefinedObject(String),
+    // Inconsistency Errors
+    InconsistentArity { symbol: String, expected: usize, found: usize },
+    InconsistentArgType { symbol: String, object: String },
+    // Decomposition Errors
+    InconsistentMethodTask { method: String, task: String },
+    InconsistentDecomposition { id: usize, method: String },
+    UnsatisfiedConstraints { id: usize, method: String },
+    UnsatisfiedMethodPrecondition { id: usize, method: String },
+    OrderingViolation { first: usize, second: usize },
+    InconsistentInitialTaskNetwork,
+    MultipleParents(usize),
+    UnreachableTask(usize),
+    // Execution Errors
+    InapplicableAction { id: usize, action: String, literal: String },
+    UnsatisfiedGoal(String),
+}
+
+// an error in a plan, along with the line of the plan file it refers to (if any)
+#[derive(Debug, Serialize)]
+pub struct PlanError {
+    pub error_type: PlanErrorType,
+    pub line: Option<usize>,
+}
+
+impl PlanError {
+    pub fn new(error_type: PlanErrorType, line: usize) -> PlanError {
+        PlanError {
+            error_type,
+            line: Some(line),
+        }
+    }
+}
+
+impl fmt::Display for PlanErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanErrorType::SyntaxError { expected, found } => {
+                write!(f, "expected {}, found '{}'.", expected, found)
+            }
+            PlanErrorType::DuplicateId(id) => {
+                write!(f, "id {} is used more than once.", id)
+            }
+            PlanErrorType::UndefinedId(id) => {
+                write!(f, "id {} is not defined.", id)
+            }
+            PlanErrorType::UndefinedAction(name) => {
+                write!(f, "action {} is not defined.", name)
+            }
+            PlanErrorType::UndefinedTask(name) => {
+                write!(f, "compound task {} is not defined.", name)
+            }
+            PlanErrorType::UndefinedMethod(name) => {
+                write!(f, "method {} is not defined.", name)
+            }
+            PlanErrorType::UndefinedObject(name) => {
+                write!(f, "object {} is not defined.", name)
+            }
+            PlanErrorType::InconsistentArity { symbol, expected, found } => {
+                write!(f, "{} expects {} argument(s), but {} are given.", symbol, expected, found)
+            }
+            PlanErrorType::InconsistentArgType { symbol, object } => {
+                write!(f, "the type of object {} is inconsistent with the parameters of {}.", object, symbol)
+            }
+            PlanErrorType::InconsistentMethodTask { method, task } => {
+                write!(f, "method {} does not refine task {}.", method, task)
+            }
+            PlanErrorType::InconsistentDecomposition { id, method } => {
+                write!(f, "the decomposition of {} does not match method {}.", id, method)
+            }
+            PlanErrorType::UnsatisfiedConstraints { id, method } => {
+                write!(f, "the decomposition of {} violates the constraints of method {}.", id, method)
+            }
+            PlanErrorType::UnsatisfiedMethodPrecondition { id, method } => {
+                write!(f, "the precondition of method {} does not hold when {} is decomposed.", method, id)
+            }
+            PlanErrorType::OrderingViolation { first, second } => {
+                write!(f, "the actions of {} must be executed before those of {}.", first, second)
+            }
+            PlanErrorType::InconsistentInitialTaskNetwork => {
+                write!(f, "the root tasks do not match the initial task network.")
+            }
+            PlanErrorType::MultipleParents(id) => {
+                write!(f, "{} is a subtask of more than one task.", id)
+            }
+            PlanErrorType::UnreachableTask(id) => {
+                write!(f, "{} is not part of the decomposition of the root tasks.", id)
+            }
+            PlanErrorType::InapplicableAction { id, action, literal } => {
+                write!(f, "action {} ({}) is not applicable, since {} does not hold.", id, action, literal)
+            }
+            PlanErrorType::UnsatisfiedGoal(literal) => {
+                write!(f, "the goal is not satisfied, since {} does not hold.", literal)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.error_type),
+            None => write!(f, "{}", self.error_type),
+        }
+    }
+}
\ No newline at end of file
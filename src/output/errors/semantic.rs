@@ -1,15 +1,18 @@
-use crate::lexical_analyzer::{RequirementType, TokenPosition};
+use crate::lexical_analyzer::TokenPosition;
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "data")]
 pub enum SemanticErrorType {
     // Duplicate Errors
     DuplicateObjectDeclaration(DuplicateError),
-    DuplicateRequirementDeclaration(RequirementType),
+    DuplicateRequirementDeclaration(DuplicateError),
     DuplicatePredicateDeclaration(DuplicateError),
     DuplicateActionDeclaration(DuplicateError),
     DuplicateCompoundTaskDeclaration(DuplicateError),
     DuplicateMethodDeclaration(DuplicateError),
+    DuplicateFunctionDeclaration(DuplicateError),
     // Undefined Entities
     UndefinedPredicate(UndefinedSymbolError),
     UndefinedType(UndefinedSymbolError),
@@ -17,13 +20,18 @@ pub enum SemanticErrorType {
     UndefinedTask(UndefinedSymbolError),
     UndefinedParameter(UndefinedSymbolError),
     UndefinedObject(UndefinedSymbolError),
+    UndefinedFunction(UndefinedSymbolError),
     // Inconsistency Error
     InconsistentPredicateArity(ArityError),
     InconsistentTaskArity(ArityError),
     InconsistentPredicateArgType(TypeError),
     InconsistentTaskArgType(TypeError),
+    InconsistentFunctionArity(ArityError),
+    InconsistentFunctionArgType(TypeError),
+    InconsistentDomainName(DomainNameError),
     // Ordering Errors
-    CyclicTypeDeclaration,
+    // the position of the type declaration that closes the cycle
+    CyclicTypeDeclaration(TokenPosition),
     CyclicOrderingDeclaration(TokenPosition),
 }
 
@@ -34,8 +42,8 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::DuplicateObjectDeclaration(duplicate) => {
                 write!(f, "object {}", duplicate)
             }
-            SemanticErrorType::DuplicateRequirementDeclaration(req) => {
-                write!(f, "requirement {}", req)
+            SemanticErrorType::DuplicateRequirementDeclaration(duplicate) => {
+                write!(f, "requirement {}", duplicate)
             }
             SemanticErrorType::DuplicatePredicateDeclaration(duplicate) => {
                 write!(f, "predicate {}", duplicate)
@@ -49,35 +57,37 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::DuplicateMethodDeclaration(duplicate) => {
                 write!(f, "method {}", duplicate)
             }
+            SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => {
+                write!(f, "function {}", duplicate)
+            }
             // Undefined Entities
             SemanticErrorType::UndefinedPredicate(undefined) => {
-                write!(
-                    f,
-                    "line {}: predicate {} is not defined.",
-                    undefined.position.line, undefined.symbol
-                )
+                write!(f, "predicate {} is not defined.", undefined.symbol)
             }
             SemanticErrorType::UndefinedType(undefined) => {
-                write!(f, "line {}: type {} is not defined.", undefined.position.line, undefined.symbol)
+                write!(f, "type {} is not defined.", undefined.symbol)
             },
             SemanticErrorType::UndefinedSubtask(undefined) => {
-                write!(f, "line {}: subtask {} is not defined.", undefined.position.line, undefined.symbol)
+                write!(f, "subtask {} is not defined.", undefined.symbol)
             }
             SemanticErrorType::UndefinedTask(undefined) => {
-                write!(f, "line {}: task {} is not defined.", undefined.position.line, undefined.symbol)
+                write!(f, "task {} is not defined.", undefined.symbol)
             }
             SemanticErrorType::UndefinedParameter(undefined) => {
-                write!(f, "line {}: parameter {} is not defined.", undefined.position.line, undefined.symbol)
+                write!(f, "parameter {} is not defined.", undefined.symbol)
             }
             SemanticErrorType::UndefinedObject(undefined) => {
-                write!(f, "line {}: object {} is not defined.", undefined.position.line, undefined.symbol)
+                write!(f, "object {} is not defined.", undefined.symbol)
+            }
+            SemanticErrorType::UndefinedFunction(undefined) => {
+                write!(f, "function {} is not defined.", undefined.symbol)
             }
             // Inconsistency Error
             SemanticErrorType::InconsistentPredicateArity(ar_error) => {
                 write!(
                     f,
-                    "line {}: predicate {} takes {} parameters, but {} are given.",
-                    ar_error.position.line, ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
+                    "predicate {} takes {} parameters, but {} are given.",
+                    ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
                 )
             }
             SemanticErrorType::InconsistentTaskArity(ar_error) => {
@@ -91,18 +101,80 @@ impl fmt::Display for SemanticErrorType {
                 write!(f, "{}", type_error)
             }
             SemanticErrorType::InconsistentTaskArgType(type_error) => write!(f, "{}", type_error),
+            SemanticErrorType::InconsistentFunctionArity(ar_error) => {
+                write!(
+                    f,
+                    "function {} takes {} parameters, but {} are given.",
+                    ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
+                )
+            }
+            SemanticErrorType::InconsistentFunctionArgType(type_error) => write!(f, "{}", type_error),
+            SemanticErrorType::InconsistentDomainName(name_error) => {
+                write!(
+                    f,
+                    "problem refers to domain {}, but the given domain is {}.",
+                    name_error.found, name_error.expected
+                )
+            }
             // Ordering Errors
-            SemanticErrorType::CyclicTypeDeclaration => {
+            SemanticErrorType::CyclicTypeDeclaration(_) => {
                 write!(f, "Type hierarchy is cyclic.")
             }
-            SemanticErrorType::CyclicOrderingDeclaration(pos) => {
-                write!(f, "line {}: task ordering is cyclic.", pos.line)
+            SemanticErrorType::CyclicOrderingDeclaration(_) => {
+                write!(f, "task ordering is cyclic.")
             }
         }
     }
 }
 
-#[derive(Debug)]
+impl SemanticErrorType {
+    // location of the offending symbol (if any)
+    pub fn position(&self) -> Option<TokenPosition> {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(duplicate)
+            | SemanticErrorType::DuplicateRequirementDeclaration(duplicate)
+            | SemanticErrorType::DuplicatePredicateDeclaration(duplicate)
+            | SemanticErrorType::DuplicateActionDeclaration(duplicate)
+            | SemanticErrorType::DuplicateCompoundTaskDeclaration(duplicate)
+            | SemanticErrorType::DuplicateMethodDeclaration(duplicate)
+            | SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => Some(duplicate.second_pos),
+            SemanticErrorType::UndefinedPredicate(undefined)
+            | SemanticErrorType::UndefinedType(undefined)
+            | SemanticErrorType::UndefinedSubtask(undefined)
+            | SemanticErrorType::UndefinedTask(undefined)
+            | SemanticErrorType::UndefinedParameter(undefined)
+            | SemanticErrorType::UndefinedObject(undefined)
+            | SemanticErrorType::UndefinedFunction(undefined) => Some(undefined.position),
+            SemanticErrorType::InconsistentPredicateArity(ar_error)
+            | SemanticErrorType::InconsistentTaskArity(ar_error)
+            | SemanticErrorType::InconsistentFunctionArity(ar_error) => Some(ar_error.position),
+            SemanticErrorType::InconsistentPredicateArgType(type_error)
+            | SemanticErrorType::InconsistentTaskArgType(type_error)
+            | SemanticErrorType::InconsistentFunctionArgType(type_error) => Some(type_error.position),
+            SemanticErrorType::InconsistentDomainName(name_error) => Some(name_error.position),
+            SemanticErrorType::CyclicTypeDeclaration(pos)
+            | SemanticErrorType::CyclicOrderingDeclaration(pos) => Some(*pos),
+        }
+    }
+
+    // other locations that are relevant to the error, along with their description
+    pub fn secondary_labels(&self) -> Vec<(TokenPosition, String)> {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(duplicate)
+            | SemanticErrorType::DuplicateRequirementDeclaration(duplicate)
+            | SemanticErrorType::DuplicatePredicateDeclaration(duplicate)
+            | SemanticErrorType::DuplicateActionDeclaration(duplicate)
+            | SemanticErrorType::DuplicateCompoundTaskDeclaration(duplicate)
+            | SemanticErrorType::DuplicateMethodDeclaration(duplicate)
+            | SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => {
+                vec![(duplicate.first_pos, format!("'{}' is first defined here", duplicate.symbol))]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct TypeError {
     pub expected: Option<String>,
     pub found: Option<String>,
@@ -112,7 +184,7 @@ pub struct TypeError {
 
 impl fmt::Display for TypeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "line {}: Type error for variable {}. ", self.position.line, self.var_name)?;
+        write!(f, "Type error for variable {}. ", self.var_name)?;
         match (&self.expected, &self.found) {
             (Some(expected), Some(found)) => {
                 write!(
@@ -138,7 +210,7 @@ impl fmt::Display for TypeError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ArityError {
     pub symbol: String,
     pub expected_arity: u32,
@@ -146,7 +218,14 @@ pub struct ArityError {
     pub position: TokenPosition,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+pub struct DomainNameError {
+    pub expected: String,
+    pub found: String,
+    pub position: TokenPosition,
+}
+
+#[derive(Debug, Serialize)]
 pub struct DuplicateError {
     pub symbol: String,
     pub first_pos: TokenPosition,
@@ -157,13 +236,13 @@ impl fmt::Display for DuplicateError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "'{}' is first defined in line {}, and then redefined in line {}.",
-            self.symbol, self.first_pos.line, self.second_pos.line
+            "'{}' is first defined at {}, and then redefined at {}.",
+            self.symbol, self.first_pos, self.second_pos
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct UndefinedSymbolError {
     pub symbol: String,
     pub position: TokenPosition,
@@ -1,8 +1,9 @@
 use super::*;
+use serde::Serialize;
 use std::fmt;
 
-#[derive(Debug)]
-pub struct SyntacticError {  
+#[derive(Debug, Serialize)]
+pub struct SyntacticError {
     pub expected: String,
     pub found: String,
     pub position: TokenPosition,
@@ -10,6 +11,6 @@ pub struct SyntacticError {
 
 impl fmt::Display for SyntacticError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "line {}: expected '{}', but found '{}'", self.position.line, self.expected, self.found)
+        write!(f, "expected '{}', but found '{}'", self.expected, self.found)
     }
 }
\ No newline at end of file
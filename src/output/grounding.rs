@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::fmt::{Display, Formatter, Result};
+
+// a predicate, task or action together with its (object) arguments
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct GroundInstance {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl GroundInstance {
+    pub fn new(name: &str, args: &[&str]) -> GroundInstance {
+        GroundInstance {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+}
+
+impl Display for GroundInstance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "({}", self.name)?;
+        for arg in self.args.iter() {
+            write!(f, " {}", arg)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GroundMethod {
+    pub name: String,
+    pub args: Vec<String>,
+    // the compound task that the method refines
+    pub task: GroundInstance,
+    pub subtasks: Vec<GroundInstance>,
+}
+
+impl Display for GroundMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}[{}]: {} ->", self.name, self.args.join(" "), self.task)?;
+        for subtask in self.subtasks.iter() {
+            write!(f, " {}", subtask)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroundModel {
+    pub actions: Vec<GroundInstance>,
+    // compound tasks that at least one ground method refines
+    pub tasks: Vec<GroundInstance>,
+    pub methods: Vec<GroundMethod>,
+    pub initial_tasks: Vec<GroundInstance>,
+}
+
+impl GroundModel {
+    // the size of the model, without its elements
+    pub fn summary(&self) -> String {
+        format!(
+            "Grounding\n\tGround actions: {}\n\tGround compound tasks: {}\n\tGround methods: {}\n",
+            self.actions.len(),
+            self.tasks.len(),
+            self.methods.len()
+        )
+    }
+}
+
+impl Display for GroundModel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.summary())?;
+        writeln!(f, "Actions")?;
+        for action in self.actions.iter() {
+            writeln!(f, "\t{}", action)?;
+        }
+        writeln!(f, "Compound Tasks")?;
+        for task in self.tasks.iter() {
+            writeln!(f, "\t{}", task)?;
+        }
+        writeln!(f, "Methods")?;
+        for method in self.methods.iter() {
+            writeln!(f, "\t{}", method)?;
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+#[derive(Serialize)]
+struct JsonLabel {
+    position: TokenPosition,
+    message: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsonDetails<'a> {
+    Error(&'a ParsingError),
+    Warning(&'a WarningType),
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    file: Option<&'a str>,
+    position: Option<TokenPosition>,
+    labels: Vec<JsonLabel>,
+    // "kind" and "data" of the error (or warning)
+    #[serde(flatten)]
+    details: JsonDetails<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    success: bool,
+    error_count: usize,
+    warning_count: usize,
+    suppressed: usize,
+    omitted: usize,
+    diagnostics: Vec<JsonDiagnostic<'a>>,
+}
+
+// serializes all diagnostics, errors first. "file_names" maps the inputs to their path.
+pub fn diagnostics_to_json(diagnostics: &Diagnostics, file_names: &HashMap<InputFile, &str>) -> String {
+    let mut entries = vec![];
+    for (file, error) in diagnostics.located_errors() {
+        let rule = error.rule();
+        entries.push(JsonDiagnostic {
+            severity: rule.severity,
+            code: rule.code,
+            message: error.to_string(),
+            file: file_names.get(&file).copied(),
+            position: error.position(),
+            labels: error
+                .secondary_labels()
+                .into_iter()
+                .map(|(position, message)| JsonLabel { position, message })
+                .collect(),
+            details: JsonDetails::Error(error),
+        });
+    }
+    for (file, severity, warning) in diagnostics.located_warnings() {
+        let rule = warning.rule();
+        entries.push(JsonDiagnostic {
+            severity,
+            code: rule.code,
+            message: warning.to_string(),
+            file: file_names.get(&file).copied(),
+            position: warning.position(),
+            labels: vec![],
+            details: JsonDetails::Warning(warning),
+        });
+    }
+    let report = JsonReport {
+        success: !diagnostics.has_errors(),
+        error_count: diagnostics.error_count(),
+        warning_count: diagnostics.warning_count(),
+        suppressed: diagnostics.suppressed(),
+        omitted: diagnostics.omitted(),
+        diagnostics: entries,
+    };
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+pub fn metadata_to_json(metadata: &MetaData) -> String {
+    serde_json::to_string_pretty(metadata).unwrap()
+}
+
+pub fn ground_model_to_json(model: &GroundModel) -> String {
+    serde_json::to_string_pretty(model).unwrap()
+}
+
+#[derive(Serialize)]
+struct JsonPlanError<'a> {
+    message: String,
+    line: Option<usize>,
+    #[serde(flatten)]
+    details: &'a PlanErrorType,
+}
+
+#[derive(Serialize)]
+struct JsonPlanReport<'a> {
+    valid: bool,
+    errors: Vec<JsonPlanError<'a>>,
+}
+
+fn json_plan_errors(errors: &[PlanError]) -> Vec<JsonPlanError<'_>> {
+    errors
+        .iter()
+        .map(|error| JsonPlanError {
+            message: error.error_type.to_string(),
+            line: error.line,
+            details: &error.error_type,
+        })
+        .collect()
+}
+
+pub fn plan_errors_to_json(errors: &[PlanError]) -> String {
+    let report = JsonPlanReport {
+        valid: errors.is_empty(),
+        errors: json_plan_errors(errors),
+    };
+    serde_json::to_string_pretty(&report).unwrap()
+}
+
+#[derive(Serialize)]
+struct JsonSimulationReport<'a> {
+    valid: bool,
+    executed_steps: usize,
+    total_steps: usize,
+    trace: &'a [TraceEntry],
+    errors: Vec<JsonPlanError<'a>>,
+}
+
+pub fn simulation_to_json(report: &SimulationReport) -> String {
+    let report = JsonSimulationReport {
+        valid: report.errors.is_empty(),
+        executed_steps: report.executed_steps,
+        total_steps: report.total_steps,
+        trace: &report.trace,
+        errors: json_plan_errors(&report.errors),
+    };
+    serde_json::to_string_pretty(&report).unwrap()
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use super::*;
+
+// how a warning is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+const DIRECTIVE_PREFIX: &str = "hddl-analyzer:";
+
+// finds a warning by its code (with or without the "HDDL-" prefix) or its name
+pub fn find_warning(id: &str) -> Option<&'static Rule> {
+    let id = id.trim();
+    RULES.iter().find(|rule| {
+        rule.severity == Severity::Warning
+            && (rule.code.eq_ignore_ascii_case(id)
+                || rule.code.trim_start_matches("HDDL-").eq_ignore_ascii_case(id)
+                || rule.name.eq_ignore_ascii_case(id))
+    })
+}
+
+// parses comments of the form "; hddl-analyzer: allow(W003, W001)".
+// unknown codes (and comments that are not directives) are ignored.
+pub fn parse_directive(comment: &str) -> Vec<(&'static Rule, Level)> {
+    let text = comment.trim_start_matches(';').trim();
+    let directive = match text.strip_prefix(DIRECTIVE_PREFIX) {
+        Some(directive) => directive.trim(),
+        None => return vec![],
+    };
+    let (level, codes) = match directive.split_once('(') {
+        Some((level, codes)) => (level.trim(), codes.trim_end()),
+        None => return vec![],
+    };
+    let level = match level {
+        "allow" => Level::Allow,
+        "warn" => Level::Warn,
+        "deny" => Level::Deny,
+        _ => return vec![],
+    };
+    match codes.strip_suffix(')') {
+        Some(codes) => codes
+            .split(',')
+            .filter_map(find_warning)
+            .map(|rule| (rule, level))
+            .collect(),
+        None => vec![],
+    }
+}
\ No newline at end of file
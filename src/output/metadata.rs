@@ -1,6 +1,8 @@
+use serde::Serialize;
 use std::fmt::{Display, Formatter, Error};
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Serialize)]
+#[serde(tag = "class", content = "cycle")]
 pub enum RecursionType {
     NonRecursive,
     Recursive(Vec<(String, String)>), // (task_name, method_name) 
@@ -50,30 +52,46 @@ fn format_task_pairs(pairs: &[(String, String)], f: &mut Formatter<'_>) -> std::
 }
 
 
+#[derive(Serialize)]
 pub struct MetaData {
     pub recursion: RecursionType,
     pub nullables: Vec<String>,
     pub domain_name: String,
+    // only known when a problem is given
+    pub problem_name: Option<String>,
+    // the initial tasks from which a recursive task is reachable (only
+    // computed when a problem is given)
+    pub recursive_initial_tasks: Vec<String>,
     pub n_actions: u32,
     pub n_tasks: u32,
-    pub n_methods: u32
+    pub n_methods: u32,
+    // whether the domain has durative actions
+    pub is_temporal: bool
 }
 
 impl Display for MetaData {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         writeln!(f, "Description")?;
+        writeln!(f, "\tDomain: {}", self.domain_name)?;
+        if let Some(problem_name) = &self.problem_name {
+            writeln!(f, "\tProblem: {}", problem_name)?;
+        }
         writeln!(f, "\tHierarchy type: {}", self.recursion)?;
-        if self.nullables.len() == 0 {
-            writeln!(f, "\tNullable Tasks: None")?;
-        } else {
+        if !self.recursive_initial_tasks.is_empty() {
+            writeln!(f, "\tRecursion driven by: {}", self.recursive_initial_tasks.join(", "))?;
+        }
+        if !self.nullables.is_empty() {
             writeln!(f, "\tNullable Tasks:")?;
             for nullable in self.nullables.iter() {
                 writeln!(f, "\t\t{}", nullable)?
             }
+        } else {
+            writeln!(f, "\tNullable Tasks: None")?;
         }
         writeln!(f, "\tNumber of actions: {}", self.n_actions)?;
         writeln!(f, "\tNumber of abstract tasks: {}", self.n_tasks)?;
         writeln!(f, "\tNumber of methods: {}", self.n_methods)?;
+        writeln!(f, "\tTemporal: {}", if self.is_temporal { "yes" } else { "no" })?;
         Ok(())
     }
 }
\ No newline at end of file
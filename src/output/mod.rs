@@ -1,7 +1,23 @@
 mod errors;
 mod warnings;
 mod metadata;
+mod grounding;
+mod simulation;
+mod diagnostics;
+mod renderer;
+mod rules;
+mod levels;
+mod json;
+mod sarif;
 
 pub use errors::*;
 pub use warnings::*;
-pub use metadata::*;
\ No newline at end of file
+pub use metadata::*;
+pub use grounding::*;
+pub use simulation::*;
+pub use diagnostics::*;
+pub use renderer::*;
+pub use rules::*;
+pub use levels::*;
+pub use json::*;
+pub use sarif::*;
\ No newline at end of file
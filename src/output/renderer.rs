@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+// ANSI escape color codes
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const GREEN: &str = "\x1b[1;32m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+// ANSI escape code to reset text color
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Error,
+    Warning,
+    Success,
+    Note,
+}
+
+// Prints diagnostics along with the excerpt of the source they refer to, e.g.,
+//
+// error[HDDL-E020]: predicate at is not defined.
+//   --> domain.hddl:12:18
+//    |
+// 12 |         :precondition (at ?x)
+//    |                        ^^
+pub struct Renderer<'a> {
+    colored: bool,
+    // name and content of the input files
+    sources: HashMap<InputFile, (&'a str, &'a [u8])>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(colored: bool) -> Renderer<'a> {
+        Renderer {
+            colored,
+            sources: HashMap::new(),
+        }
+    }
+
+    pub fn add_source(&mut self, file: InputFile, name: &'a str, content: &'a [u8]) {
+        self.sources.insert(file, (name, content));
+    }
+
+    pub fn paint(&self, style: Style, text: &str) -> String {
+        if !self.colored {
+            return text.to_string();
+        }
+        let color = match style {
+            Style::Error => RED,
+            Style::Warning => YELLOW,
+            Style::Success => GREEN,
+            Style::Note => BLUE,
+        };
+        format!("{}{}{}", color, text, RESET)
+    }
+
+    pub fn render_error(&self, file: InputFile, error: &ParsingError) -> String {
+        self.render(
+            Style::Error,
+            error.rule(),
+            file,
+            &error.to_string(),
+            error.position(),
+            error.secondary_labels(),
+        )
+    }
+
+    // denied warnings are rendered as errors
+    pub fn render_warning(&self, file: InputFile, severity: Severity, warning: &WarningType) -> String {
+        let style = match severity {
+            Severity::Error => Style::Error,
+            Severity::Warning => Style::Warning,
+        };
+        self.render(style, warning.rule(), file, &warning.to_string(), warning.position(), vec![])
+    }
+
+    fn render(
+        &self,
+        style: Style,
+        rule: &Rule,
+        file: InputFile,
+        message: &str,
+        position: Option<TokenPosition>,
+        secondary_labels: Vec<(TokenPosition, String)>,
+    ) -> String {
+        let severity = match style {
+            Style::Warning => "warning",
+            _ => "error",
+        };
+        let mut output = format!(
+            "{}{}\n",
+            self.paint(style, &format!("{}[{}]", severity, rule.code)),
+            self.bold(&format!(": {}", message))
+        );
+        let (file_name, content) = match self.sources.get(&file) {
+            Some(source) => *source,
+            None => return output,
+        };
+        let position = match position {
+            Some(position) => position,
+            None => {
+                output.push_str(&format!(" {} {}\n", self.paint(Style::Note, "-->"), file_name));
+                return output;
+            }
+        };
+        // labels are printed in the order they appear in the file
+        let mut labels: Vec<(TokenPosition, String, bool)> = secondary_labels
+            .into_iter()
+            .map(|(pos, text)| (pos, text, false))
+            .collect();
+        labels.push((position, String::new(), true));
+        labels.sort_by_key(|(pos, _, _)| pos.offset);
+
+        let max_line = labels.iter().map(|(pos, _, _)| pos.line).max().unwrap();
+        let width = max_line.to_string().len();
+        let gutter = self.paint(Style::Note, &format!("{} |", " ".repeat(width)));
+        output.push_str(&format!(
+            "{}{} {}:{}\n",
+            " ".repeat(width),
+            self.paint(Style::Note, "-->"),
+            file_name,
+            position
+        ));
+        output.push_str(&format!("{}\n", gutter));
+        let mut last_line = None;
+        for (pos, text, is_primary) in labels {
+            match last_line {
+                Some(line) if line == pos.line => {}
+                Some(line) if line + 1 != pos.line => {
+                    output.push_str(&format!("{}\n", self.paint(Style::Note, "...")));
+                }
+                _ => {}
+            }
+            let line_text = source_line(content, &pos);
+            if last_line != Some(pos.line) {
+                output.push_str(&format!(
+                    "{} {}\n",
+                    self.paint(Style::Note, &format!("{:>width$} |", pos.line, width = width)),
+                    line_text
+                ));
+            }
+            last_line = Some(pos.line);
+            // underline the span, while keeping the tabs of the line for alignment. Columns
+            // and spans are in bytes, but the underline is in characters.
+            let byte_column = (pos.column.saturating_sub(1) as usize).min(line_text.len());
+            let indent: String = line_text
+                .get(..byte_column)
+                .unwrap_or(&line_text)
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let span = &content[pos.offset.min(content.len())..pos.end.min(content.len())];
+            let max_length = line_text.chars().count().saturating_sub(indent.chars().count()).max(1);
+            let length = String::from_utf8_lossy(span).chars().count().clamp(1, max_length);
+            let (marker, marker_style) = match is_primary {
+                true => ("^", style),
+                false => ("-", Style::Note),
+            };
+            let underline = format!("{} {}", marker.repeat(length), text);
+            output.push_str(&format!(
+                "{} {}{}\n",
+                gutter,
+                indent,
+                self.paint(marker_style, underline.trim_end())
+            ));
+        }
+        output
+    }
+
+    fn bold(&self, text: &str) -> String {
+        if !self.colored {
+            return text.to_string();
+        }
+        format!("{}{}{}", BOLD, text, RESET)
+    }
+}
+
+// returns the line of "content" that contains "position"
+fn source_line(content: &[u8], position: &TokenPosition) -> String {
+    let start = position.offset.saturating_sub(position.column.saturating_sub(1) as usize);
+    let end = content[start..]
+        .iter()
+        .position(|c| *c == b'\n')
+        .map_or(content.len(), |i| start + i);
+    String::from_utf8_lossy(&content[start..end])
+        .trim_end_matches('\r')
+        .to_string()
+}
\ No newline at end of file
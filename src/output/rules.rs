@@ -0,0 +1,160 @@
+use serde::Serialize;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+// Every kind of diagnostic has a stable code, which is used to identify it
+// in the machine-readable outputs
+#[derive(Debug)]
+pub struct Rule {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub severity: Severity,
+}
+
+const fn error(code: &'static str, name: &'static str, description: &'static str) -> Rule {
+    Rule {
+        code,
+        name,
+        description,
+        severity: Severity::Error,
+    }
+}
+
+const fn warning(code: &'static str, name: &'static str, description: &'static str) -> Rule {
+    Rule {
+        code,
+        name,
+        description,
+        severity: Severity::Warning,
+    }
+}
+
+// NOTE: codes must never be reassigned, new rules get new codes
+pub const RULES: &[Rule] = &[
+    // Lexical and Syntactic Errors
+    error("HDDL-E001", "InvalidIdentifier", "An identifier contains invalid characters."),
+    error("HDDL-E002", "InvalidKeyword", "A keyword (starting with ':') is not part of HDDL."),
+    error("HDDL-E003", "SyntaxError", "The file does not follow the HDDL grammar."),
+    // Duplicate Errors
+    error("HDDL-E010", "DuplicateObjectDeclaration", "An object is declared more than once."),
+    error("HDDL-E011", "DuplicateRequirementDeclaration", "A requirement is declared more than once."),
+    error("HDDL-E012", "DuplicatePredicateDeclaration", "A predicate is declared more than once."),
+    error("HDDL-E013", "DuplicateActionDeclaration", "An action is declared more than once."),
+    error("HDDL-E014", "DuplicateCompoundTaskDeclaration", "A compound task is declared more than once."),
+    error("HDDL-E015", "DuplicateMethodDeclaration", "A method is declared more than once."),
+    error("HDDL-E016", "DuplicateFunctionDeclaration", "A function is declared more than once."),
+    // Undefined Entities
+    error("HDDL-E020", "UndefinedPredicate", "A predicate is used, but not declared."),
+    error("HDDL-E021", "UndefinedType", "A type is used, but not declared."),
+    error("HDDL-E022", "UndefinedSubtask", "A subtask is neither a declared action nor a compound task."),
+    error("HDDL-E023", "UndefinedTask", "A method refines a compound task that is not declared."),
+    error("HDDL-E024", "UndefinedParameter", "A variable is used, but not declared as a parameter."),
+    error("HDDL-E025", "UndefinedObject", "An object (or constant) is used, but not declared."),
+    error("HDDL-E026", "UndefinedFunction", "A function is used, but not declared."),
+    // Inconsistency Errors
+    error("HDDL-E030", "InconsistentPredicateArity", "A predicate is used with the wrong number of arguments."),
+    error("HDDL-E031", "InconsistentTaskArity", "A task is used with the wrong number of arguments."),
+    error("HDDL-E032", "InconsistentPredicateArgType", "A predicate argument does not match the declared type."),
+    error("HDDL-E033", "InconsistentTaskArgType", "A task argument does not match the declared type."),
+    error("HDDL-E034", "InconsistentFunctionArity", "A function is used with the wrong number of arguments."),
+    error("HDDL-E035", "InconsistentFunctionArgType", "A function argument does not match the declared type."),
+    error("HDDL-E036", "InconsistentDomainName", "A problem refers to a domain other than the given one."),
+    // Ordering Errors
+    error("HDDL-E040", "CyclicTypeDeclaration", "The type hierarchy contains a cycle."),
+    error("HDDL-E041", "CyclicOrderingDeclaration", "The ordering constraints of a task network contain a cycle."),
+    // Warnings
+    warning("HDDL-W001", "UnsatisfiableActionPrecondition", "The precondition of an action can never be satisfied."),
+    warning("HDDL-W002", "UnsatisfiableMethodPrecondition", "The precondition of a method can never be satisfied."),
+    warning("HDDL-W003", "NoPrimitiveRefinement", "A compound task can not be refined into primitive tasks."),
+    warning("HDDL-W004", "ImmutablePredicate", "A predicate does not appear in the effect of any action."),
+    warning("HDDL-W005", "UnusedType", "A type is declared, but never used."),
+    warning("HDDL-W006", "UnusedPredicate", "A predicate is declared, but never used."),
+    warning("HDDL-W007", "UnusedParameter", "A parameter is declared, but never used."),
+    warning("HDDL-W008", "RedundantEffect", "An effect of an action has no impact on the state."),
+    warning("HDDL-W009", "UnsatisfiableStaticPrecondition", "A precondition on a static predicate is never satisfied by the initial state."),
+    warning("HDDL-W010", "ShadowedVariable", "A quantified variable has the same name as a variable in the outer scope."),
+    warning("HDDL-W011", "MissingRequirement", "A language feature is used, but its requirement is not declared."),
+    warning("HDDL-W012", "UnusedRequirement", "A requirement is declared, but no language feature needs it."),
+    warning("HDDL-W013", "UnknownRequirement", "A declared requirement is not part of HDDL (or PDDL)."),
+    warning("HDDL-W014", "NeverApplicableAction", "An action is not applicable in any state reachable from the initial state, even if effects never delete facts."),
+    warning("HDDL-W015", "UnreachableMethod", "A method has a subtask that is never applicable."),
+    warning("HDDL-W016", "UnrefinableTask", "A compound task can not be refined into applicable actions in the given problem."),
+];
+
+fn find_rule(name: &str) -> &'static Rule {
+    RULES.iter().find(|rule| rule.name == name).unwrap()
+}
+
+impl ParsingError {
+    pub fn rule(&self) -> &'static Rule {
+        match self {
+            ParsingError::Lexiacal(error) => match error.error_type {
+                LexicalErrorType::InvalidIdentifier => find_rule("InvalidIdentifier"),
+                LexicalErrorType::InvalidKeyword => find_rule("InvalidKeyword"),
+            },
+            ParsingError::Syntactic(_) => find_rule("SyntaxError"),
+            ParsingError::Semantic(error) => error.rule(),
+        }
+    }
+}
+
+impl SemanticErrorType {
+    pub fn rule(&self) -> &'static Rule {
+        find_rule(match self {
+            SemanticErrorType::DuplicateObjectDeclaration(_) => "DuplicateObjectDeclaration",
+            SemanticErrorType::DuplicateRequirementDeclaration(..) => "DuplicateRequirementDeclaration",
+            SemanticErrorType::DuplicatePredicateDeclaration(_) => "DuplicatePredicateDeclaration",
+            SemanticErrorType::DuplicateActionDeclaration(_) => "DuplicateActionDeclaration",
+            SemanticErrorType::DuplicateCompoundTaskDeclaration(_) => "DuplicateCompoundTaskDeclaration",
+            SemanticErrorType::DuplicateMethodDeclaration(_) => "DuplicateMethodDeclaration",
+            SemanticErrorType::DuplicateFunctionDeclaration(_) => "DuplicateFunctionDeclaration",
+            SemanticErrorType::UndefinedPredicate(_) => "UndefinedPredicate",
+            SemanticErrorType::UndefinedType(_) => "UndefinedType",
+            SemanticErrorType::UndefinedSubtask(_) => "UndefinedSubtask",
+            SemanticErrorType::UndefinedTask(_) => "UndefinedTask",
+            SemanticErrorType::UndefinedParameter(_) => "UndefinedParameter",
+            SemanticErrorType::UndefinedObject(_) => "UndefinedObject",
+            SemanticErrorType::UndefinedFunction(_) => "UndefinedFunction",
+            SemanticErrorType::InconsistentPredicateArity(_) => "InconsistentPredicateArity",
+            SemanticErrorType::InconsistentTaskArity(_) => "InconsistentTaskArity",
+            SemanticErrorType::InconsistentPredicateArgType(_) => "InconsistentPredicateArgType",
+            SemanticErrorType::InconsistentTaskArgType(_) => "InconsistentTaskArgType",
+            SemanticErrorType::InconsistentFunctionArity(_) => "InconsistentFunctionArity",
+            SemanticErrorType::InconsistentFunctionArgType(_) => "InconsistentFunctionArgType",
+            SemanticErrorType::InconsistentDomainName(_) => "InconsistentDomainName",
+            SemanticErrorType::CyclicTypeDeclaration(_) => "CyclicTypeDeclaration",
+            SemanticErrorType::CyclicOrderingDeclaration(_) => "CyclicOrderingDeclaration",
+        })
+    }
+}
+
+impl WarningType {
+    pub fn rule(&self) -> &'static Rule {
+        find_rule(match self {
+            WarningType::UnsatisfiableActionPrecondition(_) => "UnsatisfiableActionPrecondition",
+            WarningType::UnsatisfiableMethodPrecondition(_) => "UnsatisfiableMethodPrecondition",
+            WarningType::ImmutablePredicate(_) => "ImmutablePredicate",
+            WarningType::UnsatisfiableStaticPrecondition(_) => "UnsatisfiableStaticPrecondition",
+            WarningType::NoPrimitiveRefinement(_) => "NoPrimitiveRefinement",
+            WarningType::UnusedType(_) => "UnusedType",
+            WarningType::UnusedPredicate(_) => "UnusedPredicate",
+            WarningType::UnusedParameter(_) => "UnusedParameter",
+            WarningType::ShadowedVariable(_) => "ShadowedVariable",
+            WarningType::RedundantEffect(_) => "RedundantEffect",
+            WarningType::MissingRequirement(_) => "MissingRequirement",
+            WarningType::UnusedRequirement(_) => "UnusedRequirement",
+            WarningType::UnknownRequirement(_) => "UnknownRequirement",
+            WarningType::NeverApplicableAction(_) => "NeverApplicableAction",
+            WarningType::UnreachableMethod(_) => "UnreachableMethod",
+            WarningType::UnrefinableTask(_) => "UnrefinableTask",
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// serializes all diagnostics as a SARIF 2.1.0 log (with a single run).
+// "file_names" maps the inputs to their path, and "sources" to their content.
+pub fn diagnostics_to_sarif(
+    diagnostics: &Diagnostics,
+    file_names: &HashMap<InputFile, &str>,
+    sources: &HashMap<InputFile, &[u8]>,
+) -> String {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.code,
+                "name": rule.name,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": sarif_level(rule.severity) },
+            })
+        })
+        .collect();
+    let mut results = vec![];
+    for (file, error) in diagnostics.located_errors() {
+        let related_locations: Vec<Value> = error
+            .secondary_labels()
+            .into_iter()
+            .enumerate()
+            .map(|(id, (position, message))| {
+                let mut location = sarif_location(file, file_names, sources, Some(position));
+                location["id"] = json!(id);
+                location["message"] = json!({ "text": message });
+                location
+            })
+            .collect();
+        let mut result = sarif_result(
+            error.rule(),
+            Severity::Error,
+            error.to_string(),
+            sarif_location(file, file_names, sources, error.position()),
+        );
+        if !related_locations.is_empty() {
+            result["relatedLocations"] = json!(related_locations);
+        }
+        results.push(result);
+    }
+    for (file, severity, warning) in diagnostics.located_warnings() {
+        results.push(sarif_result(
+            warning.rule(),
+            severity,
+            warning.to_string(),
+            sarif_location(file, file_names, sources, warning.position()),
+        ));
+    }
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+            "columnKind": "utf16CodeUnits",
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+fn sarif_result(rule: &'static Rule, severity: Severity, message: String, location: Value) -> Value {
+    json!({
+        "ruleId": rule.code,
+        "ruleIndex": RULES.iter().position(|r| r.code == rule.code).unwrap(),
+        "level": sarif_level(severity),
+        "message": { "text": message },
+        "locations": [location],
+    })
+}
+
+fn sarif_location(
+    file: InputFile,
+    file_names: &HashMap<InputFile, &str>,
+    sources: &HashMap<InputFile, &[u8]>,
+    position: Option<TokenPosition>,
+) -> Value {
+    let mut physical_location = json!({
+        "artifactLocation": { "uri": file_names.get(&file).copied().unwrap_or_default() },
+    });
+    if let Some(position) = position {
+        let length = position.end - position.offset;
+        let (start_column, end_column) = match sources.get(&file) {
+            Some(source) => utf16_columns(source, position),
+            None => (position.column as usize, position.column as usize + length),
+        };
+        physical_location["region"] = json!({
+            "startLine": position.line,
+            "startColumn": start_column,
+            "endLine": position.line,
+            "endColumn": end_column,
+            "byteOffset": position.offset,
+            "byteLength": length,
+        });
+    }
+    json!({ "physicalLocation": physical_location })
+}
+
+// the (1-based) columns of the first character of "position", and the one after its
+// last character, counted in UTF-16 code units (the default column kind of SARIF)
+fn utf16_columns(source: &[u8], position: TokenPosition) -> (usize, usize) {
+    let line_start = position.offset + 1 - position.column as usize;
+    let units = |start: usize, end: usize| -> usize {
+        let end = end.min(source.len());
+        String::from_utf8_lossy(&source[start.min(end)..end]).encode_utf16().count()
+    };
+    let start_column = units(line_start, position.offset) + 1;
+    (start_column, start_column + units(position.offset, position.end))
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
\ No newline at end of file
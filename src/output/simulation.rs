@@ -0,0 +1,38 @@
+use serde::Serialize;
+use std::fmt::{Display, Formatter, Result};
+
+use super::PlanError;
+
+// the state after some executed step of a plan (or the initial state, if there is no step)
+#[derive(Debug, Serialize)]
+pub struct TraceEntry {
+    pub id: Option<usize>,
+    pub action: Option<String>,
+    pub facts: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SimulationReport {
+    pub executed_steps: usize,
+    pub total_steps: usize,
+    // every state of the execution if a trace is requested, and otherwise, only
+    // the initial and final states
+    pub trace: Vec<TraceEntry>,
+    pub errors: Vec<PlanError>,
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Simulation\n\tExecuted steps: {}/{}", self.executed_steps, self.total_steps)?;
+        for entry in self.trace.iter() {
+            match (entry.id, &entry.action) {
+                (Some(id), Some(action)) => writeln!(f, "After step {}: {}", id, action)?,
+                _ => writeln!(f, "Initial state")?,
+            }
+            for fact in entry.facts.iter() {
+                writeln!(f, "\t{}", fact)?;
+            }
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
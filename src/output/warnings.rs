@@ -1,59 +1,189 @@
 use crate::lexical_analyzer::TokenPosition;
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "data")]
 pub enum WarningType {
     // Action Errors
     UnsatisfiableActionPrecondition(WarningInfo),
     UnsatisfiableMethodPrecondition(WarningInfo),
-    // TODO: implement
-    ImmutablePredicate(String),
+    ImmutablePredicate(WarningInfo),
+    UnsatisfiableStaticPrecondition(StaticLiteralInfo),
     // Compound Task errors
     NoPrimitiveRefinement(WarningInfo),
     // Redundant Elements
-    // TODO: implement
-    UnusedType(String),
-    // TODO: implement
-    UnusedPredicate(String),
-    // TODO: implement
-    UnusedParameter(String),
-    // TODO: implement
-    RedundantEffect
+    UnusedType(WarningInfo),
+    UnusedPredicate(WarningInfo),
+    UnusedParameter(WarningInfo),
+    // a quantified variable hides a parameter (or variable) with the same name
+    ShadowedVariable(WarningInfo),
+    RedundantEffect(RedundantEffectInfo),
+    // Requirements
+    MissingRequirement(RequirementInfo),
+    UnusedRequirement(WarningInfo),
+    UnknownRequirement(WarningInfo),
+    // Relaxed reachability (with respect to a problem)
+    NeverApplicableAction(WarningInfo),
+    UnreachableMethod(UnreachableMethodInfo),
+    UnrefinableTask(WarningInfo),
+}
+
+impl WarningType {
+    pub fn position(&self) -> Option<TokenPosition> {
+        match self {
+            Self::UnsatisfiableActionPrecondition(info)
+            | Self::UnsatisfiableMethodPrecondition(info)
+            | Self::NoPrimitiveRefinement(info)
+            | Self::UnusedType(info)
+            | Self::UnusedPredicate(info)
+            | Self::UnusedParameter(info)
+            | Self::ImmutablePredicate(info)
+            | Self::ShadowedVariable(info)
+            | Self::UnusedRequirement(info)
+            | Self::UnknownRequirement(info)
+            | Self::NeverApplicableAction(info)
+            | Self::UnrefinableTask(info) => Some(info.position),
+            Self::UnsatisfiableStaticPrecondition(info) => Some(info.position),
+            Self::RedundantEffect(info) => Some(info.position),
+            Self::MissingRequirement(info) => Some(info.position),
+            Self::UnreachableMethod(info) => Some(info.position),
+        }
+    }
 }
 
 impl std::fmt::Display for WarningType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             Self::UnsatisfiableActionPrecondition(info) => {
-                write!(f, "line {}: the precondition of action {} is inconsistent.", info.position.line, info.symbol)
+                write!(f, "the precondition of action {} is inconsistent.", info.symbol)
             }
             Self::UnsatisfiableMethodPrecondition(info) => {
-                write!(f, "line {}: the precondition of method {} is inconsistent.", info.position.line, info.symbol)
+                write!(f, "the precondition of method {} is inconsistent.", info.symbol)
+            }
+            Self::ImmutablePredicate(info) => {
+                write!(f, "predicate {} does not appear in the effect of any action", info.symbol)
             }
-            Self::ImmutablePredicate(predicate) => {
-                write!(f, "Predicate {} does not appear in the effect of any action", predicate)
+            Self::UnsatisfiableStaticPrecondition(info) => {
+                write!(
+                    f,
+                    "the precondition {} of {} can never hold, since {} is static and the initial state does not satisfy it",
+                    format_literal(&info.predicate, &info.terms, info.negated),
+                    info.owner,
+                    info.predicate
+                )
             }
             Self::NoPrimitiveRefinement(info) => {
-                write!(f, "line {}: compound task {} does not have a primitive refinement", info.position.line, info.symbol)
+                write!(f, "compound task {} does not have a primitive refinement", info.symbol)
+            }
+            Self::UnusedType(info) => {
+                write!(f, "type {} is declared, but never used", info.symbol)
+            }
+            Self::UnusedPredicate(info) => {
+                write!(f, "predicate {} is declared, but never used", info.symbol)
             }
-            Self::UnusedType(type_name) => {
-                write!(f, "Type {} is declared, but never used", type_name)
+            Self::UnusedParameter(info) => {
+                write!(f, "parameter {} is declared, but never used", info.symbol)
             }
-            Self::UnusedPredicate(predicate) => {
-                write!(f, "Predicate {} is declared, but never used", predicate)
+            Self::ShadowedVariable(info) => {
+                write!(f, "quantified variable {} shadows an outer variable with the same name", info.symbol)
             }
-            Self::UnusedParameter(parameter) => {
-                write!(f, "Parameter {} is declared, but never used", parameter)
+            Self::RedundantEffect(info) => {
+                let effect = format_literal(&info.predicate, &info.terms, info.negated);
+                match info.reason {
+                    RedundancyType::Duplicate => {
+                        write!(f, "effect {} of action {} is repeated", effect, info.action)
+                    }
+                    RedundancyType::AddedAndDeleted => {
+                        write!(f, "effect {} of action {} is both added and deleted", effect, info.action)
+                    }
+                    RedundancyType::ImpliedByPrecondition => {
+                        write!(f, "effect {} of action {} already holds due to its precondition", effect, info.action)
+                    }
+                }
             }
-            Self::RedundantEffect => {
-                // TODO:
-                todo!()
+            Self::MissingRequirement(info) => {
+                write!(f, "{} requires {}, which is not declared", info.feature, info.requirement)
+            }
+            Self::UnusedRequirement(info) => {
+                write!(f, "requirement {} is declared, but never used", info.symbol)
+            }
+            Self::UnknownRequirement(info) => {
+                write!(f, "requirement {} is not part of HDDL, and is ignored", info.symbol)
+            }
+            Self::NeverApplicableAction(info) => {
+                write!(f, "action {} is never applicable, even if effects never delete facts", info.symbol)
+            }
+            Self::UnreachableMethod(info) => {
+                write!(f, "method {} can never be applied, since its subtask {} is never applicable", info.method, info.action)
+            }
+            Self::UnrefinableTask(info) => {
+                write!(f, "compound task {} can not be refined into applicable actions in this problem", info.symbol)
             }
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct WarningInfo {
     pub symbol: String,
     pub position: TokenPosition,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticLiteralInfo {
+    pub predicate: String,
+    pub terms: Vec<String>,
+    pub negated: bool,
+    // the action (or method) whose precondition contains the literal
+    pub owner: String,
+    pub position: TokenPosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RedundancyType {
+    // the same literal appears more than once
+    Duplicate,
+    // the atom is both added and deleted
+    AddedAndDeleted,
+    // the precondition already requires the literal
+    ImpliedByPrecondition,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RedundantEffectInfo {
+    pub action: String,
+    pub predicate: String,
+    pub terms: Vec<String>,
+    pub negated: bool,
+    pub reason: RedundancyType,
+    pub position: TokenPosition,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequirementInfo {
+    pub requirement: String,
+    // the language feature that needs the requirement, e.g., "negative precondition"
+    pub feature: String,
+    // the first use of the feature
+    pub position: TokenPosition,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnreachableMethodInfo {
+    pub method: String,
+    // the never applicable action among the subtasks of the method
+    pub action: String,
+    pub position: TokenPosition,
+}
+
+// e.g., "(not (at ?x))"
+fn format_literal(predicate: &str, terms: &[String], negated: bool) -> String {
+    let atom = std::iter::once(predicate)
+        .chain(terms.iter().map(|term| term.as_str()))
+        .collect::<Vec<&str>>()
+        .join(" ");
+    match negated {
+        true => format!("(not ({}))", atom),
+        false => format!("({})", atom),
+    }
 }
\ No newline at end of file
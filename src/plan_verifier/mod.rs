@@ -0,0 +1,15 @@
+mod plan;
+mod simulator;
+mod state;
+mod verifier;
+#[cfg(test)]
+mod tests;
+
+use crate::output::*;
+use crate::semantic_analyzer::*;
+use crate::syntactic_analyzer::*;
+
+pub use plan::*;
+pub use simulator::Simulator;
+pub use state::*;
+pub use verifier::PlanVerifier;
\ No newline at end of file
@@ -0,0 +1,146 @@
+use super::*;
+
+// A hierarchical plan in the IPC (2020 and 2023) format, e.g.,
+//
+// ==>
+// 0 (drive truck a b)
+// root 1
+// 1 (deliver truck b) -> m_deliver 0
+// <==
+//
+// Primitive actions are listed in their execution order, and each compound
+// task is followed by the method that decomposes it and the ids of its subtasks.
+// Sequential plans may omit the ids of their actions (or follow them by ':').
+#[derive(Debug)]
+pub struct Plan {
+    pub actions: Vec<PlanStep>,
+    pub root: Vec<usize>,
+    pub root_line: Option<usize>,
+    pub decompositions: Vec<Decomposition>,
+}
+
+#[derive(Debug)]
+pub struct PlanStep {
+    pub id: usize,
+    pub name: String,
+    pub args: Vec<String>,
+    pub line: usize,
+}
+
+#[derive(Debug)]
+pub struct Decomposition {
+    pub id: usize,
+    pub task: String,
+    pub args: Vec<String>,
+    pub method: String,
+    pub subtasks: Vec<usize>,
+    pub line: usize,
+}
+
+impl Plan {
+    // only the lines between "==>" and "<==" are read (if the plan has them),
+    // since planners may print other output around the plan
+    pub fn parse(text: &str) -> Result<Plan, PlanError> {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, content)| (index + 1, content.trim()))
+            .collect();
+        let body = match lines.iter().position(|(_, content)| *content == "==>") {
+            Some(start) => &lines[start + 1..],
+            None => &lines[..],
+        };
+        let mut plan = Plan {
+            actions: vec![],
+            root: vec![],
+            root_line: None,
+            decompositions: vec![],
+        };
+        for (line, content) in body.iter() {
+            let line = *line;
+            if *content == "<==" {
+                break;
+            }
+            // skip comments
+            let content = match content.find(';') {
+                Some(index) => &content[..index],
+                None => content,
+            };
+            let tokens: Vec<&str> = content
+                .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .filter(|token| !token.is_empty())
+                .collect();
+            if tokens.is_empty() {
+                continue;
+            }
+            if tokens[0] == "root" {
+                if plan.root_line.is_some() {
+                    return Err(syntax_error("a task id", "root", line));
+                }
+                plan.root = parse_ids(&tokens[1..], line)?;
+                plan.root_line = Some(line);
+                continue;
+            }
+            // a sequential plan may list its actions without ids, e.g., "(drive truck a b)"
+            if content.trim_start().starts_with('(') {
+                plan.actions.push(PlanStep {
+                    id: plan.actions.len(),
+                    name: tokens[0].to_string(),
+                    args: tokens[1..].iter().map(|arg| arg.to_string()).collect(),
+                    line,
+                });
+                continue;
+            }
+            let id = parse_id(tokens[0], line)?;
+            match tokens.iter().position(|token| *token == "->") {
+                Some(arrow) => {
+                    if arrow < 2 {
+                        return Err(syntax_error("a compound task", "->", line));
+                    }
+                    let method = match tokens.get(arrow + 1) {
+                        Some(method) => method.to_string(),
+                        None => return Err(syntax_error("a method", "end of line", line)),
+                    };
+                    plan.decompositions.push(Decomposition {
+                        id,
+                        task: tokens[1].to_string(),
+                        args: tokens[2..arrow].iter().map(|arg| arg.to_string()).collect(),
+                        method,
+                        subtasks: parse_ids(&tokens[arrow + 2..], line)?,
+                        line,
+                    });
+                }
+                None => {
+                    if tokens.len() < 2 {
+                        return Err(syntax_error("an action", "end of line", line));
+                    }
+                    plan.actions.push(PlanStep {
+                        id,
+                        name: tokens[1].to_string(),
+                        args: tokens[2..].iter().map(|arg| arg.to_string()).collect(),
+                        line,
+                    });
+                }
+            }
+        }
+        Ok(plan)
+    }
+}
+
+fn parse_id(token: &str, line: usize) -> Result<usize, PlanError> {
+    token.trim_end_matches(':').parse().map_err(|_| syntax_error("a task id", token, line))
+}
+
+fn parse_ids(tokens: &[&str], line: usize) -> Result<Vec<usize>, PlanError> {
+    tokens.iter().map(|token| parse_id(token, line)).collect()
+}
+
+fn syntax_error(expected: &str, found: &str, line: usize) -> PlanError {
+    PlanError::new(
+        PlanErrorType::SyntaxError {
+            expected: expected.to_string(),
+            found: found.to_string(),
+        },
+        line,
+    )
+}
\ No newline at end of file
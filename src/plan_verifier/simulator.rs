@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use super::*;
+
+// the objects bound to the parameters (and quantified variables) in scope
+pub type Binding<'b> = HashMap<&'b str, &'b str>;
+
+// executes ground actions from the initial state of a problem
+pub struct Simulator<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    type_checker: TypeChecker<'a>,
+    objects: Vec<&'a Symbol<'a>>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        symbol_table: &SymbolTable<'a>,
+    ) -> Simulator<'a> {
+        let mut objects: Vec<&Symbol> = problem.objects.iter().collect();
+        for constant in symbol_table.constants.iter() {
+            if !objects.iter().any(|object| object.name == constant.name) {
+                objects.push(constant);
+            }
+        }
+        Simulator {
+            domain,
+            problem,
+            type_checker: TypeChecker {
+                type_hierarchy: symbol_table.type_hierarchy.clone(),
+            },
+            objects,
+        }
+    }
+
+    // executes "steps", and reports the states they pass through (only the initial
+    // and final states, unless "trace" is set)
+    pub fn simulate(&self, steps: &[PlanStep], trace: bool) -> SimulationReport {
+        let (states, errors) = self.execute(steps);
+        let executed_steps = states.len() - 1;
+        let mut entries = vec![];
+        for (index, state) in states.iter().enumerate() {
+            if !trace && index != 0 && index != executed_steps {
+                continue;
+            }
+            let step = index.checked_sub(1).map(|position| &steps[position]);
+            let action = step.map(|step| {
+                let action = GroundInstance {
+                    name: step.name.clone(),
+                    args: step.args.clone(),
+                };
+                action.to_string()
+            });
+            entries.push(TraceEntry {
+                id: step.map(|step| step.id),
+                action,
+                facts: state.sorted_facts(),
+            });
+        }
+        SimulationReport {
+            executed_steps,
+            total_steps: steps.len(),
+            trace: entries,
+            errors,
+        }
+    }
+
+    // executes "steps" from the initial state, and returns the state before each
+    // executed step (and after the last one). The execution stops at the first step
+    // that is not applicable, and otherwise, the goal is checked at the end.
+    pub fn execute(&self, steps: &[PlanStep]) -> (Vec<State>, Vec<PlanError>) {
+        let mut states = vec![State::new(&self.problem.init_state)];
+        for step in steps.iter() {
+            let mut errors = vec![];
+            let action = match self.check_action(step, &mut errors) {
+                Some(action) => action,
+                None => return (states, errors),
+            };
+            let binding: Binding = action
+                .parameters
+                .iter()
+                .map(|param| param.name)
+                .zip(step.args.iter().map(|arg| arg.as_str()))
+                .collect();
+            let state = states.last().unwrap();
+            if let Some(precondition) = &action.preconditions {
+                if let Some(literal) = self.failing_literal(state, precondition, &binding) {
+                    let error_type = PlanErrorType::InapplicableAction {
+                        id: step.id,
+                        action: step.name.clone(),
+                        literal,
+                    };
+                    return (states, vec![PlanError::new(error_type, step.line)]);
+                }
+            }
+            let mut next_state = state.clone();
+            if let Some(effect) = &action.effects {
+                let mut adds = vec![];
+                let mut deletes = vec![];
+                self.collect_effects(state, effect, &binding, &mut adds, &mut deletes);
+                next_state.apply(adds, deletes);
+            }
+            states.push(next_state);
+        }
+        let mut errors = vec![];
+        if let Some(goal) = &self.problem.goal {
+            if let Some(literal) = self.failing_literal(states.last().unwrap(), goal, &Binding::new()) {
+                errors.push(PlanError {
+                    error_type: PlanErrorType::UnsatisfiedGoal(literal),
+                    line: None,
+                });
+            }
+        }
+        (states, errors)
+    }
+
+    // returns the action of "step" if it is defined, and its arguments are consistent with it
+    pub fn check_action(&self, step: &PlanStep, errors: &mut Vec<PlanError>) -> Option<&'a Action<'a>> {
+        match self.domain.actions.iter().find(|action| action.name == step.name) {
+            Some(action) => {
+                match self.check_arguments(action.name, &action.parameters, &step.args, step.line, errors) {
+                    true => Some(action),
+                    false => None,
+                }
+            }
+            None => {
+                errors.push(PlanError::new(PlanErrorType::UndefinedAction(step.name.clone()), step.line));
+                None
+            }
+        }
+    }
+
+    // whether "args" are declared objects that are consistent with "parameters"
+    pub fn check_arguments(
+        &self,
+        symbol: &str,
+        parameters: &[Symbol<'a>],
+        args: &[String],
+        line: usize,
+        errors: &mut Vec<PlanError>,
+    ) -> bool {
+        if parameters.len() != args.len() {
+            errors.push(PlanError::new(
+                PlanErrorType::InconsistentArity {
+                    symbol: symbol.to_string(),
+                    expected: parameters.len(),
+                    found: args.len(),
+                },
+                line,
+            ));
+            return false;
+        }
+        let mut is_consistent = true;
+        for (param, arg) in parameters.iter().zip(args.iter()) {
+            let error_type = match self.find_object(arg) {
+                None => PlanErrorType::UndefinedObject(arg.clone()),
+                Some(object) if !self.is_object_consistent(object, param) => PlanErrorType::InconsistentArgType {
+                    symbol: symbol.to_string(),
+                    object: arg.clone(),
+                },
+                Some(_) => continue,
+            };
+            errors.push(PlanError::new(error_type, line));
+            is_consistent = false;
+        }
+        is_consistent
+    }
+
+    pub fn find_object(&self, name: &str) -> Option<&'a Symbol<'a>> {
+        self.objects.iter().find(|object| object.name == name).copied()
+    }
+
+    pub fn is_object_consistent(&self, object: &Symbol<'a>, param: &Symbol<'a>) -> bool {
+        self.type_checker
+            .is_var_type_consistent(object.symbol_type.as_ref(), param.symbol_type.as_ref())
+    }
+
+    // every assignment of type-consistent objects to "vars"
+    pub fn assignments(&self, vars: &[Symbol<'a>]) -> Vec<Vec<&'a str>> {
+        let mut assignments = vec![vec![]];
+        for var in vars.iter() {
+            let candidates: Vec<&'a str> = self
+                .objects
+                .iter()
+                .filter(|object| self.is_object_consistent(object, var))
+                .map(|object| object.name)
+                .collect();
+            assignments = assignments
+                .into_iter()
+                .flat_map(|assignment| {
+                    candidates.iter().map(move |candidate| {
+                        let mut assignment = assignment.clone();
+                        assignment.push(*candidate);
+                        assignment
+                    })
+                })
+                .collect();
+        }
+        assignments
+    }
+
+    // whether "formula" holds in "state" (numeric conditions are assumed to hold)
+    pub fn holds(&self, state: &State, formula: &Formula<'a>, binding: &Binding) -> bool {
+        match formula {
+            Formula::Empty => true,
+            Formula::Atom(predicate) => {
+                let args: Vec<&str> = predicate.variables.iter().map(|term| value(binding, term.name)).collect();
+                state.contains(predicate.name, &args)
+            }
+            Formula::Not(f) => !self.holds(state, f, binding),
+            Formula::And(fs) => fs.iter().all(|f| self.holds(state, f, binding)),
+            Formula::Or(fs) => fs.iter().any(|f| self.holds(state, f, binding)),
+            Formula::Xor(fs) => fs.iter().filter(|f| self.holds(state, f, binding)).count() == 1,
+            Formula::Imply(ps, qs) => {
+                !ps.iter().all(|p| self.holds(state, p, binding)) || qs.iter().all(|q| self.holds(state, q, binding))
+            }
+            Formula::Exists(vars, f) => self.assignments(vars).into_iter().any(|args| {
+                let mut binding = binding.clone();
+                binding.extend(vars.iter().map(|var| var.name).zip(args));
+                self.holds(state, f, &binding)
+            }),
+            Formula::ForAll(vars, f) => self.assignments(vars).into_iter().all(|args| {
+                let mut binding = binding.clone();
+                binding.extend(vars.iter().map(|var| var.name).zip(args));
+                self.holds(state, f, &binding)
+            }),
+            Formula::Equals(a, b) => value(binding, a.name) == value(binding, b.name),
+            Formula::Timed(_, f) => self.holds(state, f, binding),
+            Formula::Compare(..) | Formula::Assign(..) => true,
+        }
+    }
+
+    // returns the (first) conjunct of "formula" that does not hold in "state", e.g.,
+    // "(not (at truck a))", or None if the formula holds
+    pub fn failing_literal(&self, state: &State, formula: &Formula<'a>, binding: &Binding) -> Option<String> {
+        if self.holds(state, formula, binding) {
+            return None;
+        }
+        match formula {
+            Formula::And(fs) => fs.iter().find_map(|f| self.failing_literal(state, f, binding)),
+            Formula::Timed(_, f) => self.failing_literal(state, f, binding),
+            _ => Some(instantiate(formula, binding)),
+        }
+    }
+
+    // collects the facts that "effect" adds and deletes when applied in "state"
+    fn collect_effects(
+        &self,
+        state: &State,
+        effect: &Formula<'a>,
+        binding: &Binding,
+        adds: &mut Vec<Fact>,
+        deletes: &mut Vec<Fact>,
+    ) {
+        let fact = |predicate: &Predicate| -> Fact {
+            let args = predicate.variables.iter().map(|term| value(binding, term.name).to_string()).collect();
+            (predicate.name.to_string(), args)
+        };
+        match effect {
+            Formula::Atom(predicate) => adds.push(fact(predicate)),
+            Formula::Not(f) => {
+                if let Formula::Atom(predicate) = &**f {
+                    deletes.push(fact(predicate));
+                }
+            }
+            Formula::And(fs) => {
+                for f in fs {
+                    self.collect_effects(state, f, binding, adds, deletes);
+                }
+            }
+            // conditional effects
+            Formula::Imply(conditions, fs) if conditions.iter().all(|condition| self.holds(state, condition, binding)) => {
+                for f in fs {
+                    self.collect_effects(state, f, binding, adds, deletes);
+                }
+            }
+            Formula::ForAll(vars, f) => {
+                for args in self.assignments(vars) {
+                    let mut binding = binding.clone();
+                    binding.extend(vars.iter().map(|var| var.name).zip(args));
+                    self.collect_effects(state, f, &binding, adds, deletes);
+                }
+            }
+            Formula::Timed(_, f) => self.collect_effects(state, f, binding, adds, deletes),
+            _ => {}
+        }
+    }
+}
+
+// the object bound to "term" (every unbound term is a constant, or a quantified variable)
+pub fn value<'b>(binding: &Binding<'b>, term: &'b str) -> &'b str {
+    binding.get(term).unwrap_or(&term)
+}
+
+// the formula with the objects bound to its terms, e.g., "(not (at truck a))"
+fn instantiate(formula: &Formula, binding: &Binding) -> String {
+    let list = |fs: &[Box<Formula>]| -> String { fs.iter().map(|f| format!(" {}", instantiate(f, binding))).collect() };
+    let quantified = |quantifier: &str, vars: &[Symbol], f: &Formula| -> String {
+        let vars: Vec<String> = vars.iter().map(|var| format!("?{}", var.name)).collect();
+        format!("({} ({}) {})", quantifier, vars.join(" "), instantiate(f, binding))
+    };
+    match formula {
+        Formula::Empty => "()".to_string(),
+        Formula::Atom(predicate) => {
+            let terms: String = predicate.variables.iter().map(|term| format!(" {}", value(binding, term.name))).collect();
+            format!("({}{})", predicate.name, terms)
+        }
+        Formula::Not(f) => format!("(not {})", instantiate(f, binding)),
+        Formula::And(fs) => format!("(and{})", list(fs)),
+        Formula::Or(fs) => format!("(or{})", list(fs)),
+        Formula::Xor(fs) => format!("(xor{})", list(fs)),
+        Formula::Imply(ps, qs) => format!("(imply (and{}) (and{}))", list(ps), list(qs)),
+        Formula::Exists(vars, f) => quantified("exists", vars, f),
+        Formula::ForAll(vars, f) => quantified("forall", vars, f),
+        Formula::Equals(a, b) => format!("(= {} {})", value(binding, a.name), value(binding, b.name)),
+        Formula::Timed(_, f) => instantiate(f, binding),
+        Formula::Compare(..) | Formula::Assign(..) => "(numeric expression)".to_string(),
+    }
+}
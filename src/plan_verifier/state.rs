@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// a ground predicate, e.g., ("at", ["truck", "depot"])
+pub type Fact = (String, Vec<String>);
+
+// the facts that hold at some point of the plan execution (the values of
+// numeric fluents are not tracked)
+#[derive(Clone, Debug)]
+pub struct State {
+    pub facts: HashSet<Fact>,
+}
+
+impl State {
+    pub fn new(init_state: &[Predicate]) -> State {
+        State {
+            facts: init_state
+                .iter()
+                .map(|fact| {
+                    let args = fact.variables.iter().map(|term| term.name.to_string()).collect();
+                    (fact.name.to_string(), args)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, name: &str, args: &[&str]) -> bool {
+        let args = args.iter().map(|arg| arg.to_string()).collect();
+        self.facts.contains(&(name.to_string(), args))
+    }
+
+    // the facts as s-expressions, e.g., "(at truck depot)", in alphabetical order
+    pub fn sorted_facts(&self) -> Vec<String> {
+        let mut facts: Vec<String> = self
+            .facts
+            .iter()
+            .map(|(name, args)| {
+                let fact = GroundInstance {
+                    name: name.clone(),
+                    args: args.clone(),
+                };
+                fact.to_string()
+            })
+            .collect();
+        facts.sort();
+        facts
+    }
+
+    // deletes are applied first, so that a fact that is both deleted and added holds afterwards
+    pub fn apply(&mut self, adds: Vec<Fact>, deletes: Vec<Fact>) {
+        for fact in deletes.iter() {
+            self.facts.remove(fact);
+        }
+        self.facts.extend(adds);
+    }
+}
\ No newline at end of file
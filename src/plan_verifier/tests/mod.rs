@@ -0,0 +1,4 @@
+mod plan_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
\ No newline at end of file
@@ -0,0 +1,219 @@
+use super::*;
+
+fn get_domain() -> Vec<u8> {
+    String::from(
+        "(define (domain transport)
+            (:requirements :typing :hierarchy :method-preconditions :equality)
+            (:types location vehicle package - object)
+            (:predicates
+                (road ?a ?b - location)
+                (at ?v - vehicle ?l - location)
+                (in ?p - package ?v - vehicle)
+                (package-at ?p - package ?l - location)
+            )
+            (:task deliver :parameters (?p - package ?l - location))
+            (:task get-to :parameters (?v - vehicle ?l - location))
+            (:method m_deliver
+                :parameters (?p - package ?a ?b - location ?v - vehicle)
+                :task (deliver ?p ?b)
+                :subtasks (and (t1 (get-to ?v ?a)) (t2 (pick-up ?v ?a ?p)) (t3 (get-to ?v ?b)) (t4 (drop ?v ?b ?p)))
+                :ordering (and (< t1 t2) (< t2 t3) (< t3 t4))
+                :constraints (not (= ?a ?b))
+            )
+            (:method m_drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :task (get-to ?v ?b)
+                :precondition (at ?v ?a)
+                :ordered-subtasks (and (drive ?v ?a ?b))
+            )
+            (:action drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :precondition (and (at ?v ?a) (road ?a ?b))
+                :effect (and (not (at ?v ?a)) (at ?v ?b))
+            )
+            (:action pick-up
+                :parameters (?v - vehicle ?l - location ?p - package)
+                :precondition (and (at ?v ?l) (package-at ?p ?l))
+                :effect (and (not (package-at ?p ?l)) (in ?p ?v))
+            )
+            (:action drop
+                :parameters (?v - vehicle ?l - location ?p - package)
+                :precondition (and (at ?v ?l) (in ?p ?v))
+                :effect (and (not (in ?p ?v)) (package-at ?p ?l))
+            )
+        )",
+    )
+    .into_bytes()
+}
+
+fn get_problem() -> Vec<u8> {
+    String::from(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location t1 - vehicle p1 - package)
+            (:htn :parameters () :subtasks (and (task0 (deliver p1 b))))
+            (:init (road a b) (road b a) (at t1 b) (package-at p1 a))
+        )",
+    )
+    .into_bytes()
+}
+
+fn validate(plan: &str) -> Vec<String> {
+    crate::HDDLAnalyzer::validate_plan(&get_domain(), &get_problem(), plan)
+        .unwrap()
+        .iter()
+        .map(|error| error.to_string())
+        .collect()
+}
+
+#[test]
+pub fn plan_parsing_test() {
+    let plan = Plan::parse(
+        "planner output
+        ==>
+        0 (drive t1 b a)
+        1 drive t1 a b ; comment
+        root 2
+        2 (get-to t1 b) -> m_drive 0 1
+        <==
+        3 (drive t1 b a)",
+    )
+    .unwrap();
+    assert_eq!(plan.actions.len(), 2);
+    assert_eq!(plan.actions[1].name, "drive");
+    assert_eq!(plan.actions[1].args, vec!["t1", "a", "b"]);
+    assert_eq!(plan.root, vec![2]);
+    assert_eq!(plan.root_line, Some(5));
+    assert_eq!(plan.decompositions.len(), 1);
+    assert_eq!(plan.decompositions[0].task, "get-to");
+    assert_eq!(plan.decompositions[0].method, "m_drive");
+    assert_eq!(plan.decompositions[0].subtasks, vec![0, 1]);
+    match Plan::parse("==>\n0 (drive t1 b a)\nx (drive t1 a b)") {
+        Err(error) => assert_eq!(error.to_string(), "line 3: expected a task id, found 'x'."),
+        Ok(_) => panic!("plan should not parse"),
+    }
+}
+
+#[test]
+pub fn valid_plan_test() {
+    let errors = validate(
+        "==>
+        0 (drive t1 b a)
+        1 (pick-up t1 a p1)
+        2 (drive t1 a b)
+        3 (drop t1 b p1)
+        root 4
+        4 (deliver p1 b) -> m_deliver 5 1 6 3
+        5 (get-to t1 a) -> m_drive 0
+        6 (get-to t1 b) -> m_drive 2
+        <==",
+    );
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+pub fn invalid_plan_test() {
+    // the package is picked up before driving to it
+    let errors = validate(
+        "==>
+        0 (pick-up t1 a p1)
+        1 (drive t1 b a)
+        2 (drive t1 a b)
+        3 (drop t1 b p1)
+        root 4
+        4 (deliver p1 b) -> m_deliver 5 0 6 3
+        5 (get-to t1 a) -> m_drive 1
+        6 (get-to t1 b) -> m_drive 2
+        <==",
+    );
+    assert_eq!(errors, vec![
+        "line 7: the actions of 5 must be executed before those of 0.",
+        "line 2: action 0 (pick-up) is not applicable, since (at t1 a) does not hold.",
+    ]);
+    // wrong methods, undefined ids, and tasks outside of the hierarchy
+    let errors = validate(
+        "==>
+        0 (drive t1 b a)
+        1 (drive t1 a b)
+        2 (drive t1 b c)
+        root 3
+        3 (deliver p1 b) -> m_drive 0
+        4 (get-to t1 b) -> m_drive 1 9
+        <==",
+    );
+    assert_eq!(errors, vec![
+        "line 4: object c is not defined.",
+        "line 6: method m_drive does not refine task deliver.",
+        "line 7: id 9 is not defined.",
+        "line 3: 1 is not part of the decomposition of the root tasks.",
+        "line 4: 2 is not part of the decomposition of the root tasks.",
+        "line 7: 4 is not part of the decomposition of the root tasks.",
+    ]);
+    // the root tasks must match the initial task network
+    let errors = validate(
+        "==>
+        0 (drive t1 b a)
+        root 1
+        1 (get-to t1 a) -> m_drive 0
+        <==",
+    );
+    assert_eq!(errors, vec!["line 3: the root tasks do not match the initial task network."]);
+}
+
+#[test]
+pub fn sequential_plan_parsing_test() {
+    let plan = Plan::parse("(drive t1 b a)\n1: (drive t1 a b)").unwrap();
+    assert_eq!(plan.actions.len(), 2);
+    assert_eq!(plan.actions[0].id, 0);
+    assert_eq!(plan.actions[0].name, "drive");
+    assert_eq!(plan.actions[1].id, 1);
+    assert_eq!(plan.actions[1].args, vec!["t1", "a", "b"]);
+    assert!(plan.root_line.is_none());
+}
+
+#[test]
+pub fn simulation_test() {
+    let domain = String::from(
+        "(define (domain lights)
+            (:requirements :typing :negative-preconditions :conditional-effects)
+            (:types switch - object)
+            (:predicates (on ?s - switch) (broken ?s - switch))
+            (:action toggle
+                :parameters (?s - switch)
+                :precondition (not (broken ?s))
+                :effect (and (when (on ?s) (not (on ?s))) (when (not (on ?s)) (on ?s)))
+            )
+            (:action break
+                :parameters (?s - switch)
+                :effect (and (broken ?s) (not (on ?s)))
+            )
+        )",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain lights)
+            (:objects s1 s2 - switch)
+            (:init (on s1))
+            (:goal (and (on s1) (on s2)))
+        )",
+    )
+    .into_bytes();
+    let simulate = |plan: &str, trace: bool| crate::HDDLAnalyzer::simulate_plan(&domain, &problem, plan, trace).unwrap();
+    // conditional effects depend on the state before the action
+    let report = simulate("(toggle s2)\n(toggle s1)", true);
+    assert_eq!(report.executed_steps, 2);
+    let facts: Vec<Vec<String>> = report.trace.iter().map(|entry| entry.facts.clone()).collect();
+    assert_eq!(facts, vec![vec!["(on s1)"], vec!["(on s1)", "(on s2)"], vec!["(on s2)"]]);
+    assert_eq!(report.trace[2].action.as_deref(), Some("(toggle s1)"));
+    let errors: Vec<String> = report.errors.iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec!["the goal is not satisfied, since (on s1) does not hold."]);
+    // the execution stops at the first inapplicable action
+    let report = simulate("0: (break s1)\n1: (toggle s1)\n2: (toggle s2)", false);
+    assert_eq!(report.executed_steps, 1);
+    assert_eq!(report.trace.len(), 2);
+    assert_eq!(report.trace[1].facts, vec!["(broken s1)"]);
+    let errors: Vec<String> = report.errors.iter().map(|error| error.to_string()).collect();
+    assert_eq!(errors, vec!["line 2: action 1 (toggle) is not applicable, since (not (broken s1)) does not hold."]);
+    // a plan that reaches the goal
+    let report = simulate("(toggle s2)", false);
+    assert!(report.errors.is_empty());
+}
\ No newline at end of file
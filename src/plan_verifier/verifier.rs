@@ -0,0 +1,414 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use super::simulator::{value, Binding};
+use super::*;
+
+// checks that a plan is a valid refinement of the initial task network of a
+// problem, and that its actions are executable from the initial state
+pub struct PlanVerifier<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    simulator: Simulator<'a>,
+}
+
+// a task of the plan, which is either an action or a decomposed compound task
+#[derive(Clone, Copy)]
+enum PlanTask<'p> {
+    Primitive(&'p PlanStep),
+    Compound(&'p Decomposition),
+}
+
+impl<'p> PlanTask<'p> {
+    fn id(&self) -> usize {
+        match self {
+            PlanTask::Primitive(step) => step.id,
+            PlanTask::Compound(decomposition) => decomposition.id,
+        }
+    }
+
+    fn name(&self) -> &'p str {
+        match self {
+            PlanTask::Primitive(step) => &step.name,
+            PlanTask::Compound(decomposition) => &decomposition.task,
+        }
+    }
+
+    fn args(&self) -> &'p [String] {
+        match self {
+            PlanTask::Primitive(step) => &step.args,
+            PlanTask::Compound(decomposition) => &decomposition.args,
+        }
+    }
+
+    fn line(&self) -> usize {
+        match self {
+            PlanTask::Primitive(step) => step.line,
+            PlanTask::Compound(decomposition) => decomposition.line,
+        }
+    }
+}
+
+// the plan tasks by their id, the first and last position of the actions that
+// each of them is refined into, and the state before each executed action
+struct PlanIndex<'p> {
+    tasks: HashMap<usize, PlanTask<'p>>,
+    spans: HashMap<usize, (usize, usize)>,
+    states: Vec<State>,
+}
+
+impl<'a> PlanVerifier<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        symbol_table: &SymbolTable<'a>,
+    ) -> PlanVerifier<'a> {
+        PlanVerifier {
+            domain,
+            problem,
+            simulator: Simulator::new(domain, problem, symbol_table),
+        }
+    }
+
+    // returns all errors of the plan (i.e., the plan is valid if there are none)
+    pub fn verify(&self, plan: &Plan) -> Vec<PlanError> {
+        let mut errors = vec![];
+        let mut tasks = HashMap::new();
+        let plan_tasks = plan
+            .actions
+            .iter()
+            .map(PlanTask::Primitive)
+            .chain(plan.decompositions.iter().map(PlanTask::Compound));
+        for task in plan_tasks {
+            match tasks.entry(task.id()) {
+                Entry::Occupied(_) => {
+                    errors.push(PlanError::new(PlanErrorType::DuplicateId(task.id()), task.line()));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(task);
+                }
+            }
+        }
+        let mut is_executable = true;
+        for step in plan.actions.iter() {
+            is_executable &= self.simulator.check_action(step, &mut errors).is_some();
+        }
+        // execution is only simulated if every action is well-defined
+        let (states, execution_errors) = match is_executable {
+            true => self.simulator.execute(&plan.actions),
+            false => (vec![], vec![]),
+        };
+        let index = PlanIndex {
+            tasks,
+            spans: compute_spans(plan),
+            states,
+        };
+        for decomposition in plan.decompositions.iter() {
+            self.check_decomposition(decomposition, &index, &mut errors);
+        }
+        self.check_root(plan, &index, &mut errors);
+        check_tree(plan, &index, &mut errors);
+        errors.extend(execution_errors);
+        errors
+    }
+
+    fn check_decomposition(&self, decomposition: &Decomposition, index: &PlanIndex, errors: &mut Vec<PlanError>) {
+        let line = decomposition.line;
+        let task = match self.domain.compound_tasks.iter().find(|task| task.name == decomposition.task) {
+            Some(task) => task,
+            None => {
+                errors.push(PlanError::new(PlanErrorType::UndefinedTask(decomposition.task.clone()), line));
+                return;
+            }
+        };
+        if !self.simulator.check_arguments(task.name, &task.parameters, &decomposition.args, line, errors) {
+            return;
+        }
+        let method = match self.domain.methods.iter().find(|method| method.name.name == decomposition.method) {
+            Some(method) => method,
+            None => {
+                errors.push(PlanError::new(PlanErrorType::UndefinedMethod(decomposition.method.clone()), line));
+                return;
+            }
+        };
+        if method.task.name != task.name {
+            let error_type = PlanErrorType::InconsistentMethodTask {
+                method: method.name.name.to_string(),
+                task: task.name.to_string(),
+            };
+            errors.push(PlanError::new(error_type, line));
+            return;
+        }
+        self.check_network(Some((decomposition, method)), &decomposition.subtasks, line, index, errors);
+    }
+
+    // checks that the root tasks match the initial task network
+    fn check_root(&self, plan: &Plan, index: &PlanIndex, errors: &mut Vec<PlanError>) {
+        match plan.root_line {
+            Some(line) => self.check_network(None, &plan.root, line, index, errors),
+            None => {
+                let has_initial_tasks = match &self.problem.init_tn {
+                    Some(init_tn) => !init_tn.tn.subtasks.is_empty(),
+                    None => false,
+                };
+                if has_initial_tasks {
+                    errors.push(PlanError {
+                        error_type: PlanErrorType::InconsistentInitialTaskNetwork,
+                        line: None,
+                    });
+                }
+            }
+        }
+    }
+
+    // checks that "subtask_ids" refine the task network of the method that decomposes
+    // a task (or the initial task network if it is None), i.e., they are its subtasks
+    // under some binding of its parameters, and satisfy its constraints, orderings,
+    // and precondition
+    fn check_network(
+        &self,
+        decomposition: Option<(&Decomposition, &Method<'a>)>,
+        subtask_ids: &[usize],
+        line: usize,
+        index: &PlanIndex,
+        errors: &mut Vec<PlanError>,
+    ) {
+        let empty_tn = HTN {
+            subtasks: vec![],
+            ordering_pos: None,
+            orderings: TaskOrdering::Total,
+            constraints: None,
+        };
+        let (parameters, tn) = match (decomposition, &self.problem.init_tn) {
+            (Some((_, method)), _) => (&method.params[..], &method.tn),
+            (None, Some(init_tn)) => (init_tn.parameters.as_deref().unwrap_or(&[]), &init_tn.tn),
+            (None, None) => (&[][..], &empty_tn),
+        };
+        let error = |error_type: PlanErrorType| PlanError::new(error_type, line);
+        let method = decomposition.map(|(decomposition, method)| (decomposition.id, method));
+        let mismatch = match method {
+            Some((id, method)) => PlanErrorType::InconsistentDecomposition {
+                id,
+                method: method.name.name.to_string(),
+            },
+            None => PlanErrorType::InconsistentInitialTaskNetwork,
+        };
+        let undefined_ids: Vec<usize> = subtask_ids
+            .iter()
+            .filter(|id| !index.tasks.contains_key(id))
+            .cloned()
+            .collect();
+        if !undefined_ids.is_empty() {
+            errors.extend(undefined_ids.into_iter().map(|id| error(PlanErrorType::UndefinedId(id))));
+            return;
+        }
+        // the head of the method binds (some of) its parameters
+        let mut binding = Binding::new();
+        if let Some((decomposition, method)) = decomposition {
+            if !unify(&method.task_terms, &decomposition.args, parameters, &mut binding) {
+                errors.push(error(mismatch));
+                return;
+            }
+        }
+        let mut mapping = vec![];
+        if subtask_ids.len() != tn.subtasks.len()
+            || !match_subtasks(&tn.subtasks, parameters, subtask_ids, index, &mut binding, &mut mapping)
+        {
+            errors.push(error(mismatch));
+            return;
+        }
+        // the bound objects must be consistent with the types of the parameters
+        for param in parameters.iter() {
+            let object = binding
+                .get(param.name)
+                .and_then(|value| self.simulator.find_object(value));
+            if let Some(object) = object {
+                if !self.simulator.is_object_consistent(object, param) {
+                    errors.push(error(mismatch));
+                    return;
+                }
+            }
+        }
+        // the unbound parameters can be any objects that satisfy the constraints
+        let free: Vec<Symbol<'a>> = parameters
+            .iter()
+            .filter(|param| !binding.contains_key(param.name))
+            .cloned()
+            .collect();
+        let constraints = tn.constraints.as_deref().unwrap_or(&[]);
+        let bindings: Vec<Binding> = self
+            .simulator
+            .assignments(&free)
+            .into_iter()
+            .map(|args| {
+                let mut binding = binding.clone();
+                binding.extend(free.iter().map(|param| param.name).zip(args));
+                binding
+            })
+            .filter(|binding| {
+                constraints.iter().all(|constraint| match constraint {
+                    Constraint::Equal(a, b) => value(binding, a) == value(binding, b),
+                    Constraint::NotEqual(a, b) => value(binding, a) != value(binding, b),
+                })
+            })
+            .collect();
+        if bindings.is_empty() {
+            errors.push(error(match method {
+                Some((id, method)) => PlanErrorType::UnsatisfiedConstraints {
+                    id,
+                    method: method.name.name.to_string(),
+                },
+                None => PlanErrorType::InconsistentInitialTaskNetwork,
+            }));
+            return;
+        }
+        // the precondition must hold before the first action of the decomposition
+        if let Some((id, method)) = method {
+            let state = index.spans.get(&id).and_then(|(first, _)| index.states.get(*first));
+            if let (Some(precondition), Some(state)) = (&method.precondition, state) {
+                if !bindings.iter().any(|binding| self.simulator.holds(state, precondition, binding)) {
+                    errors.push(error(PlanErrorType::UnsatisfiedMethodPrecondition {
+                        id,
+                        method: method.name.name.to_string(),
+                    }));
+                }
+            }
+        }
+        let orderings: Vec<(usize, usize)> = match &tn.orderings {
+            TaskOrdering::Total => (0..mapping.len())
+                .flat_map(|i| (i + 1..mapping.len()).map(move |j| (i, j)))
+                .collect(),
+            TaskOrdering::Partial(orderings) => {
+                let position = |name: &str| tn.subtasks.iter().position(|subtask| match &subtask.id {
+                    Some(id) => id.name == name,
+                    None => false,
+                });
+                orderings
+                    .iter()
+                    .filter_map(|(first, second)| Some((position(first)?, position(second)?)))
+                    .collect()
+            }
+        };
+        for (first, second) in orderings {
+            let (first, second) = (mapping[first], mapping[second]);
+            if let (Some((_, last)), Some((next, _))) = (index.spans.get(&first), index.spans.get(&second)) {
+                if last >= next {
+                    errors.push(error(PlanErrorType::OrderingViolation { first, second }));
+                }
+            }
+        }
+    }
+}
+
+// binds the parameters among "terms" to "args", and checks that the other terms
+// (i.e., constants) are equal to their argument
+fn unify<'b>(terms: &[Symbol<'b>], args: &'b [String], parameters: &[Symbol], binding: &mut Binding<'b>) -> bool {
+    if terms.len() != args.len() {
+        return false;
+    }
+    for (term, arg) in terms.iter().zip(args.iter()) {
+        if parameters.iter().any(|param| param.name == term.name) {
+            match binding.get(term.name) {
+                Some(value) if *value != arg => return false,
+                Some(_) => {}
+                None => {
+                    binding.insert(term.name, arg);
+                }
+            }
+        } else if term.name != arg {
+            return false;
+        }
+    }
+    true
+}
+
+// assigns a distinct task of "ids" to each of "subtasks" (in "mapping"), such
+// that their names match and their arguments unify under "binding"
+fn match_subtasks<'b>(
+    subtasks: &[Subtask<'b>],
+    parameters: &[Symbol],
+    ids: &[usize],
+    index: &PlanIndex<'b>,
+    binding: &mut Binding<'b>,
+    mapping: &mut Vec<usize>,
+) -> bool {
+    let subtask = match subtasks.get(mapping.len()) {
+        Some(subtask) => subtask,
+        None => return true,
+    };
+    for id in ids.iter() {
+        let task = index.tasks[id];
+        if mapping.contains(id) || task.name() != subtask.task.name {
+            continue;
+        }
+        let mut extended = binding.clone();
+        if unify(&subtask.terms, task.args(), parameters, &mut extended) {
+            mapping.push(*id);
+            if match_subtasks(subtasks, parameters, ids, index, &mut extended, mapping) {
+                *binding = extended;
+                return true;
+            }
+            mapping.pop();
+        }
+    }
+    false
+}
+
+// the first and last position of the actions that each task is refined into
+fn compute_spans(plan: &Plan) -> HashMap<usize, (usize, usize)> {
+    let mut spans: HashMap<usize, (usize, usize)> = HashMap::new();
+    for (position, step) in plan.actions.iter().enumerate() {
+        spans.insert(step.id, (position, position));
+    }
+    // spans only grow, so this terminates even if the decompositions are cyclic
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for decomposition in plan.decompositions.iter() {
+            let span = decomposition
+                .subtasks
+                .iter()
+                .chain(std::iter::once(&decomposition.id))
+                .filter_map(|id| spans.get(id))
+                .fold(None, |span: Option<(usize, usize)>, (first, last)| match span {
+                    Some((min, max)) => Some((min.min(*first), max.max(*last))),
+                    None => Some((*first, *last)),
+                });
+            if let Some(span) = span {
+                if spans.insert(decomposition.id, span) != Some(span) {
+                    changed = true;
+                }
+            }
+        }
+    }
+    spans
+}
+
+// checks that the decompositions form a tree below the root tasks
+fn check_tree(plan: &Plan, index: &PlanIndex, errors: &mut Vec<PlanError>) {
+    let mut has_parent = HashSet::new();
+    let root = plan.root.iter().map(|id| (*id, plan.root_line.unwrap_or_default()));
+    let subtasks = plan
+        .decompositions
+        .iter()
+        .flat_map(|decomposition| decomposition.subtasks.iter().map(|id| (*id, decomposition.line)));
+    for (id, line) in root.chain(subtasks) {
+        if !has_parent.insert(id) {
+            errors.push(PlanError::new(PlanErrorType::MultipleParents(id), line));
+        }
+    }
+    let mut reached = HashSet::new();
+    let mut queue = plan.root.clone();
+    while let Some(id) = queue.pop() {
+        if reached.insert(id) {
+            if let Some(PlanTask::Compound(decomposition)) = index.tasks.get(&id) {
+                queue.extend(decomposition.subtasks.iter());
+            }
+        }
+    }
+    let mut unreachables: Vec<&PlanTask> = index.tasks.values().filter(|task| !reached.contains(&task.id())).collect();
+    unreachables.sort_by_key(|task| task.line());
+    for task in unreachables {
+        errors.push(PlanError::new(PlanErrorType::UnreachableTask(task.id()), task.line()));
+    }
+}
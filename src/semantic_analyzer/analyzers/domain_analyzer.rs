@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
 use super::*;
-use crate::lexical_analyzer::RequirementType;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
 
 pub struct DomainSemanticAnalyzer<'a> {
     domain: &'a DomainAST<'a>,
+    // the problem (if any), whose predicates count as used
+    problem: Option<&'a ProblemAST<'a>>,
     pub type_checker: DomainTypeChecker<'a>,
 }
 
@@ -12,23 +14,33 @@ impl<'a> DomainSemanticAnalyzer<'a> {
     pub fn new(domain: &'a DomainAST<'a>) -> DomainSemanticAnalyzer<'a> {
         DomainSemanticAnalyzer {
             domain,
+            problem: None,
             type_checker: DomainTypeChecker::new(&domain.types),
         }
     }
 
-    pub fn verify_domain(&'a self) -> Result<SymbolTable<'a>, SemanticErrorType> {
+    pub fn with_problem(mut self, problem: &'a ProblemAST<'a>) -> DomainSemanticAnalyzer<'a> {
+        self.problem = Some(problem);
+        self
+    }
+
+    // analyzes the whole domain, and records all errors and warnings in "diagnostics"
+    pub fn analyze(&'a self, diagnostics: &mut Diagnostics) -> SymbolTable<'a> {
         // Assert there are no duplicate requirements
-        if let Some(duplicate) =
-            DomainSemanticAnalyzer::check_duplicate_requirements(&self.domain.requirements)
-        {
-            return Err(duplicate);
+        for duplicate in DomainSemanticAnalyzer::check_duplicate_requirements(
+            &self.domain.requirements,
+            &self.domain.requirement_positions,
+        ) {
+            diagnostics.add_error(duplicate);
         }
         // Assert type hierarchy is acyclic
-        let _ = self.type_checker.verify_type_hierarchy()?;
-        let mut warnings = vec![];
+        if let Err(error) = self.type_checker.verify_type_hierarchy(&self.domain.types) {
+            diagnostics.add_error(error);
+        }
         // Domain declarations
-        let declared_predicates = self.verify_predicates()?;
-        let declared_tasks = self.verify_compound_tasks()?;
+        let declared_predicates = self.verify_predicates(diagnostics);
+        let declared_functions = self.verify_functions(diagnostics);
+        let declared_tasks = self.verify_compound_tasks(diagnostics);
         let mut declared_constants = HashSet::new();
         match &self.domain.constants {
             Some(constants) => {
@@ -44,7 +56,7 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         let mut action_positions = HashMap::new();
         for action in self.domain.actions.iter() {
             if !declared_actions.insert(action) {
-                return Err(SemanticErrorType::DuplicateActionDeclaration(
+                diagnostics.add_error(SemanticErrorType::DuplicateActionDeclaration(
                     DuplicateError {
                         symbol: action.name.to_string(),
                         first_pos: *action_positions.get(action.name).unwrap(),
@@ -54,22 +66,32 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             } else {
                 action_positions.insert(action.name, action.name_pos);
             }
+            // assert parameter types are declared
+            if let Some(error) = self.type_checker.check_type_declarations(&action.parameters) {
+                diagnostics.add_error(error);
+            }
             // assert precondition predicates are declared
             match &action.preconditions {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
                     self.type_checker.check_formula(
-                        &precond_predicates,
+                        precondition,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !precondition.is_sat() {
-                        warnings.push(WarningType::UnsatisfiableActionPrecondition(WarningInfo {
-                            symbol: action.name.to_string(),
-                            position: action.name_pos,
-                        }));
+                        &declared_functions,
+                        diagnostics,
+                    );
+                    report_shadowed_variables(precondition, &action.parameters, diagnostics);
+                    // the conditions of a durative action hold at different times
+                    let satisfiable = precondition.is_sat()
+                        && precondition.get_timed_formulas().iter().all(|(_, f)| f.is_sat());
+                    if !satisfiable {
+                        diagnostics.add_warning(WarningType::UnsatisfiableActionPrecondition(
+                            WarningInfo {
+                                symbol: action.name.to_string(),
+                                position: action.name_pos,
+                            },
+                        ));
                     }
                 }
                 _ => {}
@@ -77,17 +99,39 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             // assert effect predicates are declared
             match &action.effects {
                 Some(effect) => {
-                    check_predicate_declarations(effect, &self.domain.predicates)?;
-                    let eff_predicates = effect.get_propositional_predicates();
                     self.type_checker.check_formula(
-                        &eff_predicates,
+                        effect,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
+                        &declared_functions,
+                        diagnostics,
+                    );
+                    report_shadowed_variables(effect, &action.parameters, diagnostics);
                 }
                 _ => {}
             }
+            // assert the functions of the duration are declared
+            if let Some(duration) = &action.duration {
+                self.type_checker.check_formula(
+                    duration,
+                    &action.parameters,
+                    &declared_constants,
+                    &declared_predicates,
+                    &declared_functions,
+                    diagnostics,
+                );
+            }
+            for (effect, negated, reason) in redundant_effects(action) {
+                diagnostics.add_warning(WarningType::RedundantEffect(RedundantEffectInfo {
+                    action: action.name.to_string(),
+                    predicate: effect.name.to_string(),
+                    terms: effect.variables.iter().map(|var| var.name.to_string()).collect(),
+                    negated,
+                    reason,
+                    position: effect.name_pos,
+                }));
+            }
         }
 
         // assert methods are correct
@@ -95,7 +139,7 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         let mut method_positions = HashMap::new();
         for method in self.domain.methods.iter() {
             if !declared_methods.insert(&method.name) {
-                return Err(SemanticErrorType::DuplicateMethodDeclaration(
+                diagnostics.add_error(SemanticErrorType::DuplicateMethodDeclaration(
                     DuplicateError {
                         symbol: method.name.name.to_string(),
                         first_pos: *method_positions.get(&method.name).unwrap(),
@@ -105,107 +149,266 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             } else {
                 method_positions.insert(&method.name, method.name.name_pos);
             }
+            // assert parameter types are declared
+            if let Some(error) = self.type_checker.check_type_declarations(&method.params) {
+                diagnostics.add_error(error);
+            }
             // Assert preconditions are valid
             match &method.precondition {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
                     self.type_checker.check_formula(
-                        &precond_predicates,
+                        precondition,
                         &method.params,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
+                        &declared_functions,
+                        diagnostics,
+                    );
+                    report_shadowed_variables(precondition, &method.params, diagnostics);
                     if !precondition.is_sat() {
-                        warnings.push(WarningType::UnsatisfiableMethodPrecondition(WarningInfo {
-                            symbol: method.name.name.to_string(),
-                            position: method.name.name_pos,
-                        }));
+                        diagnostics.add_warning(WarningType::UnsatisfiableMethodPrecondition(
+                            WarningInfo {
+                                symbol: method.name.name.to_string(),
+                                position: method.name.name_pos,
+                            },
+                        ));
                     }
                 }
                 _ => {}
             }
             // Assert task is defined
             if !declared_tasks.contains(method.task.name) {
-                return Err(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
+                diagnostics.add_error(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
                     symbol: method.task.name.to_string(),
                     position: method.task.name_pos,
                 }));
             } else {
                 // Assert task arity is consistent
+                let mut consistent_arity = true;
                 for declared_compound_task in self.domain.compound_tasks.iter() {
                     if method.task.name == declared_compound_task.name {
                         if method.task_terms.len() != declared_compound_task.parameters.len() {
-                            return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
-                                symbol: method.task.name.to_string(),
-                                expected_arity: method.task_terms.len() as u32,
-                                found_arity: declared_compound_task.parameters.len() as u32,
-                                position: method.task.name_pos,
-                            }));
-                        } else {
-                            break;
+                            consistent_arity = false;
+                            diagnostics.add_error(SemanticErrorType::InconsistentTaskArity(
+                                ArityError {
+                                    symbol: method.task.name.to_string(),
+                                    expected_arity: method.task_terms.len() as u32,
+                                    found_arity: declared_compound_task.parameters.len() as u32,
+                                    position: method.task.name_pos,
+                                },
+                            ));
                         }
+                        break;
+                    }
+                }
+                // Assert task type is consistent
+                if consistent_arity {
+                    if let Err(error) = self.type_checker.is_task_consistent(
+                        &method.task,
+                        &method.task_terms,
+                        &method.params,
+                        &declared_constants,
+                        &declared_tasks,
+                        &HashSet::new(),
+                    ) {
+                        diagnostics.add_error(error);
                     }
                 }
             }
 
-            // Assert task type is consistent
-            let _ = self.type_checker.is_task_consistent(
-                &method.task,
-                &method.task_terms,
-                &method.params,
-                &declared_constants,
-                &declared_tasks,
-                &HashSet::new(),
-            )?;
-
             // Assert subtask types are consistent
             for subtask in method.tn.subtasks.iter() {
-                let _ = self.type_checker.is_task_consistent(
+                if let Err(error) = self.type_checker.is_task_consistent(
                     &subtask.task,
                     &subtask.terms,
                     &method.params,
                     &declared_constants,
                     &declared_tasks,
                     &declared_actions,
-                )?;
+                ) {
+                    diagnostics.add_error(error);
+                }
             }
             // Assert orderings are acyclic
             if !method.tn.orderings.is_acyclic() {
-                return Err(SemanticErrorType::CyclicOrderingDeclaration(
+                diagnostics.add_error(SemanticErrorType::CyclicOrderingDeclaration(
                     method.tn.ordering_pos.unwrap(),
                 ));
             }
         }
         // Check whether all compound tasks can be refined to primitive ones
         let tdg = TDG::new(self.domain);
-        for task in declared_tasks.iter() {
+        let mut checked_tasks = HashSet::new();
+        for task in self.domain.compound_tasks.iter() {
+            // duplicate declarations are checked once
+            if !checked_tasks.insert(task.name) {
+                continue;
+            }
             let reachables = tdg.reachable(&task.name);
             if (reachables.primitives.len() == 0) && (reachables.nullable == false) {
-                warnings.push(WarningType::NoPrimitiveRefinement(WarningInfo {
+                diagnostics.add_warning(WarningType::NoPrimitiveRefinement(WarningInfo {
                     symbol: task.name.to_string(),
                     position: task.name_pos,
                 }));
             }
         }
+        let used_predicates = self.check_unused_elements(diagnostics);
+        self.check_static_predicates(&used_predicates, diagnostics);
+        // Check the declared requirements against the used features
+        self.check_requirements(diagnostics);
         let type_hierarchy = self.type_checker.get_type_hierarchy();
-        Ok(SymbolTable {
-            warnings: warnings,
+        SymbolTable {
+            domain_name: &self.domain.name,
             constants: declared_constants,
             predicates: declared_predicates,
+            functions: declared_functions,
             tasks: declared_tasks,
             actions: declared_actions,
             type_hierarchy: type_hierarchy,
-        })
+        }
+    }
+
+    // records a warning for every type, predicate, and parameter that is never used,
+    // and returns the used predicates
+    fn check_unused_elements(&self, diagnostics: &mut Diagnostics) -> HashSet<&'a str> {
+        let mut used_types = HashSet::new();
+        let mut used_predicates = HashSet::new();
+        // types are used by parameters, constants, and other types (as their supertype)
+        let declarations = self
+            .domain
+            .types
+            .iter()
+            .chain(self.domain.constants.iter())
+            .flatten()
+            .chain(self.domain.predicates.iter().flat_map(|p| p.variables.iter()))
+            .chain(self.domain.functions.iter().flat_map(|f| f.variables.iter()))
+            .chain(self.domain.compound_tasks.iter().flat_map(|t| t.parameters.iter()))
+            .chain(self.domain.actions.iter().flat_map(|a| a.parameters.iter()))
+            .chain(self.domain.methods.iter().flat_map(|m| m.params.iter()));
+        collect_types(declarations, &mut used_types);
+        for action in self.domain.actions.iter() {
+            let mut used_terms = HashSet::new();
+            for formula in action
+                .preconditions
+                .iter()
+                .chain(action.effects.iter())
+                .chain(action.duration.iter())
+            {
+                collect_predicates(formula, &mut used_predicates);
+                collect_terms(formula, &mut used_terms);
+                collect_quantified_types(formula, &mut used_types);
+            }
+            for param in unused_parameters(&action.parameters, &used_terms) {
+                diagnostics.add_warning(WarningType::UnusedParameter(WarningInfo {
+                    symbol: param.name.to_string(),
+                    position: param.name_pos,
+                }));
+            }
+        }
+        for method in self.domain.methods.iter() {
+            let mut used_terms = HashSet::new();
+            if let Some(precondition) = &method.precondition {
+                collect_predicates(precondition, &mut used_predicates);
+                collect_terms(precondition, &mut used_terms);
+                collect_quantified_types(precondition, &mut used_types);
+            }
+            used_terms.extend(method.task_terms.iter().map(|term| term.name));
+            collect_tn_terms(&method.tn, &mut used_terms);
+            for param in unused_parameters(&method.params, &used_terms) {
+                diagnostics.add_warning(WarningType::UnusedParameter(WarningInfo {
+                    symbol: param.name.to_string(),
+                    position: param.name_pos,
+                }));
+            }
+        }
+        if let Some(problem) = self.problem {
+            collect_types(problem.objects.iter(), &mut used_types);
+            used_predicates.extend(problem.init_state.iter().map(|p| p.name));
+            if let Some(goal) = &problem.goal {
+                collect_predicates(goal, &mut used_predicates);
+            }
+        }
+        let mut reported = HashSet::new();
+        for declared_type in self.domain.types.iter().flatten() {
+            if !used_types.contains(declared_type.name) && reported.insert(declared_type.name) {
+                diagnostics.add_warning(WarningType::UnusedType(WarningInfo {
+                    symbol: declared_type.name.to_string(),
+                    position: declared_type.name_pos,
+                }));
+            }
+        }
+        for predicate in self.domain.predicates.iter() {
+            if !used_predicates.contains(predicate.name) && reported.insert(predicate.name) {
+                diagnostics.add_warning(WarningType::UnusedPredicate(WarningInfo {
+                    symbol: predicate.name.to_string(),
+                    position: predicate.name_pos,
+                }));
+            }
+        }
+        used_predicates
     }
 
-    // returns declared predicates (if there is no error)
-    fn verify_predicates(&'a self) -> Result<HashSet<&'a Predicate>, SemanticErrorType> {
+    // records a warning for every used predicate that no action changes, and for the
+    // preconditions on them that the initial state (if known) never satisfies
+    fn check_static_predicates(
+        &self,
+        used_predicates: &HashSet<&'a str>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let static_predicates = static_predicates(self.domain);
+        let mut reported = HashSet::new();
+        for predicate in self.domain.predicates.iter() {
+            if static_predicates.contains(predicate.name)
+                && used_predicates.contains(predicate.name)
+                && reported.insert(predicate.name)
+            {
+                diagnostics.add_warning(WarningType::ImmutablePredicate(WarningInfo {
+                    symbol: predicate.name.to_string(),
+                    position: predicate.name_pos,
+                }));
+            }
+        }
+        let problem = match self.problem {
+            Some(problem) => problem,
+            None => return,
+        };
+        let action_preconditions = self.domain.actions.iter().map(|action| {
+            (action.name, &action.parameters, &action.preconditions)
+        });
+        let method_preconditions = self.domain.methods.iter().map(|method| {
+            (method.name.name, &method.params, &method.precondition)
+        });
+        for (owner, parameters, precondition) in action_preconditions.chain(method_preconditions) {
+            if let Some(precondition) = precondition {
+                let variables = parameters.iter().map(|param| param.name).collect();
+                for (predicate, negated) in unsatisfiable_static_literals(
+                    precondition,
+                    false,
+                    &static_predicates,
+                    &problem.init_state,
+                    &variables,
+                ) {
+                    diagnostics.add_warning(WarningType::UnsatisfiableStaticPrecondition(
+                        StaticLiteralInfo {
+                            predicate: predicate.name.to_string(),
+                            terms: predicate.variables.iter().map(|var| var.name.to_string()).collect(),
+                            negated,
+                            owner: owner.to_string(),
+                            position: predicate.name_pos,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    // returns declared predicates, and records the errors in their declaration
+    fn verify_predicates(&'a self, diagnostics: &mut Diagnostics) -> HashSet<&'a Predicate> {
         let mut declared_predicates = HashSet::new();
         let mut predicate_positions = HashMap::new();
         for predicate in self.domain.predicates.iter() {
             if !declared_predicates.insert(predicate) {
-                return Err(SemanticErrorType::DuplicatePredicateDeclaration(
+                diagnostics.add_error(SemanticErrorType::DuplicatePredicateDeclaration(
                     DuplicateError {
                         symbol: predicate.name.to_string(),
                         first_pos: *predicate_positions.get(predicate.name).unwrap(),
@@ -219,19 +422,45 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 .type_checker
                 .check_type_declarations(&predicate.variables)
             {
-                return Err(error);
+                diagnostics.add_error(error);
+            }
+        }
+        declared_predicates
+    }
+
+    // returns declared functions, and records the errors in their declaration
+    fn verify_functions(&'a self, diagnostics: &mut Diagnostics) -> HashSet<&'a Predicate<'a>> {
+        let mut declared_functions = HashSet::new();
+        let mut function_positions = HashMap::new();
+        for function in self.domain.functions.iter() {
+            if !declared_functions.insert(function) {
+                diagnostics.add_error(SemanticErrorType::DuplicateFunctionDeclaration(
+                    DuplicateError {
+                        symbol: function.name.to_string(),
+                        first_pos: *function_positions.get(function.name).unwrap(),
+                        second_pos: function.name_pos,
+                    },
+                ));
+            } else {
+                function_positions.insert(function.name, function.name_pos);
+            }
+            if let Some(error) = self
+                .type_checker
+                .check_type_declarations(&function.variables)
+            {
+                diagnostics.add_error(error);
             }
         }
-        Ok(declared_predicates)
+        declared_functions
     }
 
-    // returns declared compound tasks (if there is no error)
-    fn verify_compound_tasks(&'a self) -> Result<HashSet<&Task<'a>>, SemanticErrorType> {
+    // returns declared compound tasks, and records the errors in their declaration
+    fn verify_compound_tasks(&'a self, diagnostics: &mut Diagnostics) -> HashSet<&Task<'a>> {
         let mut declared_tasks = HashSet::new();
         let mut task_positions = HashMap::new();
         for task in self.domain.compound_tasks.iter() {
             if !declared_tasks.insert(task) {
-                return Err(SemanticErrorType::DuplicateCompoundTaskDeclaration(
+                diagnostics.add_error(SemanticErrorType::DuplicateCompoundTaskDeclaration(
                     DuplicateError {
                         symbol: task.name.to_string(),
                         first_pos: *task_positions.get(task.name).unwrap(),
@@ -243,23 +472,72 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             }
             // assert parameter types are declared
             if let Some(error) = self.type_checker.check_type_declarations(&task.parameters) {
-                return Err(error);
+                diagnostics.add_error(error);
             }
         }
-        Ok(declared_tasks)
+        declared_tasks
+    }
+
+    // records a warning for every language feature whose requirement is not
+    // declared, and for every declared requirement that is never used
+    fn check_requirements(&self, diagnostics: &mut Diagnostics) {
+        for (name, position) in self.domain.unknown_requirements.iter() {
+            diagnostics.add_warning(WarningType::UnknownRequirement(WarningInfo {
+                symbol: name.clone(),
+                position: *position,
+            }));
+        }
+        let features = required_features(self.domain);
+        let mut reported = HashSet::new();
+        for (requirement, feature, position) in features.iter() {
+            let declared = self
+                .domain
+                .requirements
+                .iter()
+                .any(|declared| covers(*declared, *requirement));
+            if !declared && reported.insert(*requirement) {
+                diagnostics.add_warning(WarningType::MissingRequirement(RequirementInfo {
+                    requirement: requirement.to_string(),
+                    feature: feature.to_string(),
+                    position: *position,
+                }));
+            }
+        }
+        for requirement in unused_requirements(self.domain, &features) {
+            diagnostics.add_warning(WarningType::UnusedRequirement(WarningInfo {
+                symbol: requirement.to_string(),
+                position: self.domain.requirement_positions[&requirement][0],
+            }));
+        }
     }
 
     pub fn check_duplicate_requirements(
         requirements: &'a Vec<RequirementType>,
-    ) -> Option<SemanticErrorType> {
-        let mut names = HashSet::new();
+        positions: &HashMap<RequirementType, Vec<TokenPosition>>,
+    ) -> Vec<SemanticErrorType> {
+        // the number of times each requirement is declared so far
+        let mut occurrences: HashMap<RequirementType, usize> = HashMap::new();
+        let mut duplicates = vec![];
         for req in requirements {
-            if !names.insert(req) {
-                return Some(SemanticErrorType::DuplicateRequirementDeclaration(*req));
+            let occurrence = occurrences.entry(*req).or_default();
+            if *occurrence > 0 {
+                duplicates.push(SemanticErrorType::DuplicateRequirementDeclaration(DuplicateError {
+                    symbol: req.to_string(),
+                    first_pos: positions[req][0],
+                    second_pos: positions[req][*occurrence],
+                }));
             }
+            *occurrence += 1;
         }
-        None
+        duplicates
     }
-
-    // fn verify_formula(formula: &Formula<'a>, declared_predicates: HashSet<u>)
 }
+
+fn report_shadowed_variables(formula: &Formula, parameters: &[Symbol], diagnostics: &mut Diagnostics) {
+    for var in shadowed_variables(formula, parameters) {
+        diagnostics.add_warning(WarningType::ShadowedVariable(WarningInfo {
+            symbol: var.name.to_string(),
+            position: var.name_pos,
+        }));
+    }
+}
\ No newline at end of file
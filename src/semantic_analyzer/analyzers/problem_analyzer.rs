@@ -1,5 +1,5 @@
 use super::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 pub struct ProblemSemanticAnalyzer<'a> {
     problem: &'a ProblemAST<'a>,
@@ -17,70 +17,108 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
         }
     }
 
-    pub fn verify_problem(&self) -> Result<Vec<WarningType>, SemanticErrorType> {
-        if let Some(error) = self
-            .type_checker
-            .check_type_declarations(&self.problem.objects)
-        {
-            return Err(error);
+    // analyzes the whole problem, and records all errors and warnings in "diagnostics"
+    pub fn analyze(&self, diagnostics: &mut Diagnostics) {
+        // check the problem refers to the given domain
+        let domain_name = self.type_checker.symbol_table.domain_name;
+        if self.problem.domain_name != domain_name {
+            diagnostics.add_error(SemanticErrorType::InconsistentDomainName(DomainNameError {
+                expected: domain_name.to_string(),
+                found: self.problem.domain_name.clone(),
+                position: self.problem.domain_name_pos,
+            }));
+        }
+        for (name, position) in self.problem.unknown_requirements.iter() {
+            diagnostics.add_warning(WarningType::UnknownRequirement(WarningInfo {
+                symbol: name.clone(),
+                position: *position,
+            }));
+        }
+        // check object types are declared
+        for obj in self.problem.objects.iter() {
+            if let Some(error) = self.type_checker.check_type_declaration(obj) {
+                diagnostics.add_error(error);
+            }
         }
 
         // check for duplicate objects
-        let mut object_types = HashMap::new();
         let mut object_positions = HashMap::new();
         for obj in self.problem.objects.iter() {
-            if object_types.contains_key(obj.name) {
-                return Err(SemanticErrorType::DuplicateObjectDeclaration(
+            if object_positions.contains_key(obj.name) {
+                diagnostics.add_error(SemanticErrorType::DuplicateObjectDeclaration(
                     DuplicateError {
                         symbol: obj.name.to_string(),
                         first_pos: *object_positions.get(obj.name).unwrap(),
                         second_pos: obj.name_pos,
-                    }, 
+                    },
                 ));
             } else {
-                object_types.insert(obj.name, obj.symbol_type);
                 object_positions.insert(obj.name, obj.name_pos);
             }
         }
 
         // check the consistency of init predicates
         for predicate in self.problem.init_state.iter() {
-            let _ = self.type_checker.check_predicate_instantiation(predicate)?;
+            if let Err(error) = self
+                .type_checker
+                .check_predicate_instantiation(predicate, &Scope::new(&[]))
+            {
+                diagnostics.add_error(error);
+            }
+        }
+
+        // check the consistency of init function values
+        for value in self.problem.init_values.iter() {
+            if let Err(error) = self
+                .type_checker
+                .check_function_instantiation(&value.function, &Scope::new(&[]))
+            {
+                diagnostics.add_error(error);
+            }
+        }
+
+        // check the functions of the plan metric
+        if let Some(metric) = &self.problem.metric {
+            for function in metric.expression.get_functions() {
+                if let Err(error) = self
+                    .type_checker
+                    .check_function_instantiation(function, &Scope::new(&[]))
+                {
+                    diagnostics.add_error(error);
+                }
+            }
         }
 
         // check the initial task network
         if let Some(htn) = &self.problem.init_tn {
             if !htn.tn.orderings.is_acyclic() {
-                return Err(
-                    SemanticErrorType::CyclicOrderingDeclaration(
-                        htn.tn.ordering_pos.unwrap()
-                    )
-                );
+                diagnostics.add_error(SemanticErrorType::CyclicOrderingDeclaration(
+                    htn.tn.ordering_pos.unwrap(),
+                ));
             }
 
             for subtask in htn.tn.subtasks.iter() {
-                let _ = self
+                if let Err(error) = self
                     .type_checker
-                    .check_subtask_instantiation(subtask, &htn.parameters)?;
+                    .check_subtask_instantiation(subtask, &htn.parameters)
+                {
+                    diagnostics.add_error(error);
+                }
             }
         }
 
         // check goal description
         match &self.problem.goal {
             Some(goal) => {
-                for predicate in goal.get_propositional_predicates() {
-                    let _ = self.type_checker.check_predicate_instantiation(predicate)?;
+                self.type_checker.check_goal(goal, diagnostics);
+                for var in shadowed_variables(goal, &[]) {
+                    diagnostics.add_warning(WarningType::ShadowedVariable(WarningInfo {
+                        symbol: var.name.to_string(),
+                        position: var.name_pos,
+                    }));
                 }
             }
             None => {}
         }
-
-        Ok(self
-            .type_checker
-            .symbol_table
-            .warnings
-            .iter()
-            .cloned()
-            .collect())
     }
 }
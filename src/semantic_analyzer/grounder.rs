@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+// instantiates the actions, methods and compound tasks of a domain with the
+// objects of a problem (and the constants of the domain)
+pub struct Grounder<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    type_checker: TypeChecker<'a>,
+    objects: Vec<&'a Symbol<'a>>,
+    static_predicates: HashSet<&'a str>,
+    init_state: HashSet<Fact<'a>>,
+}
+
+// a ground predicate
+type Fact<'a> = (&'a str, Vec<&'a str>);
+
+// a condition on the parameters that is known before planning, i.e., a
+// literal of a static predicate, or an (in)equality
+enum StaticCheck<'a> {
+    Literal(&'a Predicate<'a>, bool),
+    Equality(&'a str, &'a str, bool),
+}
+
+impl<'a> StaticCheck<'a> {
+    fn terms(&self) -> Vec<&'a str> {
+        match self {
+            StaticCheck::Literal(predicate, _) => predicate.variables.iter().map(|term| term.name).collect(),
+            StaticCheck::Equality(a, b, _) => vec![a, b],
+        }
+    }
+}
+
+impl<'a> Grounder<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        symbol_table: &SymbolTable<'a>,
+    ) -> Grounder<'a> {
+        let mut objects: Vec<&Symbol> = problem.objects.iter().collect();
+        for constant in symbol_table.constants.iter() {
+            if !objects.iter().any(|object| object.name == constant.name) {
+                objects.push(constant);
+            }
+        }
+        Grounder {
+            domain,
+            problem,
+            type_checker: TypeChecker {
+                type_hierarchy: symbol_table.type_hierarchy.clone(),
+            },
+            objects,
+            static_predicates: static_predicates(domain),
+            init_state: problem
+                .init_state
+                .iter()
+                .map(|fact| (fact.name, fact.variables.iter().map(|term| term.name).collect()))
+                .collect(),
+        }
+    }
+
+    pub fn ground(&self) -> GroundModel {
+        let actions: Vec<GroundInstance> = self
+            .ground_actions()
+            .iter()
+            .map(|(action, args)| GroundInstance::new(action.name, args))
+            .collect();
+        let mut methods = vec![];
+        for method in self.domain.methods.iter() {
+            let mut checks = vec![];
+            if let Some(precondition) = &method.precondition {
+                self.collect_static_checks(precondition, &mut checks);
+            }
+            for constraint in method.tn.constraints.iter().flatten() {
+                checks.push(match constraint {
+                    Constraint::Equal(a, b) => StaticCheck::Equality(a, b, true),
+                    Constraint::NotEqual(a, b) => StaticCheck::Equality(a, b, false),
+                });
+            }
+            for args in self.instantiate(&method.params, &checks) {
+                let binding: HashMap<&str, &str> = method
+                    .params
+                    .iter()
+                    .map(|param| param.name)
+                    .zip(args.iter().cloned())
+                    .collect();
+                let substitute = |name: &str, terms: &[Symbol<'a>]| {
+                    let args: Vec<&str> = terms
+                        .iter()
+                        .map(|term| *binding.get(term.name).unwrap_or(&term.name))
+                        .collect();
+                    GroundInstance::new(name, &args)
+                };
+                methods.push(GroundMethod {
+                    name: method.name.name.to_string(),
+                    args: args.iter().map(|arg| arg.to_string()).collect(),
+                    task: substitute(method.task.name, &method.task_terms),
+                    subtasks: method
+                        .tn
+                        .subtasks
+                        .iter()
+                        .map(|subtask| substitute(subtask.task.name, &subtask.terms))
+                        .collect(),
+                });
+            }
+        }
+        let methods = Grounder::refinable_methods(&actions, methods);
+        let mut tasks: Vec<GroundInstance> = vec![];
+        for method in methods.iter() {
+            if !tasks.contains(&method.task) {
+                tasks.push(method.task.clone());
+            }
+        }
+        let initial_tasks = self
+            .problem
+            .init_tn
+            .iter()
+            .flat_map(|init_tn| init_tn.tn.subtasks.iter())
+            .map(|subtask| {
+                let args: Vec<&str> = subtask.terms.iter().map(|term| term.name).collect();
+                GroundInstance::new(subtask.task.name, &args)
+            })
+            .collect();
+        GroundModel {
+            actions,
+            tasks,
+            methods,
+            initial_tasks,
+        }
+    }
+
+    // returns the ground actions that are applicable in the delete relaxation of
+    // the problem, i.e., when effects never delete facts
+    pub fn relaxed_applicable_actions(&self) -> Vec<GroundInstance> {
+        let actions = self.ground_actions();
+        let mut facts = self.init_state.clone();
+        let mut is_applied = vec![false; actions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, (action, args)) in actions.iter().enumerate() {
+                if is_applied[index] {
+                    continue;
+                }
+                let binding: HashMap<&'a str, &'a str> =
+                    action.parameters.iter().map(|param| param.name).zip(args.iter().cloned()).collect();
+                let is_applicable = match &action.preconditions {
+                    Some(precondition) => self.relaxed_holds(precondition, &binding, &facts),
+                    None => true,
+                };
+                if is_applicable {
+                    is_applied[index] = true;
+                    changed = true;
+                    if let Some(effect) = &action.effects {
+                        self.add_relaxed_effects(effect, &binding, &mut facts);
+                    }
+                }
+            }
+        }
+        actions
+            .iter()
+            .zip(is_applied)
+            .filter(|(_, is_applied)| *is_applied)
+            .map(|((action, args), _)| GroundInstance::new(action.name, args))
+            .collect()
+    }
+
+    // the instances of the actions that satisfy their static preconditions
+    fn ground_actions(&self) -> Vec<(&'a Action<'a>, Vec<&'a str>)> {
+        let mut actions = vec![];
+        let mut action_names = HashSet::new();
+        for action in self.domain.actions.iter() {
+            // duplicate declarations are reported by the semantic analyzer
+            if !action_names.insert(action.name) {
+                continue;
+            }
+            let mut checks = vec![];
+            if let Some(precondition) = &action.preconditions {
+                self.collect_static_checks(precondition, &mut checks);
+            }
+            for args in self.instantiate(&action.parameters, &checks) {
+                actions.push((action, args));
+            }
+        }
+        actions
+    }
+
+    // whether "formula" can hold once "facts" are reached. Conditions that can not
+    // be decided without deletes (e.g., negated fluents) are assumed to hold.
+    fn relaxed_holds(&self, formula: &Formula<'a>, binding: &HashMap<&'a str, &'a str>, facts: &HashSet<Fact<'a>>) -> bool {
+        match formula {
+            Formula::Atom(predicate) => facts.contains(&ground_fact(predicate, binding)),
+            Formula::Equals(a, b) => value(binding, a.name) == value(binding, b.name),
+            Formula::Not(f) => match &**f {
+                Formula::Atom(predicate) if self.static_predicates.contains(predicate.name) => {
+                    !self.init_state.contains(&ground_fact(predicate, binding))
+                }
+                Formula::Equals(a, b) => value(binding, a.name) != value(binding, b.name),
+                _ => true,
+            },
+            Formula::And(fs) => fs.iter().all(|f| self.relaxed_holds(f, binding, facts)),
+            Formula::Or(fs) | Formula::Xor(fs) => fs.iter().any(|f| self.relaxed_holds(f, binding, facts)),
+            Formula::Exists(vars, f) => self.instantiate(vars, &[]).iter().any(|args| {
+                let mut binding = binding.clone();
+                binding.extend(vars.iter().map(|var| var.name).zip(args.iter().cloned()));
+                self.relaxed_holds(f, &binding, facts)
+            }),
+            Formula::Timed(_, f) => self.relaxed_holds(f, binding, facts),
+            // e.g., universal quantifiers, implications, and numeric comparisons
+            _ => true,
+        }
+    }
+
+    // adds the facts that "effect" can add (conditional effects are assumed to trigger)
+    fn add_relaxed_effects(&self, effect: &Formula<'a>, binding: &HashMap<&'a str, &'a str>, facts: &mut HashSet<Fact<'a>>) {
+        match effect {
+            Formula::Atom(predicate) => {
+                facts.insert(ground_fact(predicate, binding));
+            }
+            Formula::And(fs) => {
+                for f in fs {
+                    self.add_relaxed_effects(f, binding, facts);
+                }
+            }
+            Formula::Imply(_, consequents) => {
+                for f in consequents {
+                    self.add_relaxed_effects(f, binding, facts);
+                }
+            }
+            Formula::ForAll(vars, f) => {
+                for args in self.instantiate(vars, &[]) {
+                    let mut binding = binding.clone();
+                    binding.extend(vars.iter().map(|var| var.name).zip(args.iter().cloned()));
+                    self.add_relaxed_effects(f, &binding, facts);
+                }
+            }
+            Formula::Timed(_, f) => self.add_relaxed_effects(f, binding, facts),
+            _ => {}
+        }
+    }
+
+    // keeps the methods whose subtasks can all be refined into ground actions
+    fn refinable_methods(actions: &[GroundInstance], methods: Vec<GroundMethod>) -> Vec<GroundMethod> {
+        let mut refinables: HashSet<&GroundInstance> = actions.iter().collect();
+        let mut is_kept = vec![false; methods.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, method) in methods.iter().enumerate() {
+                if !is_kept[index] && method.subtasks.iter().all(|subtask| refinables.contains(subtask)) {
+                    is_kept[index] = true;
+                    refinables.insert(&method.task);
+                    changed = true;
+                }
+            }
+        }
+        methods
+            .iter()
+            .zip(is_kept)
+            .filter(|(_, is_kept)| *is_kept)
+            .map(|(method, _)| method.clone())
+            .collect()
+    }
+
+    // collects the static conditions in the top-level conjunction of "formula"
+    fn collect_static_checks(&self, formula: &'a Formula<'a>, checks: &mut Vec<StaticCheck<'a>>) {
+        match formula {
+            Formula::And(fs) => {
+                for f in fs {
+                    self.collect_static_checks(f, checks);
+                }
+            }
+            // static predicates have the same value at every time point
+            Formula::Timed(_, f) => self.collect_static_checks(f, checks),
+            Formula::Atom(predicate) if self.static_predicates.contains(predicate.name) => {
+                checks.push(StaticCheck::Literal(predicate, true));
+            }
+            Formula::Equals(a, b) => checks.push(StaticCheck::Equality(a.name, b.name, true)),
+            Formula::Not(f) => match &**f {
+                Formula::Atom(predicate) if self.static_predicates.contains(predicate.name) => {
+                    checks.push(StaticCheck::Literal(predicate, false));
+                }
+                Formula::Equals(a, b) => checks.push(StaticCheck::Equality(a.name, b.name, false)),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    // returns every assignment of type-compatible objects to "parameters"
+    // that satisfies "checks"
+    fn instantiate(&self, parameters: &[Symbol<'a>], checks: &[StaticCheck<'a>]) -> Vec<Vec<&'a str>> {
+        let candidates: Vec<Vec<&'a str>> = parameters
+            .iter()
+            .map(|param| {
+                self.objects
+                    .iter()
+                    .filter(|object| {
+                        self.type_checker
+                            .is_var_type_consistent(object.symbol_type.as_ref(), param.symbol_type.as_ref())
+                    })
+                    .map(|object| object.name)
+                    .collect()
+            })
+            .collect();
+        // each check is done as soon as the last of its parameters is assigned
+        let mut checks_at: Vec<Vec<&StaticCheck>> = (0..=parameters.len()).map(|_| vec![]).collect();
+        for check in checks {
+            let depth = check
+                .terms()
+                .iter()
+                .filter_map(|term| parameters.iter().position(|param| param.name == *term))
+                .map(|index| index + 1)
+                .max()
+                .unwrap_or(0);
+            checks_at[depth].push(check);
+        }
+        let mut assignments = vec![];
+        if checks_at[0].iter().all(|check| self.holds(check, parameters, &[])) {
+            self.assign(parameters, &candidates, &checks_at, &mut vec![], &mut assignments);
+        }
+        assignments
+    }
+
+    fn assign(
+        &self,
+        parameters: &[Symbol<'a>],
+        candidates: &[Vec<&'a str>],
+        checks_at: &[Vec<&StaticCheck<'a>>],
+        args: &mut Vec<&'a str>,
+        assignments: &mut Vec<Vec<&'a str>>,
+    ) {
+        if args.len() == parameters.len() {
+            assignments.push(args.clone());
+            return;
+        }
+        for object in candidates[args.len()].iter() {
+            args.push(object);
+            if checks_at[args.len()].iter().all(|check| self.holds(check, parameters, args)) {
+                self.assign(parameters, candidates, checks_at, args, assignments);
+            }
+            args.pop();
+        }
+    }
+
+    // whether "check" holds, given the objects assigned to (a prefix of) "parameters"
+    fn holds(&self, check: &StaticCheck<'a>, parameters: &[Symbol<'a>], args: &[&'a str]) -> bool {
+        let value = |term: &'a str| match parameters.iter().position(|param| param.name == term) {
+            Some(index) => args[index],
+            // every other term is a constant
+            None => term,
+        };
+        match check {
+            StaticCheck::Literal(predicate, positive) => {
+                let fact = (predicate.name, predicate.variables.iter().map(|term| value(term.name)).collect());
+                self.init_state.contains(&fact) == *positive
+            }
+            StaticCheck::Equality(a, b, positive) => (value(a) == value(b)) == *positive,
+        }
+    }
+}
+
+// the object bound to "term" (every unbound term is a constant)
+fn value<'a>(binding: &HashMap<&'a str, &'a str>, term: &'a str) -> &'a str {
+    binding.get(term).unwrap_or(&term)
+}
+
+fn ground_fact<'a>(predicate: &Predicate<'a>, binding: &HashMap<&'a str, &'a str>) -> Fact<'a> {
+    (predicate.name, predicate.variables.iter().map(|term| value(binding, term.name)).collect())
+}
\ No newline at end of file
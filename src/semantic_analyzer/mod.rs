@@ -1,15 +1,27 @@
-mod undefined_elements;
+mod unused_elements;
+mod static_facts;
+mod redundant_effects;
+mod requirements;
+mod reachability;
 mod type_checker;
 mod tests;
 mod analyzers;
 mod tdg;
+mod grounder;
 
 use crate::syntactic_analyzer::*;
 use crate::output::*;
-use undefined_elements::*;
+use unused_elements::*;
+use static_facts::*;
+use redundant_effects::*;
+use requirements::*;
 use type_checker::*;
 
 extern crate petgraph;
 
 pub use analyzers::*;
-pub use tdg::TDG;
\ No newline at end of file
+pub use tdg::TDG;
+pub use grounder::Grounder;
+pub use type_checker::TypeChecker;
+pub use static_facts::static_predicates;
+pub use reachability::check_reachability;
\ No newline at end of file
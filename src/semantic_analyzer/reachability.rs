@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// records a warning for every action that is never applicable in the delete
+// relaxation of the problem, for every method with such an action as a subtask,
+// and for every compound task that can only be refined by these methods
+pub fn check_reachability<'a>(domain: &'a DomainAST<'a>, grounder: &Grounder<'a>, diagnostics: &mut Diagnostics) {
+    let applicable_actions: HashSet<String> = grounder
+        .relaxed_applicable_actions()
+        .into_iter()
+        .map(|action| action.name)
+        .collect();
+    let mut never_applicable = HashSet::new();
+    for action in domain.actions.iter() {
+        if !applicable_actions.contains(action.name) && never_applicable.insert(action.name) {
+            diagnostics.add_warning(WarningType::NeverApplicableAction(WarningInfo {
+                symbol: action.name.to_string(),
+                position: action.name_pos,
+            }));
+        }
+    }
+    let mut pruned_methods = HashSet::new();
+    for method in domain.methods.iter() {
+        let subtask = method
+            .tn
+            .subtasks
+            .iter()
+            .find(|subtask| never_applicable.contains(subtask.task.name));
+        if let Some(subtask) = subtask {
+            pruned_methods.insert(method.name.name);
+            diagnostics.add_warning(WarningType::UnreachableMethod(UnreachableMethodInfo {
+                method: method.name.name.to_string(),
+                action: subtask.task.name.to_string(),
+                position: method.name.name_pos,
+            }));
+        }
+    }
+    // tasks without any refinement are already reported by the domain analysis
+    let refinables = TDG::new(domain).compute_refinables();
+    let mut pruned_tdg = TDG::new(domain);
+    pruned_tdg.prune_methods(&pruned_methods);
+    let problem_refinables = pruned_tdg.compute_refinables();
+    let mut reported = HashSet::new();
+    for task in domain.compound_tasks.iter() {
+        if refinables.contains(task.name) && !problem_refinables.contains(task.name) && reported.insert(task.name) {
+            diagnostics.add_warning(WarningType::UnrefinableTask(WarningInfo {
+                symbol: task.name.to_string(),
+                position: task.name_pos,
+            }));
+        }
+    }
+}
\ No newline at end of file
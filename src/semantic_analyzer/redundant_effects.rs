@@ -0,0 +1,61 @@
+use super::*;
+
+// returns the redundant literals in the effect of "action"
+pub fn redundant_effects<'a, 'b>(action: &'b Action<'a>) -> Vec<(&'b Predicate<'a>, bool, RedundancyType)> {
+    let effects = match &action.effects {
+        Some(effect) => conjunctive_literals(effect),
+        None => return vec![],
+    };
+    let preconditions = match &action.preconditions {
+        Some(precondition) => conjunctive_literals(precondition),
+        None => vec![],
+    };
+    let mut redundants = vec![];
+    for (i, (effect, negated)) in effects.iter().enumerate() {
+        let previous_effects = &effects[..i];
+        let reason = if previous_effects
+            .iter()
+            .any(|(other, other_negated)| other_negated == negated && is_same_atom(other, effect))
+        {
+            RedundancyType::Duplicate
+        } else if previous_effects
+            .iter()
+            .any(|(other, other_negated)| other_negated != negated && is_same_atom(other, effect))
+        {
+            RedundancyType::AddedAndDeleted
+        } else if preconditions
+            .iter()
+            .any(|(other, other_negated)| other_negated == negated && is_same_atom(other, effect))
+        {
+            RedundancyType::ImpliedByPrecondition
+        } else {
+            continue;
+        };
+        redundants.push((*effect, *negated, reason));
+    }
+    redundants
+}
+
+// returns the literals (and whether they are negated) that a conjunction consists of.
+// other formulas (e.g., quantified ones) are ignored.
+fn conjunctive_literals<'a, 'b>(formula: &'b Formula<'a>) -> Vec<(&'b Predicate<'a>, bool)> {
+    match formula {
+        Formula::Atom(predicate) => vec![(predicate, false)],
+        Formula::Not(f) => match &**f {
+            Formula::Atom(predicate) => vec![(predicate, true)],
+            _ => vec![],
+        },
+        Formula::And(fs) => fs.iter().flat_map(|f| conjunctive_literals(f)).collect(),
+        _ => vec![],
+    }
+}
+
+fn is_same_atom(first: &Predicate, second: &Predicate) -> bool {
+    first.name == second.name
+        && first.variables.len() == second.variables.len()
+        && first
+            .variables
+            .iter()
+            .zip(second.variables.iter())
+            .all(|(a, b)| a.name == b.name)
+}
\ No newline at end of file
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
+
+// returns the requirements that the language features of "domain" need, along
+// with a description of the feature, and the position of its first use
+pub fn required_features<'a>(domain: &DomainAST<'a>) -> Vec<(RequirementType, &'static str, TokenPosition)> {
+    let mut features = vec![];
+    // typing
+    let declared_type = domain.types.iter().flatten().next();
+    let typed_symbol = domain
+        .constants
+        .iter()
+        .flatten()
+        .chain(domain.predicates.iter().flat_map(|p| p.variables.iter()))
+        .chain(domain.functions.iter().flat_map(|f| f.variables.iter()))
+        .chain(domain.compound_tasks.iter().flat_map(|t| t.parameters.iter()))
+        .chain(domain.actions.iter().flat_map(|a| a.parameters.iter()))
+        .chain(domain.methods.iter().flat_map(|m| m.params.iter()))
+        .find(|symbol| symbol.symbol_type.is_some());
+    if let Some(symbol) = declared_type {
+        features.push((RequirementType::TypedObjects, "type declaration", symbol.name_pos));
+    } else if let Some(symbol) = typed_symbol {
+        let position = symbol.type_pos.unwrap_or(symbol.name_pos);
+        features.push((RequirementType::TypedObjects, "typed parameter", position));
+    }
+    // hierarchy
+    if let Some(task) = domain.compound_tasks.first() {
+        features.push((RequirementType::Hierarchy, "compound task", task.name_pos));
+    } else if let Some(method) = domain.methods.first() {
+        features.push((RequirementType::Hierarchy, "method", method.name.name_pos));
+    }
+    // numeric fluents (action costs allow static functions in "total-cost" increases)
+    let mut numeric_functions = HashSet::new();
+    for action in domain.actions.iter() {
+        for formula in [&action.preconditions, &action.effects, &action.duration].into_iter().flatten() {
+            collect_numeric_functions(formula, &mut numeric_functions);
+        }
+    }
+    for method in domain.methods.iter() {
+        if let Some(precondition) = &method.precondition {
+            collect_numeric_functions(precondition, &mut numeric_functions);
+        }
+    }
+    for function in domain.functions.iter() {
+        if function.name == "total-cost" {
+            features.push((RequirementType::ActionCosts, "action cost", function.name_pos));
+        } else if numeric_functions.contains(function.name) {
+            features.push((RequirementType::NumericFluents, "numeric fluent", function.name_pos));
+        } else {
+            features.push((RequirementType::ActionCosts, "static cost function", function.name_pos));
+        }
+    }
+    for action in domain.actions.iter() {
+        if action.is_durative() {
+            features.push((RequirementType::DurativeActions, "durative action", action.name_pos));
+        }
+        if action.duration.as_ref().is_some_and(has_inequality) {
+            features.push((RequirementType::DurationInequalities, "duration inequality", action.name_pos));
+        }
+        if let Some(precondition) = &action.preconditions {
+            condition_features(precondition, action.name_pos, &mut features);
+        }
+        if let Some(effect) = &action.effects {
+            effect_features(effect, action.name_pos, &mut features);
+        }
+    }
+    for method in domain.methods.iter() {
+        match &method.precondition {
+            None | Some(Formula::Empty) => {}
+            Some(precondition) => {
+                features.push((RequirementType::MethodPreconditions, "method precondition", method.name.name_pos));
+                condition_features(precondition, method.name.name_pos, &mut features);
+            }
+        }
+        if method.tn.constraints.as_ref().is_some_and(|constraints| !constraints.is_empty()) {
+            features.push((RequirementType::Equality, "method constraint", method.name.name_pos));
+        }
+    }
+    features
+}
+
+// returns the declared requirements that no language feature of "domain" needs
+pub fn unused_requirements(domain: &DomainAST, features: &[(RequirementType, &'static str, TokenPosition)]) -> Vec<RequirementType> {
+    let mut reported = HashSet::new();
+    domain
+        .requirements
+        .iter()
+        // ":strips" is the basis of every domain
+        .filter(|requirement| **requirement != RequirementType::Strips)
+        .filter(|requirement| !features.iter().any(|(used, _, _)| covers(**requirement, *used)))
+        .filter(|requirement| reported.insert(**requirement))
+        .copied()
+        .collect()
+}
+
+// whether declaring "declared" allows the use of "used"
+pub fn covers(declared: RequirementType, used: RequirementType) -> bool {
+    declared.expand().into_iter().any(|requirement| {
+        requirement == used
+            // action costs are a special case of numeric fluents
+            || (requirement == RequirementType::NumericFluents && used == RequirementType::ActionCosts)
+            // disjunctive preconditions allow arbitrary negations
+            || (requirement == RequirementType::DisjunctivePreconditions
+                && used == RequirementType::NegativePreconditions)
+    })
+}
+
+// "position" is the fallback position for the features of "formula" (e.g., the action name)
+fn condition_features(
+    formula: &Formula,
+    position: TokenPosition,
+    features: &mut Vec<(RequirementType, &'static str, TokenPosition)>,
+) {
+    match formula {
+        Formula::Empty | Formula::Atom(_) | Formula::Compare(..) | Formula::Assign(..) => {}
+        Formula::Equals(a, _) => {
+            features.push((RequirementType::Equality, "equality", a.name_pos));
+        }
+        Formula::Not(f) => {
+            // inequality only needs ":equality"
+            if !matches!(**f, Formula::Equals(_, _)) {
+                let position = first_position(f).unwrap_or(position);
+                features.push((RequirementType::NegativePreconditions, "negative precondition", position));
+            }
+            condition_features(f, position, features);
+        }
+        Formula::ForAll(vars, f) => {
+            let position = vars.first().map_or(position, |var| var.name_pos);
+            features.push((RequirementType::UniversalPreconditions, "universal precondition", position));
+            condition_features(f, position, features);
+        }
+        Formula::Exists(vars, f) => {
+            let position = vars.first().map_or(position, |var| var.name_pos);
+            features.push((RequirementType::ExistentialPreconditions, "existential precondition", position));
+            condition_features(f, position, features);
+        }
+        Formula::Timed(_, f) => condition_features(f, position, features),
+        Formula::And(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                condition_features(f, position, features);
+            }
+        }
+        Formula::Or(fs) => {
+            let position = first_position(formula).unwrap_or(position);
+            features.push((RequirementType::DisjunctivePreconditions, "disjunctive precondition", position));
+            for f in fs {
+                condition_features(f, position, features);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            let position = first_position(formula).unwrap_or(position);
+            features.push((RequirementType::DisjunctivePreconditions, "implication", position));
+            for f in ps.iter().chain(qs.iter()) {
+                condition_features(f, position, features);
+            }
+        }
+    }
+}
+
+// only the conditions of conditional effects have precondition features
+fn effect_features(
+    formula: &Formula,
+    position: TokenPosition,
+    features: &mut Vec<(RequirementType, &'static str, TokenPosition)>,
+) {
+    match formula {
+        Formula::Imply(conditions, effects) => {
+            let position = first_position(formula).unwrap_or(position);
+            features.push((RequirementType::ConditionalEffects, "conditional effect", position));
+            for f in conditions {
+                condition_features(f, position, features);
+            }
+            for f in effects {
+                effect_features(f, position, features);
+            }
+        }
+        Formula::And(fs) => {
+            for f in fs {
+                effect_features(f, position, features);
+            }
+        }
+        Formula::ForAll(vars, f) => {
+            let position = vars.first().map_or(position, |var| var.name_pos);
+            features.push((RequirementType::ConditionalEffects, "universal effect", position));
+            effect_features(f, position, features);
+        }
+        Formula::Timed(_, f) => effect_features(f, position, features),
+        _ => {}
+    }
+}
+
+// the functions that are compared, or changed by an effect other than a "total-cost" increase
+fn collect_numeric_functions<'a>(formula: &Formula<'a>, functions: &mut HashSet<&'a str>) {
+    match formula {
+        Formula::Compare(..) => functions.extend(formula.get_functions().iter().map(|f| f.name)),
+        Formula::Assign(assignment, function, value) => {
+            if function.name != "total-cost" || *assignment != AssignmentType::Increase {
+                functions.insert(function.name);
+                functions.extend(value.get_functions().iter().map(|f| f.name));
+            }
+        }
+        Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => {}
+        Formula::Not(f) | Formula::ForAll(_, f) | Formula::Exists(_, f) | Formula::Timed(_, f) => {
+            collect_numeric_functions(f, functions)
+        }
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                collect_numeric_functions(f, functions);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_numeric_functions(f, functions);
+            }
+        }
+    }
+}
+
+// whether a duration constraint bounds the duration, rather than fixing it
+fn has_inequality(duration: &Formula) -> bool {
+    match duration {
+        Formula::Compare(comparison, _, _) => *comparison != ComparisonType::Equal,
+        Formula::And(fs) => fs.iter().any(|f| has_inequality(f)),
+        _ => false,
+    }
+}
+
+// the position of the first predicate (or term) in "formula"
+fn first_position(formula: &Formula) -> Option<TokenPosition> {
+    match formula {
+        Formula::Empty => None,
+        Formula::Atom(predicate) => Some(predicate.name_pos),
+        Formula::Equals(a, _) => Some(a.name_pos),
+        Formula::Compare(..) | Formula::Assign(..) => formula.get_functions().first().map(|f| f.name_pos),
+        Formula::Not(f) | Formula::ForAll(_, f) | Formula::Exists(_, f) | Formula::Timed(_, f) => first_position(f),
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => fs.iter().find_map(|f| first_position(f)),
+        Formula::Imply(ps, qs) => ps.iter().chain(qs.iter()).find_map(|f| first_position(f)),
+    }
+}
\ No newline at end of file
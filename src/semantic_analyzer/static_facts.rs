@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// returns the predicates that do not appear in the effect of any action, i.e.,
+// their value is fixed by the initial state
+pub fn static_predicates<'a>(domain: &DomainAST<'a>) -> HashSet<&'a str> {
+    let mut mutables = HashSet::new();
+    for action in domain.actions.iter() {
+        if let Some(effect) = &action.effects {
+            collect_effect_predicates(effect, &mut mutables);
+        }
+    }
+    domain
+        .predicates
+        .iter()
+        .map(|predicate| predicate.name)
+        .filter(|name| !mutables.contains(name))
+        .collect()
+}
+
+// same as "collect_predicates", but ignores the conditions of conditional effects
+fn collect_effect_predicates<'a>(effect: &Formula<'a>, used: &mut HashSet<&'a str>) {
+    match effect {
+        Formula::Imply(_, consequents) => {
+            for f in consequents {
+                collect_effect_predicates(f, used);
+            }
+        }
+        Formula::And(fs) => {
+            for f in fs {
+                collect_effect_predicates(f, used);
+            }
+        }
+        Formula::ForAll(_, f) | Formula::Timed(_, f) => collect_effect_predicates(f, used),
+        _ => collect_predicates(effect, used),
+    }
+}
+
+// returns the literals of static predicates in "formula" that the initial state
+// can never satisfy, along with their polarity (true if negated). Only the literals
+// that must hold for the formula to hold are checked (e.g., not the branches of an "or").
+// "variables" are the parameters in scope, and every other term is a constant.
+pub fn unsatisfiable_static_literals<'a, 'b>(
+    formula: &'b Formula<'a>,
+    negated: bool,
+    static_predicates: &HashSet<&'a str>,
+    init_state: &[Predicate<'a>],
+    variables: &HashSet<&'a str>,
+) -> Vec<(&'b Predicate<'a>, bool)> {
+    let mut literals = vec![];
+    let mut visit = |f: &'b Formula<'a>, negated: bool, variables: &HashSet<&'a str>| {
+        literals.extend(unsatisfiable_static_literals(
+            f,
+            negated,
+            static_predicates,
+            init_state,
+            variables,
+        ))
+    };
+    match formula {
+        Formula::Empty | Formula::Equals(_, _) | Formula::Compare(..) | Formula::Assign(..) => {}
+        Formula::Atom(predicate) => {
+            if static_predicates.contains(predicate.name)
+                && !is_satisfiable(predicate, negated, init_state, variables)
+            {
+                return vec![(predicate, negated)];
+            }
+        }
+        Formula::Not(f) => visit(f, !negated, variables),
+        Formula::Timed(_, f) => visit(f, negated, variables),
+        // conjunctions, i.e., "(and ...)" or "(not (or ...))"
+        Formula::And(fs) if !negated => {
+            for f in fs {
+                visit(f, negated, variables);
+            }
+        }
+        Formula::Or(fs) if negated => {
+            for f in fs {
+                visit(f, negated, variables);
+            }
+        }
+        // "(not (imply p q))" is "(and p (not q))", where "(not q)" is a disjunction
+        // if "q" is a conjunction
+        Formula::Imply(ps, qs) if negated => {
+            for p in ps {
+                visit(p, !negated, variables);
+            }
+            if let [q] = &qs[..] {
+                visit(q, negated, variables);
+            }
+        }
+        // another branch of a disjunction can satisfy the formula
+        Formula::And(_) | Formula::Or(_) | Formula::Xor(_) | Formula::Imply(..) => {}
+        Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+            let mut scope = variables.clone();
+            scope.extend(vars.iter().map(|var| var.name));
+            visit(f, negated, &scope);
+        }
+    }
+    literals
+}
+
+fn is_satisfiable<'a>(
+    predicate: &Predicate<'a>,
+    negated: bool,
+    init_state: &[Predicate<'a>],
+    variables: &HashSet<&'a str>,
+) -> bool {
+    let matches = |fact: &&Predicate<'a>| {
+        fact.name == predicate.name
+            && fact.variables.len() == predicate.variables.len()
+            && fact
+                .variables
+                .iter()
+                .zip(predicate.variables.iter())
+                .all(|(value, term)| variables.contains(term.name) || value.name == term.name)
+    };
+    match negated {
+        // some fact in the initial state must match the literal
+        false => init_state.iter().any(|fact| matches(&fact)),
+        // a negative literal can only be violated if it is ground
+        true => {
+            predicate.variables.iter().any(|term| variables.contains(term.name))
+                || !init_state.iter().any(|fact| matches(&fact))
+        }
+    }
+}
\ No newline at end of file
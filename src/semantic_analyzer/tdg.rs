@@ -36,27 +36,22 @@ impl<'a> TDG<'a> {
         // collect "task to method" edges
         for (method_index, method) in domain.methods.iter().enumerate() {
             methods.push((&method.name, method.tn.clone()));
-            match task_indices.get(method.task.name) {
-                Some(task_index) => match to_methods.get_mut(task_index) {
-                    Some(set) => {
-                        set.insert(method_index);
-                    }
-                    None => panic!("{} not found", task_index),
-                },
-                None => panic!("{} is not defined", method.task.name),
+            // methods of undefined tasks are reported by the semantic analyzer,
+            // and do not contribute to the graph
+            if let Some(task_index) = task_indices.get(method.task.name) {
+                if let Some(set) = to_methods.get_mut(task_index) {
+                    set.insert(method_index);
+                }
             }
         }
 
-        // collect "method to task" edges
+        // collect "method to task" edges (undefined subtasks are skipped)
         for (method_index, method) in methods.iter().enumerate() {
             let tasks: HashSet<usize> = method
                 .1
                 .subtasks
                 .iter()
-                .map(|x| match task_indices.get(x.task.name) {
-                    Some(id) => *id,
-                    None => panic!("{} not found", x.task.name),
-                })
+                .filter_map(|x| task_indices.get(x.task.name).cloned())
                 .collect();
             to_tasks.insert(method_index, tasks);
         }
@@ -69,7 +64,6 @@ impl<'a> TDG<'a> {
     }
 
     pub fn reachable(&self, task_name: &str) -> ReachableSet {
-        let mut reach_t = HashSet::new();
         let task_index = match self
             .tasks
             .iter()
@@ -90,6 +84,32 @@ impl<'a> TDG<'a> {
                     i
                 }
             };
+        let reach_t = self.reachable_indices(task_index);
+        let nullables = self.compute_nullables();
+        let mut primitives = HashSet::new();
+        let mut compounds = HashSet::new();
+        for (index, (reachable_name, reachable_type)) in self.tasks.iter().enumerate() {
+            if reach_t.contains(&index) {
+                match reachable_type {
+                    TaskType::Primitive => {
+                        primitives.insert(*reachable_name);
+                    }
+                    TaskType::Compound => {
+                        compounds.insert(*reachable_name);
+                    }
+                }
+            }
+        }
+        ReachableSet {
+            primitives,
+            compounds,
+            nullable: nullables.contains(task_name),
+        }
+    }
+
+    // the indices of the tasks reachable from "task_index" (including itself)
+    fn reachable_indices(&self, task_index: usize) -> HashSet<usize> {
+        let mut reach_t = HashSet::new();
         reach_t.insert(task_index);
         let mut visited= HashSet::new();
         let mut queue = VecDeque::from([task_index]);
@@ -112,30 +132,26 @@ impl<'a> TDG<'a> {
             }
             
         }
-        
-        let nullables = self.compute_nullables();
-        let mut primitives = HashSet::new();
-        let mut compounds = HashSet::new();
-        for (index, (reachable_name, reachable_type)) in self.tasks.iter().enumerate() {
-            if reach_t.contains(&index) {
-                match reachable_type {
-                    TaskType::Primitive => {
-                        primitives.insert(*reachable_name);
-                    }
-                    TaskType::Compound => {
-                        compounds.insert(*reachable_name);
-                    }
-                }
-            }
-        }
-        ReachableSet {
-            primitives,
-            compounds,
-            nullable: nullables.contains(task_name),
-        }
+        reach_t
     }
 
     pub fn get_recursion_type(&self, nullable_symbols: &HashSet<&'a str>) -> RecursionType {
+        let tasks = self.edges_from_tasks.keys().cloned().collect();
+        self.recursion_type(nullable_symbols, tasks)
+    }
+
+    // same as "get_recursion_type", but only considers the tasks that are
+    // reachable from "initial_tasks" (e.g., the initial task network)
+    pub fn get_recursion_type_from(&self, nullable_symbols: &HashSet<&'a str>, initial_tasks: &[&str]) -> RecursionType {
+        let tasks = initial_tasks
+            .iter()
+            .filter_map(|task| self.find_task_index(task))
+            .flat_map(|task| self.reachable_indices(task))
+            .collect();
+        self.recursion_type(nullable_symbols, tasks)
+    }
+
+    fn recursion_type(&self, nullable_symbols: &HashSet<&'a str>, tasks: HashSet<usize>) -> RecursionType {
         let nullables: HashSet<usize> = nullable_symbols
             .iter()
             .map(|x| self.get_task_index(&x))
@@ -144,8 +160,10 @@ impl<'a> TDG<'a> {
         // DFS over TDG
         let mut stack = vec![];
         // initiating the stack
-        // TODO: restrict to those reachable from inital task network
         for (t, methods) in self.edges_from_tasks.iter() {
+            if !tasks.contains(t) {
+                continue;
+            }
             for method in methods {
                 stack.push(vec![(*t, *method)]);
             }
@@ -392,12 +410,54 @@ impl<'a> TDG<'a> {
     }
 
     fn get_task_index(&self, task_name: &str) -> usize {
+        self.find_task_index(task_name).unwrap()
+    }
+
+    fn find_task_index(&self, task_name: &str) -> Option<usize> {
         self.tasks
+            .iter()
+            .position(|(name, _)| *name == task_name)
+    }
+
+    // whether "task_name" is a declared task (either primitive or compound)
+    pub fn contains(&self, task_name: &str) -> bool {
+        self.find_task_index(task_name).is_some()
+    }
+
+    // removes the methods named in "method_names" from the graph, e.g., the
+    // methods that can never be applied in a given problem
+    pub fn prune_methods(&mut self, method_names: &HashSet<&str>) {
+        let methods = &self.methods;
+        for task_methods in self.edges_from_tasks.values_mut() {
+            task_methods.retain(|method| !method_names.contains(methods[*method].0.name));
+        }
+    }
+
+    // the tasks that can be refined into primitive tasks, i.e., primitive tasks and
+    // compound tasks with a method whose subtasks are all refinable
+    pub fn compute_refinables(&self) -> HashSet<&'a str> {
+        let mut refinables: HashSet<usize> = self
+            .tasks
             .iter()
             .enumerate()
-            .find(|(_, (name, t_type))| *name == task_name)
-            .unwrap()
-            .0
+            .filter(|(_, (_, task_type))| *task_type == TaskType::Primitive)
+            .map(|(index, _)| index)
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (task, methods) in self.edges_from_tasks.iter() {
+                if !refinables.contains(task)
+                    && methods.iter().any(|method| {
+                        self.edges_to_tasks.get(method).unwrap().iter().all(|t| refinables.contains(t))
+                    })
+                {
+                    refinables.insert(*task);
+                    changed = true;
+                }
+            }
+        }
+        refinables.into_iter().map(|index| self.tasks[index].0).collect()
     }
 
     pub fn compute_nullables(&self) -> HashSet<&'a str> {
@@ -438,7 +498,8 @@ impl<'a> TDG<'a> {
             }
         }
         let mut changed_nullables = true;
-        let mut changed_unit_reachability = true;
+        // without compound tasks, there is nothing to compute
+        let mut changed_unit_reachability = !unit_reachability.is_empty();
         let mut new_nullables = HashSet::new();
         let mut new_unit_reachable: HashMap<usize, HashSet<usize>> = HashMap::new();
         while changed_nullables || changed_unit_reachability {
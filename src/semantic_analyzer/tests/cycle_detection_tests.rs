@@ -39,7 +39,7 @@ pub fn cyclic_method_ordering_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -78,14 +78,16 @@ pub fn cyclic_types_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
                 Err(error) => {
                     match error {
-                        SemanticErrorType::CyclicTypeDeclaration => {
-                            // TODO: assert locality in future
+                        SemanticErrorType::CyclicTypeDeclaration(position) => {
+                            // "t7 - t1" closes the cycle
+                            assert_eq!(position.line, 6);
+                            assert_eq!(position.column, 13);
                         }
                         _ => {
                             panic!("caught wrong error")
@@ -0,0 +1,437 @@
+use std::collections::HashSet;
+
+use super::*;
+
+#[test]
+pub fn multiple_domain_errors_test() {
+    let program = String::from(
+        "(define (domain bal) (:requirements :typing :hierarchy)
+            (:types t1 t2)
+            (:predicates 
+                (at ?l - t1)
+                (hold ?a ?b)
+            )
+            (:action a_1
+             :parameters (?p_1 - t2)
+             :precondition (at ?p_1)
+             :effect (not (hold ?p_1))
+            )
+            (:action a_2
+             :parameters (?p_1)
+             :precondition (wro ?p_1)
+            )
+            (:task abs_1 :parameters(?a))
+            (:method m_1
+                :parameters (?p1) 
+                :task (abs_2 ?p1)
+                :ordered-subtasks (and
+                    (t1 (a_2 ?p1))
+                )
+            )
+         ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            let _ = semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 4);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(x)) => {
+                    assert_eq!(x.var_name, "p_1");
+                    assert_eq!(x.position.line, 9);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArity(x)) => {
+                    assert_eq!(x.symbol, "hold");
+                    assert_eq!(x.position.line, 10);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &diagnostics.errors[2] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(x)) => {
+                    assert_eq!(x.symbol, "wro");
+                    assert_eq!(x.position.line, 14);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &diagnostics.errors[3] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedTask(x)) => {
+                    assert_eq!(x.symbol, "abs_2");
+                    assert_eq!(x.position.line, 19);
+                }
+                error => panic!("{:?}", error),
+            }
+            // abs_1 has no method, hence no primitive refinement, and no action changes "at"
+            assert_eq!(diagnostics.warnings.len(), 2);
+            assert!(matches!(diagnostics.warnings[0], WarningType::NoPrimitiveRefinement(_)));
+            assert!(matches!(diagnostics.warnings[1], WarningType::ImmutablePredicate(_)));
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn multiple_formula_errors_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:predicates (at ?l))
+            (:action a_1
+             :parameters (?l)
+             :precondition (and (foo ?l) (bar ?l) (at ?zz))
+             :effect (and (baz ?l) (at ?l ?l))
+            )
+         ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            let _ = semantic_parser.analyze(&mut diagnostics);
+            // every bad atom is reported, not only the first one of each formula
+            let errors: Vec<String> = diagnostics
+                .errors
+                .iter()
+                .map(|error| match error {
+                    ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(x)) => format!("predicate {}", x.symbol),
+                    ParsingError::Semantic(SemanticErrorType::UndefinedParameter(x)) => format!("parameter {}", x.symbol),
+                    ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArity(x)) => format!("arity {}", x.symbol),
+                    error => panic!("{:?}", error),
+                })
+                .collect();
+            assert_eq!(errors, vec!["predicate foo", "predicate bar", "parameter zz", "predicate baz", "arity at"]);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn diagnostics_limit_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:requirements :hierarchy :hierarchy :typing :typing)
+            (:predicates 
+                (at ?l)
+                (at ?l)
+                (at ?l)
+            )
+         ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(Some(3));
+            let _ = semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 3);
+            assert_eq!(diagnostics.error_count(), 4);
+            // the unused predicate and requirement warnings are omitted as well
+            assert_eq!(diagnostics.omitted(), 4);
+            assert!(diagnostics.has_errors());
+            assert!(matches!(
+                diagnostics.errors[0],
+                ParsingError::Semantic(SemanticErrorType::DuplicateRequirementDeclaration(..))
+            ));
+            assert!(matches!(
+                diagnostics.errors[2],
+                ParsingError::Semantic(SemanticErrorType::DuplicatePredicateDeclaration(_))
+            ));
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn multiple_problem_errors_test() {
+    let domain = String::from(
+        "(define (domain bal)
+            (:types t1 t2)
+            (:predicates 
+                (at ?a - t1)
+            )
+         ) ").into_bytes();
+    let domain_lexer = LexicalAnalyzer::new(&domain);
+    let domain_parser = Parser::new(domain_lexer);
+    let domain_ast = domain_parser.parse().unwrap();
+    match domain_ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let symbols = verify_domain(&semantic_parser).unwrap();
+            let problem = String::from(
+                "(define (problem p1) (domain bal)
+                    (:objects
+                        a - t1
+                        b - t3
+                        a - t2
+                    )
+                    (:init (at c) (at b))
+                )",
+            ).into_bytes();
+            let problem_lexer = LexicalAnalyzer::new(&problem);
+            let problem_parser = Parser::new(problem_lexer);
+            let problem_ast = problem_parser.parse().unwrap();
+            match problem_ast {
+                AbstractSyntaxTree::Problem(p) => {
+                    let semantic_parser = ProblemSemanticAnalyzer::new(&p, symbols);
+                    let mut diagnostics = Diagnostics::new(None);
+                    semantic_parser.analyze(&mut diagnostics);
+                    assert_eq!(diagnostics.errors.len(), 4);
+                    assert!(matches!(
+                        diagnostics.errors[0],
+                        ParsingError::Semantic(SemanticErrorType::UndefinedType(_))
+                    ));
+                    assert!(matches!(
+                        diagnostics.errors[1],
+                        ParsingError::Semantic(SemanticErrorType::DuplicateObjectDeclaration(_))
+                    ));
+                    assert!(matches!(
+                        diagnostics.errors[2],
+                        ParsingError::Semantic(SemanticErrorType::UndefinedObject(_))
+                    ));
+                    assert!(matches!(
+                        diagnostics.errors[3],
+                        ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(_))
+                    ));
+                }
+                _ => panic!()
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn multiple_goal_errors_test() {
+    let domain = String::from(
+        "(define (domain bal) (:requirements :typing)
+            (:types t1 t2)
+            (:predicates (at ?a - t1) (free ?a - t2))
+         ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p1) (:domain bal)
+            (:objects a - t1 b - t2)
+            (:init (at a) (free b))
+            (:goal (and (at b) (free a) (or (hold a) (at a))))
+        )",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+    // every ill-typed atom of the goal is reported, not only the first one
+    let errors: Vec<String> = diagnostics
+        .errors
+        .iter()
+        .map(|error| match error {
+            ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(x)) => format!("predicate {}", x.symbol),
+            ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(x)) => format!("type {}", x.var_name),
+            error => panic!("{:?}", error),
+        })
+        .collect();
+    assert_eq!(errors, vec!["type at", "type free", "predicate hold"]);
+}
+
+#[test]
+pub fn render_diagnostics_test() {
+    let domain = String::from(
+        "(define (domain bal)
+            (:predicates (at ?x) (hold ?x) (at ?y))
+            (:action a_1
+             :parameters (?x)
+             :precondition (near ?x)
+             :effect (hold ?x)
+            )
+        )",
+    )
+    .into_bytes();
+    let problem = String::from("(define (problem p) (domain bal)\n (:init (hold a)))").into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+    let mut renderer = Renderer::new(false);
+    renderer.add_source(InputFile::Domain, "domain.hddl", &domain);
+    renderer.add_source(InputFile::Problem, "problem.hddl", &problem);
+    let rendered: Vec<String> = diagnostics
+        .located_errors()
+        .map(|(file, error)| renderer.render_error(file, error))
+        .collect();
+    assert_eq!(rendered.len(), 3);
+    assert_eq!(
+        rendered[0],
+        "error[HDDL-E012]: predicate 'at' is first defined at 2:27, and then redefined at 2:45.
+ --> domain.hddl:2:45
+  |
+2 |             (:predicates (at ?x) (hold ?x) (at ?y))
+  |                           -- 'at' is first defined here
+  |                                             ^^
+"
+    );
+    assert_eq!(
+        rendered[1],
+        "error[HDDL-E020]: predicate near is not defined.
+ --> domain.hddl:5:29
+  |
+5 |              :precondition (near ?x)
+  |                             ^^^^
+"
+    );
+    assert_eq!(
+        rendered[2],
+        "error[HDDL-E025]: object a is not defined.
+ --> problem.hddl:2:15
+  |
+2 |  (:init (hold a)))
+  |               ^
+"
+    );    // the underline is aligned by characters, rather than bytes
+    let domain = String::from("(define (domain bal)\n (:predicates (ü ?x) (ü ?y)))").into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    let mut renderer = Renderer::new(false);
+    renderer.add_source(InputFile::Domain, "domain.hddl", &domain);
+    let (file, error) = diagnostics.located_errors().next().unwrap();
+    assert_eq!(
+        renderer.render_error(file, error),
+        "error[HDDL-E012]: predicate 'ü' is first defined at 2:16, and then redefined at 2:24.
+ --> domain.hddl:2:24
+  |
+2 |  (:predicates (ü ?x) (ü ?y)))
+  |                - 'ü' is first defined here
+  |                       ^
+"
+    );
+}
+
+#[test]
+pub fn json_diagnostics_test() {
+    let domain = String::from(
+        "(define (domain bal) (:requirements :typing :hierarchy)
+            (:types t1)
+            (:predicates (at ?x - t2))
+            (:task t_1 :parameters ())
+        )",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    let file_names = std::collections::HashMap::from([(InputFile::Domain, "domain.hddl")]);
+    let report: serde_json::Value =
+        serde_json::from_str(&diagnostics_to_json(&diagnostics, &file_names)).unwrap();
+    assert_eq!(report["success"], false);
+    assert_eq!(report["error_count"], 1);
+    // no primitive refinement, unused type t1, and unused predicate at
+    assert_eq!(report["warning_count"], 3);
+    let error = &report["diagnostics"][0];
+    assert_eq!(error["severity"], "error");
+    assert_eq!(error["code"], "HDDL-E021");
+    assert_eq!(error["kind"], "UndefinedType");
+    assert_eq!(error["file"], "domain.hddl");
+    assert_eq!(error["message"], "type t2 is not defined.");
+    assert_eq!(error["position"]["line"], 3);
+    assert_eq!(error["position"]["column"], 35);
+    assert_eq!(error["data"]["symbol"], "t2");
+    let warning = &report["diagnostics"][1];
+    assert_eq!(warning["severity"], "warning");
+    assert_eq!(warning["code"], "HDDL-W003");
+    assert_eq!(warning["kind"], "NoPrimitiveRefinement");
+}
+
+#[test]
+pub fn sarif_diagnostics_test() {
+    let domain = String::from(
+        "(define (domain bal)
+            (:predicates (at ?x) (at ?y))
+        )",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    let file_names = std::collections::HashMap::from([(InputFile::Domain, "domain.hddl")]);
+    let log: serde_json::Value =
+        serde_json::from_str(&diagnostics_to_sarif(&diagnostics, &file_names, &std::collections::HashMap::new())).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), RULES.len());
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "HDDL-E012");
+    assert_eq!(rules[result["ruleIndex"].as_u64().unwrap() as usize]["id"], "HDDL-E012");
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "domain.hddl");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 35);
+    assert_eq!(location["region"]["endColumn"], 37);
+    let related = &result["relatedLocations"][0]["physicalLocation"]["region"];
+    assert_eq!(related["startColumn"], 27);
+    // columns are counted in UTF-16 code units, rather than bytes
+    let domain = String::from("(define (domain bal) ; größe 𝄞\n (:predicates (ü ?x) (ü ?y)))").into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    let sources = std::collections::HashMap::from([(InputFile::Domain, domain.as_slice())]);
+    let log: serde_json::Value =
+        serde_json::from_str(&diagnostics_to_sarif(&diagnostics, &file_names, &sources)).unwrap();
+    assert_eq!(log["runs"][0]["columnKind"], "utf16CodeUnits");
+    let region = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 23);
+    assert_eq!(region["endColumn"], 24);
+    assert_eq!(region["byteLength"], 2);
+}
+
+#[test]
+pub fn unique_rule_codes_test() {
+    let mut codes = HashSet::new();
+    let mut names = HashSet::new();
+    for rule in RULES {
+        assert!(codes.insert(rule.code), "duplicate code {}", rule.code);
+        assert!(names.insert(rule.name), "duplicate rule {}", rule.name);
+    }
+}
+
+#[test]
+pub fn warning_levels_test() {
+    let domain = String::from(
+        "; hddl-analyzer: allow(W003)
+        (define (domain bal) (:requirements :hierarchy)
+            (:task t_1 :parameters ())
+        )",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    assert!(diagnostics.warnings.is_empty());
+    assert_eq!(diagnostics.suppressed(), 1);
+    // the command line takes precedence over the directives
+    let mut diagnostics = Diagnostics::new(None);
+    diagnostics.set_level(find_warning("NoPrimitiveRefinement").unwrap(), Level::Deny);
+    crate::HDDLAnalyzer::analyze(&domain, None, &mut diagnostics);
+    assert!(diagnostics.has_errors());
+    assert_eq!((diagnostics.error_count(), diagnostics.warning_count()), (1, 0));
+    let (_, severity, _) = diagnostics.located_warnings().next().unwrap();
+    assert_eq!(severity, Severity::Error);
+    // directives only apply to the file they are written in
+    let problem = String::from(
+        "; hddl-analyzer: deny(HDDL-W003)
+        (define (problem p) (:domain bal) (:objects))",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+    assert!(!diagnostics.has_errors());
+    assert_eq!(diagnostics.suppressed(), 1);
+}
+
+#[test]
+pub fn parse_directive_test() {
+    let directives = parse_directive("; hddl-analyzer: deny(W001, unusedtype, E020, W999)");
+    assert_eq!(directives.len(), 2);
+    assert_eq!((directives[0].0.code, directives[0].1), ("HDDL-W001", Level::Deny));
+    assert_eq!(directives[1].0.code, "HDDL-W005");
+    assert!(parse_directive("; allow(W001)").is_empty());
+}
\ No newline at end of file
@@ -27,7 +27,7 @@ pub fn objects_duplicate_test() {
     match domain_ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            let symbols = semantic_parser.verify_domain().unwrap();
+            let symbols = verify_domain(&semantic_parser).unwrap();
             let problem = String::from(
                 "(define (problem p1) (domain bal)
                     (:objects
@@ -44,7 +44,7 @@ pub fn objects_duplicate_test() {
             match problem_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let semantic_parser = ProblemSemanticAnalyzer::new(&p, symbols);
-                    match semantic_parser.verify_problem() {
+                    match verify_problem(&semantic_parser) {
                         Err(SemanticErrorType::DuplicateObjectDeclaration(x)) => {
                             assert_eq!(x.symbol, "a");
                             assert_eq!(x.first_pos.line, 3);
@@ -66,7 +66,8 @@ pub fn objects_duplicate_test() {
 pub fn requirements_duplicate_test() {
     let program = String::from(
         "(define (domain bal)
-            (:requirements :hierarchy :method-preconditions :hierarchy :negative-preconditions)
+            (:requirements :hierarchy :method-preconditions
+                :hierarchy :negative-preconditions)
 
          ) ",
     )
@@ -77,15 +78,16 @@ pub fn requirements_duplicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
                 Err(error) => {
                     match error {
                         SemanticErrorType::DuplicateRequirementDeclaration(x) => {
-                            assert!(matches!(x, RequirementType::Hierarchy))
-                            // TODO: assert locality in future
+                            assert_eq!(x.symbol, ":hierarchy");
+                            assert_eq!((x.first_pos.line, x.first_pos.column), (2, 28));
+                            assert_eq!((x.second_pos.line, x.second_pos.column), (3, 17));
                         }
                         _ => {
                             panic!("caught wrong error")
@@ -117,7 +119,7 @@ pub fn predicates_duplicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -170,7 +172,7 @@ pub fn action_duplicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -212,7 +214,7 @@ pub fn compound_task_duplicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -272,7 +274,7 @@ pub fn method_duplicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
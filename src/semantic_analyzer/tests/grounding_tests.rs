@@ -0,0 +1,233 @@
+use super::*;
+
+fn get_domain() -> Vec<u8> {
+    String::from(
+        "(define (domain transport)
+            (:requirements :typing :hierarchy :negative-preconditions :equality)
+            (:types location vehicle - object)
+            (:constants depot - location)
+            (:predicates (road ?a ?b - location) (at ?v - vehicle ?l - location))
+            (:task deliver :parameters (?v - vehicle ?l - location))
+            (:method m_drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :task (deliver ?v ?b)
+                :ordered-subtasks (and (drive ?v ?a ?b))
+            )
+            (:action drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :precondition (and (at ?v ?a) (road ?a ?b) (not (= ?a ?b)))
+                :effect (and (not (at ?v ?a)) (at ?v ?b))
+            )
+            (:action noop
+                :parameters (?v - vehicle ?l - location)
+                :precondition (at ?v ?l)
+            )
+            (:method m_stay
+                :parameters (?v - vehicle ?a ?b - location)
+                :task (deliver ?v ?b)
+                :ordered-subtasks (and (noop ?v ?a))
+                :constraints (and (= ?a ?b))
+            )
+        )",
+    )
+    .into_bytes()
+}
+
+#[test]
+pub fn grounding_test() {
+    let domain = get_domain();
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location t1 - vehicle)
+            (:htn :parameters () :ordered-subtasks (and (deliver t1 b)))
+            (:init (road depot a) (road a b) (at t1 depot))
+        )",
+    )
+    .into_bytes();
+    let d_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let p_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    let d_ast = d_parser.parse().unwrap();
+    let p_ast = p_parser.parse().unwrap();
+    match (d_ast, p_ast) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let d_analyzer = DomainSemanticAnalyzer::new(&d);
+            let symbol_table = verify_domain(&d_analyzer).unwrap();
+            let model = Grounder::new(&d, &p, &symbol_table).ground();
+            let actions: Vec<String> = model.actions.iter().map(|action| action.to_string()).collect();
+            // "drive" is pruned by the static "road" facts, and the constant "depot" is an object
+            assert_eq!(actions.len(), 5);
+            assert!(actions.contains(&"(drive t1 depot a)".to_string()));
+            assert!(actions.contains(&"(drive t1 a b)".to_string()));
+            assert!(actions.contains(&"(noop t1 depot)".to_string()));
+            // "m_stay" respects the equality constraint
+            let stays: Vec<&GroundMethod> = model.methods.iter().filter(|m| m.name == "m_stay").collect();
+            assert_eq!(stays.len(), 3);
+            assert!(stays.iter().all(|m| m.args[1] == m.args[2]));
+            assert_eq!(model.methods.len(), 5);
+            assert_eq!(model.tasks.len(), 3);
+            assert_eq!(model.initial_tasks, vec![GroundInstance::new("deliver", &["t1", "b"])]);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn unrefinable_grounding_test() {
+    let domain = get_domain();
+    // without roads, only staying is possible
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects t1 - vehicle)
+            (:init (at t1 depot))
+        )",
+    )
+    .into_bytes();
+    let d_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let p_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    let d_ast = d_parser.parse().unwrap();
+    let p_ast = p_parser.parse().unwrap();
+    match (d_ast, p_ast) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let d_analyzer = DomainSemanticAnalyzer::new(&d);
+            let symbol_table = verify_domain(&d_analyzer).unwrap();
+            let model = Grounder::new(&d, &p, &symbol_table).ground();
+            assert_eq!(model.actions, vec![GroundInstance::new("noop", &["t1", "depot"])]);
+            assert_eq!(model.methods.len(), 1);
+            assert_eq!(model.methods[0].to_string(), "m_stay[t1 depot depot]: (deliver t1 depot) -> (noop t1 depot)");
+            assert!(model.initial_tasks.is_empty());
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn durative_grounding_test() {
+    let domain = String::from(
+        "(define (domain transport)
+            (:requirements :typing :durative-actions)
+            (:types location - object)
+            (:constants depot - location)
+            (:predicates (road ?a ?b - location) (clean ?l - location))
+            (:durative-action wash
+                :parameters (?l - location)
+                :duration (= ?duration 2)
+                :condition (and (at start (not (clean ?l))) (over all (road ?l depot)))
+                :effect (at end (clean ?l))
+            )
+        )",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location)
+            (:init (road a depot))
+        )",
+    )
+    .into_bytes();
+    let d_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let p_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    let d_ast = d_parser.parse().unwrap();
+    let p_ast = p_parser.parse().unwrap();
+    match (d_ast, p_ast) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let d_analyzer = DomainSemanticAnalyzer::new(&d);
+            let symbol_table = verify_domain(&d_analyzer).unwrap();
+            let model = Grounder::new(&d, &p, &symbol_table).ground();
+            // the static "road" condition holds over the whole action
+            assert_eq!(model.actions, vec![GroundInstance::new("wash", &["a"])]);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn relaxed_reachability_test() {
+    let domain = String::from(
+        "(define (domain transport)
+            (:requirements :typing :hierarchy)
+            (:types location vehicle - object)
+            (:constants depot - location)
+            (:predicates (road ?a ?b - location) (at ?v - vehicle ?l - location) (loaded ?v - vehicle))
+            (:task deliver :parameters (?v - vehicle ?l - location))
+            (:task unload :parameters (?v - vehicle))
+            (:method m_drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :task (deliver ?v ?b)
+                :ordered-subtasks (and (drive ?v ?a ?b) (unload ?v))
+            )
+            (:method m_unload
+                :parameters (?v - vehicle)
+                :task (unload ?v)
+                :ordered-subtasks (and (load ?v) (drop ?v))
+            )
+            (:action drive
+                :parameters (?v - vehicle ?a ?b - location)
+                :precondition (and (at ?v ?a) (road ?a ?b))
+                :effect (and (not (at ?v ?a)) (at ?v ?b))
+            )
+            (:action load
+                :parameters (?v - vehicle)
+                :precondition (at ?v depot)
+                :effect (loaded ?v)
+            )
+            (:action drop
+                :parameters (?v - vehicle)
+                :precondition (loaded ?v)
+                :effect (not (loaded ?v))
+            )
+        )",
+    )
+    .into_bytes();
+    let reachability_warnings = |problem: &str| -> Vec<String> {
+        let problem = String::from(problem).into_bytes();
+        let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+        assert!(diagnostics.errors.is_empty());
+        diagnostics
+            .warnings
+            .iter()
+            .filter(|w| {
+                matches!(
+                    w,
+                    WarningType::NeverApplicableAction(_)
+                        | WarningType::UnreachableMethod(_)
+                        | WarningType::UnrefinableTask(_)
+                )
+            })
+            .map(|w| w.to_string() + &w.position().unwrap().to_string())
+            .collect()
+    };
+    // the truck reaches the depot, where it can be loaded
+    let warnings = reachability_warnings(
+        "(define (problem p) (:domain transport)
+            (:objects a - location t1 - vehicle)
+            (:init (at t1 a) (road a depot))
+        )",
+    );
+    assert!(warnings.is_empty());
+    // the truck can drive, but never to the depot, so it is never loaded
+    let warnings = reachability_warnings(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location t1 - vehicle)
+            (:init (at t1 a) (road a b) (road depot a))
+        )",
+    );
+    assert_eq!(warnings, vec![
+        "action load is never applicable, even if effects never delete facts23:22",
+        "action drop is never applicable, even if effects never delete facts28:22",
+        "method m_unload can never be applied, since its subtask load is never applicable13:22",
+        "compound task deliver can not be refined into applicable actions in this problem6:20",
+        "compound task unload can not be refined into applicable actions in this problem7:20",
+    ]);    // denied warnings are errors, so the reachability is not analyzed
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location t1 - vehicle)
+            (:init (at t1 a) (road a b) (road depot a))
+        )",
+    )
+    .into_bytes();
+    let mut diagnostics = Diagnostics::new(None);
+    diagnostics.set_level(find_warning("ImmutablePredicate").unwrap(), Level::Deny);
+    crate::HDDLAnalyzer::analyze(&domain, Some(&problem), &mut diagnostics);
+    assert!(diagnostics.has_errors());
+    assert!(!diagnostics.warnings.iter().any(|w| matches!(w, WarningType::NeverApplicableAction(_))));
+}
\ No newline at end of file
@@ -5,8 +5,48 @@ mod type_checking_tests;
 mod tdg_tests;
 mod problem_test;
 mod warning_tests;
+#[cfg(test)]
+mod diagnostics_tests;
+#[cfg(test)]
+mod grounding_tests;
 
 use super::*;
 use crate::syntactic_analyzer::*;
 use crate::lexical_analyzer::*;
-use analyzers::*;
\ No newline at end of file
+use analyzers::*;
+
+// the symbol table of the domain, or its first error
+#[cfg(test)]
+fn verify_domain<'a>(analyzer: &'a DomainSemanticAnalyzer<'a>) -> Result<SymbolTable<'a>, SemanticErrorType> {
+    let mut diagnostics = Diagnostics::new(None);
+    let symbol_table = analyzer.analyze(&mut diagnostics);
+    match diagnostics.errors.into_iter().next() {
+        Some(ParsingError::Semantic(error)) => Err(error),
+        Some(error) => panic!("{:?}", error),
+        None => Ok(symbol_table),
+    }
+}
+
+// the warnings of the domain, or its first error
+#[cfg(test)]
+fn domain_warnings<'a>(analyzer: &'a DomainSemanticAnalyzer<'a>) -> Result<Vec<WarningType>, SemanticErrorType> {
+    let mut diagnostics = Diagnostics::new(None);
+    analyzer.analyze(&mut diagnostics);
+    match diagnostics.errors.into_iter().next() {
+        Some(ParsingError::Semantic(error)) => Err(error),
+        Some(error) => panic!("{:?}", error),
+        None => Ok(diagnostics.warnings),
+    }
+}
+
+// the warnings of the problem, or its first error
+#[cfg(test)]
+fn verify_problem(analyzer: &ProblemSemanticAnalyzer) -> Result<Vec<WarningType>, SemanticErrorType> {
+    let mut diagnostics = Diagnostics::new(None);
+    analyzer.analyze(&mut diagnostics);
+    match diagnostics.errors.into_iter().next() {
+        Some(ParsingError::Semantic(error)) => Err(error),
+        Some(error) => panic!("{:?}", error),
+        None => Ok(diagnostics.warnings),
+    }
+}
\ No newline at end of file
@@ -139,7 +139,7 @@ fn get_domain() -> Vec<u8> {
 pub fn p_undeclared_type_test() {
     let program = get_domain();
     let problem = String::from("(define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -163,9 +163,9 @@ pub fn p_undeclared_type_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -191,7 +191,7 @@ pub fn p_undeclared_type_test() {
 pub fn p_undefined_object_test() {
     let program = get_domain();
     let problem = String::from("(define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -truck
@@ -214,9 +214,9 @@ pub fn p_undefined_object_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -243,7 +243,7 @@ pub fn p_duplicate_object_definition_test() {
     let program = get_domain();
     let problem = String::from("
         (define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -267,9 +267,9 @@ pub fn p_duplicate_object_definition_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -297,7 +297,7 @@ pub fn p_cyclic_init_tn_ordering_test() {
     let program = get_domain();
     let problem = String::from(
         "(define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -327,9 +327,9 @@ pub fn p_cyclic_init_tn_ordering_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -355,7 +355,7 @@ pub fn p_inconsistent_goal_predicate_test() {
     let program = get_domain();
     let problem = String::from("
         (define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -382,9 +382,9 @@ pub fn p_inconsistent_goal_predicate_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -412,7 +412,7 @@ pub fn p_inconsistent_init_predicate_test() {
     let program = get_domain();
     let problem = String::from("
         (define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -437,9 +437,9 @@ pub fn p_inconsistent_init_predicate_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -467,7 +467,7 @@ pub fn p_inconsistent_subtask_test() {
     let program = get_domain();
     let problem = String::from("
         (define (problem p1)
-            (:domain d)
+            (:domain Depot)
             (:objects
                 x1 x2 - place
                 truck1 truck2 -locatable
@@ -491,9 +491,9 @@ pub fn p_inconsistent_subtask_test() {
             match p_ast {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
-                    let domain_symbols = d_analyzer.verify_domain().unwrap();
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
                     let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
-                    match p_analyzer.verify_problem() {
+                    match verify_problem(&p_analyzer) {
                         Ok(_) => {
                             panic!("error not found")
                         }
@@ -514,4 +514,90 @@ pub fn p_inconsistent_subtask_test() {
         }
         AbstractSyntaxTree::Problem(_) => panic!()
     }
+}
+
+#[test]
+pub fn p_quantified_goal_test() {
+    let program = get_domain();
+    let problem = String::from("(define (problem p1)
+            (:domain Depot)
+            (:objects
+                x1 x2 - place
+                truck1 truck2 - truck
+            )
+            (:goal (and
+                (forall (?t - truck) (exists (?p - place) (at ?t ?p)))
+                (exists (?t - truck) (forall (?t - hoist) (at ?t x1)))
+                (forall (?c - crate) (or (at ?c x1) (= ?c x3)))
+            ))
+    ").into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let d_ast = parser.parse().unwrap();
+    match d_ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let p_lexer = LexicalAnalyzer::new(&problem);
+            let p_parser = Parser::new(p_lexer);
+            let p_ast = p_parser.parse().unwrap();
+            match p_ast {
+                AbstractSyntaxTree::Problem(p) => {
+                    let d_analyzer = DomainSemanticAnalyzer::new(&d);
+                    let domain_symbols = verify_domain(&d_analyzer).unwrap();
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let mut diagnostics = Diagnostics::new(None);
+                    p_analyzer.analyze(&mut diagnostics);
+                    match &diagnostics.errors[..] {
+                        [ParsingError::Semantic(SemanticErrorType::UndefinedObject(undefined))] => {
+                            assert_eq!(undefined.symbol, "x3");
+                            assert_eq!(undefined.position.line, 10);
+                        }
+                        errors => panic!("{:?}", errors)
+                    }
+                    // the inner ?t hides the outer one
+                    match &diagnostics.warnings[..] {
+                        [WarningType::ShadowedVariable(info)] => {
+                            assert_eq!(info.symbol, "t");
+                            assert_eq!(info.position.line, 9);
+                        }
+                        warnings => panic!("{:?}", warnings)
+                    }
+                }
+                _ => panic!()
+            }
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
+
+#[test]
+pub fn p_inconsistent_domain_name_test() {
+    let program = get_domain();
+    let problem = String::from("(define (problem p1)
+            (:domain depot-2)
+            (:objects x1 - place)
+    ").into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let d_ast = parser.parse().unwrap();
+    let p_lexer = LexicalAnalyzer::new(&problem);
+    let p_parser = Parser::new(p_lexer);
+    let p_ast = p_parser.parse().unwrap();
+    match (d_ast, p_ast) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            assert_eq!(d.name, "Depot");
+            assert_eq!(p.name, "p1");
+            let d_analyzer = DomainSemanticAnalyzer::new(&d);
+            let domain_symbols = verify_domain(&d_analyzer).unwrap();
+            let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+            match verify_problem(&p_analyzer) {
+                Err(SemanticErrorType::InconsistentDomainName(error)) => {
+                    assert_eq!(error.expected, "Depot");
+                    assert_eq!(error.found, "depot-2");
+                    assert_eq!((error.position.line, error.position.column), (2, 22));
+                }
+                result => panic!("{:?}", result)
+            }
+        }
+        _ => panic!()
+    }
 }
\ No newline at end of file
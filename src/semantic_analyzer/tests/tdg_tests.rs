@@ -609,4 +609,67 @@ pub fn tdg_nullables_test () {
         }
         AbstractSyntaxTree::Problem(_) => panic!()
     }
+}
+
+#[test]
+pub fn tdg_restricted_recursion_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:action p_1 :parameters ())
+            (:task loop :parameters ())
+            (:task once :parameters ())
+            (:method m_loop
+                :parameters ()
+                :task (loop)
+                :ordered-subtasks (and (p_1) (loop))
+            )
+            (:method m_once
+                :parameters ()
+                :task (once)
+                :ordered-subtasks (and (p_1))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let tdg = TDG::new(&d);
+            let nullables = tdg.compute_nullables();
+            assert!(matches!(tdg.get_recursion_type(&nullables), RecursionType::Recursive(_)));
+            // "loop" is not reachable from "once"
+            assert_eq!(tdg.get_recursion_type_from(&nullables, &["once"]), RecursionType::NonRecursive);
+            assert!(matches!(
+                tdg.get_recursion_type_from(&nullables, &["once", "loop"]),
+                RecursionType::Recursive(_)
+            ));
+            // undefined tasks are ignored
+            assert_eq!(tdg.get_recursion_type_from(&nullables, &["undefined"]), RecursionType::NonRecursive);
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
+
+#[test]
+pub fn tdg_without_compound_tasks_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:action p_1 :parameters ())
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let tdg = TDG::new(&d);
+            let nullables = tdg.compute_nullables();
+            assert!(nullables.is_empty());
+            assert_eq!(tdg.get_recursion_type(&nullables), RecursionType::NonRecursive);
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
 }
\ No newline at end of file
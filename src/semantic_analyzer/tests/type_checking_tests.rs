@@ -25,7 +25,7 @@ pub fn basic_type_checking_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -74,7 +74,7 @@ pub fn effect_type_checking_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {        }
                 Err(error) => {
                     panic!("{:?}", error)
@@ -113,7 +113,7 @@ pub fn inconsistent_predicate_arity_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Err(SemanticErrorType::InconsistentPredicateArity(x)) => {
                     assert_eq!(x.symbol, "at");
                     assert_eq!(x.expected_arity, 1);
@@ -162,7 +162,7 @@ pub fn inconsistent_subtask_arity_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Err(SemanticErrorType::InconsistentTaskArity(x)) => {
                     assert_eq!(x.symbol, "test1");
                     assert_eq!(x.expected_arity, 2);
@@ -211,7 +211,7 @@ pub fn method_prec_type_checking_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {        }
                 Err(error) => {
                     panic!("{:?}", error)
@@ -263,7 +263,7 @@ pub fn method_subtask_checking_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("error are not caught")
                 }
@@ -284,4 +284,326 @@ pub fn method_subtask_checking_test () {
         }
         _ => panic!()
     }
+}
+
+#[test]
+pub fn quantified_effect_checking_test () {
+    let program = String::from(
+        "(define (domain logistics)
+            (:types package truck location - object)
+            (:predicates
+                (at ?p - package ?l - location)
+                (in ?p - package ?t - truck)
+            )
+            (:action unload_all
+            :parameters(?t - truck ?l - location)
+            :effect (forall (?p - package)
+                (when (in ?p ?t) (and (not (in ?p ?t)) (at ?p ?l))))
+            )
+            (:action misplace
+            :parameters(?t - truck ?l - location)
+            :effect (forall (?p - truck)
+                (when (in ?t ?t) (at ?p ?l)))
+            )
+            (:action escape
+            :parameters(?t - truck ?l - location)
+            :effect (and (forall (?p - package) (at ?p ?l)) (in ?p ?t))
+            )
+            (:action teleport
+            :parameters(?l - location)
+            :effect (forall (?p - package) (when (on ?p) (at ?p ?l)))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 4);
+            // neither ?t nor the quantified ?p is a package
+            for (error, var_name) in diagnostics.errors[..2].iter().zip(["t", "p"]) {
+                match error {
+                    ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(t_err)) => {
+                        assert_eq!(t_err.var_name, var_name);
+                        assert_eq!(t_err.expected.as_deref(), Some("package"));
+                        assert_eq!(t_err.position.line, 15);
+                    }
+                    error => panic!("{:?}", error)
+                }
+            }
+            // ?p is not visible outside of the quantified formula
+            match &diagnostics.errors[2] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedParameter(error)) => {
+                    assert_eq!(error.symbol, "p");
+                    assert_eq!(error.position.line, 19);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[3] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(error)) => {
+                    assert_eq!(error.symbol, "on");
+                    assert_eq!(error.position.line, 23);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn scoped_precondition_checking_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:types location truck - object)
+            (:constants depot - location)
+            (:predicates
+                (at ?t - truck ?l - location)
+                (road ?a ?b - location)
+            )
+            (:action drive
+            :parameters(?t - truck ?l - location)
+            :precondition (and
+                (exists (?l2 - location) (and (road ?l ?l2) (not (= ?l2 depot))))
+                (exists (?l2 - truck) (road ?l ?l2))
+            )
+            )
+            (:action park
+            :parameters(?t - truck ?l - location)
+            :precondition (exists (?l - location) (and (at ?t ?l) (road ?l ?y)))
+            )
+            (:action wait
+            :parameters(?t - truck ?l - location)
+            :precondition (and (at ?t ?l) (not (= ?t ?z)))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 3);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(t_err)) => {
+                    assert_eq!(t_err.var_name, "l2");
+                    assert_eq!(t_err.found.as_deref(), Some("truck"));
+                    assert_eq!(t_err.position.line, 12);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedParameter(error)) => {
+                    assert_eq!(error.symbol, "y");
+                    assert_eq!(error.position.line, 17);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[2] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedParameter(error)) => {
+                    assert_eq!(error.symbol, "z");
+                    assert_eq!((error.position.line, error.position.column), (21, 54));
+                }
+                error => panic!("{:?}", error)
+            }
+            let shadowed: Vec<&WarningType> = diagnostics
+                .warnings
+                .iter()
+                .filter(|w| matches!(w, WarningType::ShadowedVariable(_)))
+                .collect();
+            match shadowed[..] {
+                [WarningType::ShadowedVariable(info)] => {
+                    assert_eq!(info.symbol, "l");
+                    assert_eq!(info.position.line, 17);
+                }
+                _ => panic!("{:?}", shadowed)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn either_type_checking_test () {
+    let program = String::from(
+        "(define (domain transport)
+            (:types truck plane - vehicle location cargo)
+            (:predicates
+                (at ?v - (either truck plane) ?l - location)
+                (parked ?t - truck)
+            )
+            (:action fly
+            :parameters(?v - (either truck plane) ?l - location)
+            :precondition (and (at ?v ?l) (parked ?v))
+            )
+            (:action park
+            :parameters(?t - truck ?l - location)
+            :precondition (at ?t ?l)
+            )
+            (:action load
+            :parameters(?c - cargo ?l - location)
+            :precondition (at ?c ?l)
+            )
+            (:action sail
+            :parameters(?s - (either truck ship))
+            :precondition (parked ?s)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 2);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentPredicateArgType(t_err)) => {
+                    assert_eq!(t_err.var_name, "c");
+                    assert_eq!(t_err.expected.as_deref(), Some("(either truck plane)"));
+                    assert_eq!(t_err.found.as_deref(), Some("cargo"));
+                    assert_eq!(t_err.position.line, 17);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedType(error)) => {
+                    assert_eq!(error.symbol, "ship");
+                    assert_eq!(error.position.line, 20);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+
+#[test]
+pub fn function_type_checking_test () {
+    let program = String::from(
+        "(define (domain transport)
+            (:types truck location)
+            (:predicates (at ?t - truck ?l - location))
+            (:functions (road-length ?a ?b - location) (total-cost) (total-cost))
+            (:action drive
+            :parameters(?t - truck ?a ?b - location)
+            :precondition (and (at ?t ?a) (> (fuel ?t) 0))
+            :effect (increase (total-cost) (road-length ?a ?b))
+            )
+            (:action refuel
+            :parameters(?t - truck ?a - location)
+            :effect (increase (total-cost) (road-length ?a))
+            )
+            (:action teleport
+            :parameters(?t - truck ?a - location)
+            :effect (assign (total-cost) (road-length ?t ?a))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 4);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::DuplicateFunctionDeclaration(error)) => {
+                    assert_eq!(error.symbol, "total-cost");
+                    assert_eq!(error.second_pos.line, 4);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedFunction(error)) => {
+                    assert_eq!(error.symbol, "fuel");
+                    assert_eq!(error.position.line, 7);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[2] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentFunctionArity(error)) => {
+                    assert_eq!(error.symbol, "road-length");
+                    assert_eq!(error.expected_arity, 2);
+                    assert_eq!(error.found_arity, 1);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[3] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentFunctionArgType(error)) => {
+                    assert_eq!(error.var_name, "t");
+                    assert_eq!(error.expected.as_deref(), Some("location"));
+                    assert_eq!(error.position.line, 16);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn function_value_checking_test () {
+    let domain = String::from(
+        "(define (domain transport)
+            (:types location)
+            (:functions (road-length ?a ?b - location) (total-cost))
+        ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects a b - location)
+            (:init (= (road-length a b) 7) (= (road-length a) 3))
+            (:metric minimize (distance))
+        ) ",
+    )
+    .into_bytes();
+    let d_lexer = LexicalAnalyzer::new(&domain);
+    let d_parser = Parser::new(d_lexer);
+    let d_ast = d_parser.parse().unwrap();
+    let p_lexer = LexicalAnalyzer::new(&problem);
+    let p_parser = Parser::new(p_lexer);
+    let p_ast = p_parser.parse().unwrap();
+    match (d_ast, p_ast) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let d_analyzer = DomainSemanticAnalyzer::new(&d);
+            let domain_symbols = verify_domain(&d_analyzer).unwrap();
+            let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+            let mut diagnostics = Diagnostics::new(None);
+            p_analyzer.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 2);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::InconsistentFunctionArity(error)) => {
+                    assert_eq!(error.symbol, "road-length");
+                    assert_eq!(error.position.line, 3);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedFunction(error)) => {
+                    assert_eq!(error.symbol, "distance");
+                    assert_eq!(error.position.line, 4);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
 }
\ No newline at end of file
@@ -23,7 +23,7 @@ pub fn undefined_predicate_action_precondition_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -67,7 +67,7 @@ pub fn inconsistent_predicate_action_effect_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -119,22 +119,20 @@ pub fn undefined_predicate_method_precondition_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
-                Ok(_) => {
-                    panic!("errors are not caught")
-                }
-                Err(error) => {
-                    match error {
-                        SemanticErrorType::UndefinedPredicate(x) => {
-                            assert_eq!(x.symbol, "pred_5");
-                            assert_eq!(x.position.line, 10);
-                        }
-                        _ => {
-                            panic!("caught wrong error")
-                        }
-                    }
-                }
-            }
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            // the undefined predicate is reported along with the other errors of the formula
+            let undefined: Vec<&UndefinedSymbolError> = diagnostics
+                .errors
+                .iter()
+                .filter_map(|error| match error {
+                    ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(x)) => Some(x),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(undefined.len(), 1);
+            assert_eq!(undefined[0].symbol, "pred_5");
+            assert_eq!(undefined[0].position.line, 10);
         }
         _ => panic!()
     }
@@ -172,7 +170,7 @@ pub fn undefined_method_parameters_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -228,7 +226,7 @@ pub fn undefined_subtask_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -284,7 +282,7 @@ pub fn inconsistent_subtask_arity_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -330,7 +328,7 @@ pub fn undefined_type_compound_task_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -373,7 +371,7 @@ pub fn undefined_type_predicate_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -429,22 +427,20 @@ pub fn undefined_predicate_forall_quantification_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
-                Ok(_) => {
-                    panic!("errors are not caught")
-                }
-                Err(error) => {
-                    match error {
-                        SemanticErrorType::UndefinedPredicate(x) => {
-                            assert_eq!(x.symbol, "wro");
-                            assert_eq!(x.position.line, 16)
-                        }
-                        error => {
-                            panic!("{:?}", error)
-                        }
-                    }
-                }
-            }
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            // the undefined predicate is reported along with the other errors of the formula
+            let undefined: Vec<&UndefinedSymbolError> = diagnostics
+                .errors
+                .iter()
+                .filter_map(|error| match error {
+                    ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(x)) => Some(x),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(undefined.len(), 1);
+            assert_eq!(undefined[0].symbol, "wro");
+            assert_eq!(undefined[0].position.line, 16);
         }
         _ => panic!()
     }
@@ -481,7 +477,7 @@ pub fn undefined_method_task_test() {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_parser = DomainSemanticAnalyzer::new(&d);
-            match semantic_parser.verify_domain() {
+            match verify_domain(&semantic_parser) {
                 Ok(_) => {
                     panic!("errors are not caught")
                 }
@@ -501,4 +497,94 @@ pub fn undefined_method_task_test() {
         _ => panic!()
     }
     
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn undefined_timed_predicate_test () {
+    let program = String::from(
+        "(define (domain transport)
+            (:predicates (at ?v ?l))
+            (:durative-action drive
+            :parameters(?v ?a ?b)
+            :duration (<= ?duration (road-length ?a ?b))
+            :condition (and (at start (at ?v ?a)) (over all (road ?a ?b)))
+            :effect (at end (at ?v ?b))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            assert_eq!(diagnostics.errors.len(), 2);
+            match &diagnostics.errors[0] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(error)) => {
+                    assert_eq!(error.symbol, "road");
+                    assert_eq!(error.position.line, 6);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &diagnostics.errors[1] {
+                ParsingError::Semantic(SemanticErrorType::UndefinedFunction(error)) => {
+                    assert_eq!(error.symbol, "road-length");
+                    assert_eq!(error.position.line, 5);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn undefined_type_parameters_test () {
+    let program = String::from(
+        "(define (domain bal) (:requirements :typing :hierarchy)
+            (:types t1)
+            (:predicates (at ?a - t1))
+            (:task c_1 :parameters (?a - t1))
+            (:action a_1
+            :parameters(?a - t2)
+            :precondition (at ?a)
+            :effect (not (at ?a))
+            )
+            (:action a_2
+            :parameters(?a - t3)
+            )
+            (:method m_1
+            :parameters(?a - t1 ?b - t4)
+            :task (c_1 ?a)
+            :ordered-subtasks (and (a_1 ?a))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_parser.analyze(&mut diagnostics);
+            // each parameter type is checked once, whether or not there are formulas
+            let undefined: Vec<(&str, u32)> = diagnostics
+                .errors
+                .iter()
+                .filter_map(|error| match error {
+                    ParsingError::Semantic(SemanticErrorType::UndefinedType(x)) => {
+                        Some((x.symbol.as_str(), x.position.line))
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(undefined, vec![("t2", 6), ("t3", 11), ("t4", 14)]);
+        }
+        _ => panic!()
+    }
+}
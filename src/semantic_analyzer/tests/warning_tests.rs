@@ -3,7 +3,7 @@ use super:: *;
 #[test]
 pub fn primitive_refinement_test () {
     let program = String::from(
-        "(define (domain bal)
+        "(define (domain bal) (:requirements :hierarchy)
             (:predicates 
                 (at ?l)
             )
@@ -40,10 +40,11 @@ pub fn primitive_refinement_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
-            match semantic_analyzer.verify_domain() {
-                Ok(sym_table) => {
-                    assert_eq!(sym_table.warnings.len(), 1);
-                    match &sym_table.warnings[0] {
+            match domain_warnings(&semantic_analyzer) {
+                Ok(warnings) => {
+                    // "at" is also static
+                    assert_eq!(warnings.len(), 2);
+                    match &warnings[0] {
                         WarningType::NoPrimitiveRefinement(info) => {
                             assert_eq!(info.symbol, "abs_3");
                             assert_eq!(info.position.line, 15);
@@ -61,7 +62,7 @@ pub fn primitive_refinement_test () {
 #[test]
 pub fn unsat_action_prec_test () {
     let program = String::from(
-        "(define (domain bal)
+        "(define (domain bal) (:requirements :negative-preconditions :hierarchy)
             (:predicates 
                 (at ?l)
             )
@@ -100,10 +101,11 @@ pub fn unsat_action_prec_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
-            match semantic_analyzer.verify_domain() {
-                Ok(sym_table) => {
-                    assert_eq!(sym_table.warnings.len(), 1);
-                    match &sym_table.warnings[0] {
+            match domain_warnings(&semantic_analyzer) {
+                Ok(warnings) => {
+                    // "at" is also static
+                    assert_eq!(warnings.len(), 2);
+                    match &warnings[0] {
                         WarningType::UnsatisfiableActionPrecondition(info) => {
                             assert_eq!(info.symbol, "p_2");
                             assert_eq!(info.position.line, 9);
@@ -122,7 +124,7 @@ pub fn unsat_action_prec_test () {
 #[test]
 pub fn unsat_method_prec_test () {
     let program = String::from(
-        "(define (domain bal)
+        "(define (domain bal) (:requirements :negative-preconditions :hierarchy :method-preconditions)
             (:predicates 
                 (at ?l)
             )
@@ -162,20 +164,446 @@ pub fn unsat_method_prec_test () {
     match ast {
         AbstractSyntaxTree::Domain(d) => {
             let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
-            match semantic_analyzer.verify_domain() {
-                Ok(sym_table) => {
-                    assert_eq!(sym_table.warnings.len(), 1);
-                    match &sym_table.warnings[0] {
+            match domain_warnings(&semantic_analyzer) {
+                Ok(warnings) => {
+                    assert_eq!(warnings.len(), 3);
+                    match &warnings[0] {
                         WarningType::UnsatisfiableMethodPrecondition(info) => {
                             assert_eq!(info.symbol, "m_1");
                             assert_eq!(info.position.line, 16);
                         }
                         _ => panic!()
                     }
+                    // the parameter of p_2
+                    match &warnings[1] {
+                        WarningType::UnusedParameter(info) => {
+                            assert_eq!(info.symbol, "l1");
+                            assert_eq!(info.position.line, 10);
+                        }
+                        _ => panic!()
+                    }
                 }
                 token => panic!("{:?}", token)
             }
         }
         _ => panic!()
     }
+}
+
+#[test]
+pub fn unused_elements_test () {
+    let program = String::from(
+        "(define (domain bal) (:requirements :typing :hierarchy :equality :conditional-effects)
+            (:types t1 t2 - t3 t3 t4 t5 t6)
+            (:constants c - t5)
+            (:predicates 
+                (at ?l - t1)
+                (hold ?l - t2)
+                (free)
+                (goal_reached)
+            )
+            (:action p_1
+            :parameters(?l1 - t1 ?l2 ?l3)
+            :precondition (at ?l1)
+            :effect (forall (?x - t4) (not (= ?x ?l3)))
+            )
+            (:task abs_1 :parameters(?a))
+            (:method m_1
+                :parameters (?p1 ?p2 ?p3 ?p4) 
+                :task (abs_1 ?p1)
+                :ordered-subtasks (and
+                    (t1 (p_1 ?p2 ?p2 ?p2))
+                )
+                :constraints (not (= ?p3 ?p2))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_analyzer.analyze(&mut diagnostics);
+            let unused: Vec<String> = diagnostics.warnings.iter().map(|w| w.to_string()).collect();
+            assert_eq!(unused, vec![
+                "parameter l2 is declared, but never used",
+                "parameter p4 is declared, but never used",
+                "type t6 is declared, but never used",
+                "predicate hold is declared, but never used",
+                "predicate free is declared, but never used",
+                "predicate goal_reached is declared, but never used",
+                "predicate at does not appear in the effect of any action",
+            ]);
+            match &diagnostics.warnings[0] {
+                WarningType::UnusedParameter(info) => {
+                    assert_eq!(info.position.line, 11);
+                }
+                _ => panic!()
+            }
+            // predicates in the problem count as used
+            let problem = String::from(
+                "(define (problem p) (:domain bal)
+                    (:objects o - t1)
+                    (:init (free))
+                    (:goal (goal_reached))
+                )",
+            )
+            .into_bytes();
+            let lexer = LexicalAnalyzer::new(&problem);
+            let parser = Parser::new(lexer);
+            match parser.parse().unwrap() {
+                AbstractSyntaxTree::Problem(p) => {
+                    let semantic_analyzer = DomainSemanticAnalyzer::new(&d).with_problem(&p);
+                    let mut diagnostics = Diagnostics::new(None);
+                    semantic_analyzer.analyze(&mut diagnostics);
+                    let unused_predicates = diagnostics
+                        .warnings
+                        .iter()
+                        .filter(|w| matches!(w, WarningType::UnusedPredicate(_)))
+                        .count();
+                    assert_eq!(unused_predicates, 1);
+                }
+                _ => panic!()
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn static_precondition_test () {
+    let domain = String::from(
+        "(define (domain bal) (:requirements :negative-preconditions)
+            (:constants c1 c2)
+            (:predicates (road ?a ?b) (at ?a) (blocked ?a) (sunny))
+            (:action move
+            :parameters(?a ?b)
+            :precondition (and (at ?a) (road ?a ?b) (not (blocked c1)) (not (sunny)))
+            :effect (and (not (at ?a)) (at ?b))
+            )
+            (:action fly
+            :parameters(?a)
+            :precondition (and (at ?a) (blocked c2) (not (blocked ?a)))
+            :effect (and (not (at ?a)) (at c1))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain bal)
+            (:init (at c1) (road c1 c2) (blocked c1) (sunny))
+        )",
+    )
+    .into_bytes();
+    assert_eq!(
+        crate::HDDLAnalyzer::get_static_predicates(&domain).unwrap(),
+        vec!["road", "blocked", "sunny"]
+    );
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+    let unsatisfiable: Vec<String> = diagnostics
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            WarningType::UnsatisfiableStaticPrecondition(info) => Some(w.to_string() + &info.position.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(unsatisfiable, vec![
+        "the precondition (not (blocked c1)) of move can never hold, since blocked is static and the initial state does not satisfy it6:59",
+        "the precondition (not (sunny)) of move can never hold, since sunny is static and the initial state does not satisfy it6:78",
+        "the precondition (blocked c2) of fly can never hold, since blocked is static and the initial state does not satisfy it11:41",
+    ]);
+    // without a problem, only the static predicates are reported
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, None, None);
+    assert_eq!(diagnostics.warnings.len(), 3);
+    assert!(diagnostics.warnings.iter().all(|w| matches!(w, WarningType::ImmutablePredicate(_))));
+}
+
+#[test]
+pub fn static_precondition_disjunction_test () {
+    let domain = String::from(
+        "(define (domain bal) (:requirements :negative-preconditions :disjunctive-preconditions)
+            (:predicates (at ?a) (blocked ?a) (sunny))
+            (:action move
+            :parameters(?a)
+            :precondition (and (or (blocked ?a) (at ?a)) (imply (sunny) (at ?a)) (not (or (at ?a) (sunny))))
+            :effect (not (at ?a))
+            )
+            (:action fly
+            :parameters(?a)
+            :precondition (and (at ?a) (not (imply (at ?a) (and (sunny) (at ?a)))) (blocked ?a))
+            :effect (not (at ?a))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain bal)
+            (:init (at c1) (sunny))
+        )",
+    )
+    .into_bytes();
+    let diagnostics = crate::HDDLAnalyzer::verify(&domain, Some(&problem), None);
+    let unsatisfiable: Vec<String> = diagnostics
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            WarningType::UnsatisfiableStaticPrecondition(_) => Some(w.to_string()),
+            _ => None,
+        })
+        .collect();
+    // branches of "or", "imply" and negated conjunctions are not required to hold
+    assert_eq!(unsatisfiable, vec![
+        "the precondition (not (sunny)) of move can never hold, since sunny is static and the initial state does not satisfy it",
+        "the precondition (blocked a) of fly can never hold, since blocked is static and the initial state does not satisfy it",
+    ]);
+}
+
+#[test]
+pub fn redundant_effect_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:predicates (at ?a) (free ?a) (road ?a ?b))
+            (:action move
+            :parameters(?a ?b)
+            :precondition (and (at ?a) (not (free ?b)) (road ?a ?b))
+            :effect (and
+                (at ?a)
+                (not (at ?a))
+                (at ?b)
+                (at ?b)
+                (not (free ?b))
+                (free ?a)
+            )
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let mut diagnostics = Diagnostics::new(None);
+            semantic_analyzer.analyze(&mut diagnostics);
+            let redundants: Vec<String> = diagnostics
+                .warnings
+                .iter()
+                .filter_map(|w| match w {
+                    WarningType::RedundantEffect(info) => Some(format!("{} {}", w, info.position.line)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(redundants, vec![
+                "effect (at a) of action move already holds due to its precondition 7",
+                "effect (not (at a)) of action move is both added and deleted 8",
+                "effect (at b) of action move is repeated 10",
+                "effect (not (free b)) of action move already holds due to its precondition 11",
+            ]);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn durative_action_conditions_test () {
+    let program = String::from(
+        "(define (domain transport)
+            (:requirements :durative-actions :action-costs)
+            (:predicates (at ?v ?l) (road ?a ?b))
+            (:functions (total-cost))
+            (:durative-action drive
+            :parameters(?v ?a ?b)
+            :duration (= ?duration 5)
+            :condition (and (at start (at ?v ?a)) (over all (road ?a ?b)) (at end (not (at ?v ?a))))
+            :effect (and
+                (at start (not (at ?v ?a)))
+                (at end (at ?v ?b))
+                (at end (increase (total-cost) ?duration))
+            )
+            )
+            (:durative-action stay
+            :parameters(?v ?a)
+            :duration ()
+            :condition (at start (and (at ?v ?a) (not (at ?v ?a))))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let all_warnings = domain_warnings(&semantic_analyzer).unwrap();
+            // conditions at different times do not contradict each other
+            let unsatisfiables: Vec<&WarningInfo> = all_warnings
+                .iter()
+                .filter_map(|warning| match warning {
+                    WarningType::UnsatisfiableActionPrecondition(info) => Some(info),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(unsatisfiables.len(), 1);
+            assert_eq!(unsatisfiables[0].symbol, "stay");
+            assert_eq!(unsatisfiables[0].position.line, 15);
+            // "at" is changed by the timed effects
+            assert!(!all_warnings.iter().any(|warning| matches!(
+                warning,
+                WarningType::ImmutablePredicate(info) if info.symbol == "at"
+            )));
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn requirements_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:requirements :typing :equality :numeric-fluents)
+            (:types t1)
+            (:predicates (at ?x - t1))
+            (:functions (total-cost))
+            (:action a_1
+            :parameters(?x - t1)
+            :precondition (not (at ?x))
+            :effect (and (at ?x) (increase (total-cost) 1))
+            )
+            (:action a_2
+            :parameters(?x - t1)
+            :precondition (not (at ?x))
+            :effect (at ?x)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let all_warnings = domain_warnings(&semantic_analyzer).unwrap();
+            let warnings: Vec<&WarningType> = all_warnings
+                .iter()
+                .filter(|warning| matches!(
+                    warning,
+                    WarningType::MissingRequirement(_) | WarningType::UnusedRequirement(_)
+                ))
+                .collect();
+            // :numeric-fluents covers the action costs
+            assert_eq!(warnings.len(), 2);
+            match warnings[0] {
+                WarningType::MissingRequirement(info) => {
+                    assert_eq!(info.requirement, ":negative-preconditions");
+                    assert_eq!(info.position.line, 8);
+                }
+                warning => panic!("{:?}", warning),
+            }
+            match warnings[1] {
+                WarningType::UnusedRequirement(info) => {
+                    assert_eq!(info.symbol, ":equality");
+                    assert_eq!(info.position.line, 2);
+                }
+                warning => panic!("{:?}", warning),
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn action_costs_requirements_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:requirements :action-costs)
+            (:predicates (at ?x) (road ?x ?y))
+            (:functions (total-cost) (road-length ?x ?y) (fuel ?x))
+            (:action move
+            :parameters(?x ?y)
+            :precondition (and (at ?x) (road ?x ?y))
+            :effect (and (at ?y) (increase (total-cost) (road-length ?x ?y)))
+            )
+            (:action refuel
+            :parameters(?x)
+            :precondition (at ?x)
+            :effect (increase (fuel ?x) 1)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let all_warnings = domain_warnings(&semantic_analyzer).unwrap();
+            let missing: Vec<&RequirementInfo> = all_warnings
+                .iter()
+                .filter_map(|warning| match warning {
+                    WarningType::MissingRequirement(info) => Some(info),
+                    _ => None,
+                })
+                .collect();
+            // "road-length" is a static cost function, but "fuel" is changed by an effect
+            assert_eq!(missing.len(), 1);
+            assert_eq!(missing[0].requirement, ":numeric-fluents");
+            assert_eq!(missing[0].position.line, 4);
+            assert_eq!(missing[0].position.to_string(), "4:59");
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn composite_requirements_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:requirements :adl :strips :hierarchy-free)
+            (:types t1)
+            (:predicates (at ?x - t1) (free ?x - t1))
+            (:action a_1
+            :parameters(?x - t1)
+            :precondition (or (at ?x) (exists (?y - t1) (free ?y)))
+            :effect (when (not (free ?x)) (at ?x))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let all_warnings = domain_warnings(&semantic_analyzer).unwrap();
+            let warnings: Vec<&WarningType> = all_warnings
+                .iter()
+                .filter(|warning| matches!(
+                    warning,
+                    WarningType::MissingRequirement(_)
+                        | WarningType::UnusedRequirement(_)
+                        | WarningType::UnknownRequirement(_)
+                ))
+                .collect();
+            // ":adl" covers every feature of the domain
+            assert_eq!(warnings.len(), 1);
+            match warnings[0] {
+                WarningType::UnknownRequirement(info) => {
+                    assert_eq!(info.symbol, ":hierarchy-free");
+                    assert_eq!(info.position.line, 2);
+                    assert_eq!(info.position.column, 41);
+                }
+                warning => panic!("{:?}", warning),
+            }
+        }
+        _ => panic!()
+    }
 }
\ No newline at end of file
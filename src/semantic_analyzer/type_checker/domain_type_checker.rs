@@ -28,90 +28,187 @@ impl<'a> DomainTypeChecker<'a> {
             .check_type_declarations(parameters)
     }
 
-    pub fn verify_type_hierarchy(&self) -> Result<(), SemanticErrorType> {
-        self.generic_type_checker.verify_type_hierarchy()
+    pub fn verify_type_hierarchy(&self, types: &Option<Vec<Symbol<'a>>>) -> Result<(), SemanticErrorType> {
+        self.generic_type_checker.verify_type_hierarchy(types)
     }
 
-    // TODO: Add support for "universal qunatification" parameters
+    // records an error for every ill-typed atom of "formula" (the types of "parameters"
+    // are checked with their declaration)
     pub fn check_formula(
         &self,
-        formula: &Vec<&Predicate<'a>>,
+        formula: &Formula<'a>,
         parameters: &Vec<Symbol<'a>>,
         declared_constants: &HashSet<&Symbol<'a>>,
         declared_predicates: &HashSet<&'a Predicate<'a>>,
-    ) -> Result<(), SemanticErrorType> {
-        // Assert all types are declared
-        if let Some(undeclared_type) = self
-            .generic_type_checker
-            .check_type_declarations(parameters)
-        {
-            return Err(undeclared_type);
-        }
-        // Store parameter types
-        let par_types: HashMap<&str, Option<&str>> =
-            HashMap::from_iter(parameters.iter().map(|par| (par.name, par.symbol_type)));
-        // Assert predicate typing correctness
-        for instantiated_predicate in formula {
-            match declared_predicates.get(instantiated_predicate) {
-                Some(predicate_definition) => {
-                    let mut instantiated_vars = vec![];
-                    for var in instantiated_predicate.variables.iter() {
-                        match par_types.get(var.name) {
-                            Some(par_type) => {
-                                instantiated_vars.push((var, par_type));
-                            }
-                            None => match declared_constants.get(var) {
-                                Some(constant) => {
-                                    instantiated_vars.push((var, &constant.symbol_type))
-                                }
-                                None => {
-                                    return Err(SemanticErrorType::UndefinedParameter(
-                                        UndefinedSymbolError {
-                                            symbol: var.name.to_string(),
-                                            position: var.name_pos,
-                                        },
-                                    ));
-                                }
-                            },
-                        }
-                    }
-                    let mut expected_list: Vec<&Option<&str>> = predicate_definition
-                        .variables
-                        .iter()
-                        .map(|x| &x.symbol_type)
-                        .collect();
-                    // Assert args have the same arity
-                    if &instantiated_vars.len() != &expected_list.len() {
-                        return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
-                            symbol: instantiated_predicate.name.to_string(),
-                            expected_arity: expected_list.len() as u32,
-                            found_arity: instantiated_vars.len() as u32,
-                            position: instantiated_predicate.name_pos,
+        declared_functions: &HashSet<&'a Predicate<'a>>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let mut scope = Scope::new(parameters);
+        self.check_scoped_formula(
+            formula,
+            &mut scope,
+            declared_constants,
+            declared_predicates,
+            declared_functions,
+            diagnostics,
+        )
+    }
+
+    fn check_scoped_formula<'b>(
+        &self,
+        formula: &'b Formula<'a>,
+        scope: &mut Scope<'a, 'b>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+        declared_functions: &HashSet<&'a Predicate<'a>>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        match formula {
+            Formula::Empty => {}
+            Formula::Equals(a, b) => {
+                // both sides must be variables in scope, or constants
+                for term in [a, b] {
+                    if scope.get(term.name).is_none() && !declared_constants.contains(term) {
+                        diagnostics.add_error(SemanticErrorType::UndefinedParameter(UndefinedSymbolError {
+                            symbol: term.name.to_string(),
+                            position: term.name_pos,
                         }));
                     }
-                    for ((var, f), e) in
-                        instantiated_vars.into_iter().zip(expected_list.into_iter())
-                    {
-                        if !self.generic_type_checker.is_var_type_consistent(*f, *e) {
-                            return Err(SemanticErrorType::InconsistentPredicateArgType(
-                                TypeError {
-                                    expected: e.map(|inner| inner.to_string()),
-                                    found: f.map(|inner| inner.to_string()),
-                                    var_name: var.name.to_string(),
-                                    position: var.name_pos,
-                                },
-                            ));
-                        }
+                }
+            }
+            Formula::Atom(predicate) => {
+                if let Err(error) = self.check_predicate(predicate, scope, declared_constants, declared_predicates) {
+                    diagnostics.add_error(error);
+                }
+            }
+            Formula::Not(f) | Formula::Timed(_, f) => {
+                self.check_scoped_formula(
+                    f,
+                    scope,
+                    declared_constants,
+                    declared_predicates,
+                    declared_functions,
+                    diagnostics,
+                )
+            }
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                for f in fs {
+                    self.check_scoped_formula(
+                        f,
+                        scope,
+                        declared_constants,
+                        declared_predicates,
+                        declared_functions,
+                        diagnostics,
+                    );
+                }
+            }
+            Formula::Imply(antecedents, consequents) => {
+                for f in antecedents.iter().chain(consequents.iter()) {
+                    self.check_scoped_formula(
+                        f,
+                        scope,
+                        declared_constants,
+                        declared_predicates,
+                        declared_functions,
+                        diagnostics,
+                    );
+                }
+            }
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+                if let Some(undeclared_type) = self.generic_type_checker.check_type_declarations(vars) {
+                    diagnostics.add_error(undeclared_type);
+                }
+                // quantified variables are only visible in the quantified formula
+                scope.push(vars);
+                self.check_scoped_formula(
+                    f,
+                    scope,
+                    declared_constants,
+                    declared_predicates,
+                    declared_functions,
+                    diagnostics,
+                );
+                scope.pop();
+            }
+            Formula::Compare(..) | Formula::Assign(..) => {
+                // function terms are checked like predicates, against the declared functions
+                for function in formula.get_functions() {
+                    if let Err(error) = self.check_predicate(function, scope, declared_constants, declared_functions) {
+                        diagnostics.add_error(as_function_error(error));
                     }
                 }
-                None => {
-                    return Err(SemanticErrorType::UndefinedPredicate(
-                        UndefinedSymbolError {
-                            symbol: instantiated_predicate.name.to_string(),
-                            position: instantiated_predicate.name_pos,
+            }
+        }
+    }
+
+    fn check_predicate(
+        &self,
+        instantiated_predicate: &Predicate<'a>,
+        scope: &Scope<'a, '_>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+    ) -> Result<(), SemanticErrorType> {
+        // Assert predicate typing correctness
+        match declared_predicates.get(instantiated_predicate) {
+            Some(predicate_definition) => {
+                let mut instantiated_vars = vec![];
+                for var in instantiated_predicate.variables.iter() {
+                    match scope.get(var.name) {
+                        Some(par) => {
+                            instantiated_vars.push((var, &par.symbol_type));
+                        }
+                        None => match declared_constants.get(var) {
+                            Some(constant) => {
+                                instantiated_vars.push((var, &constant.symbol_type))
+                            }
+                            None => {
+                                return Err(SemanticErrorType::UndefinedParameter(
+                                    UndefinedSymbolError {
+                                        symbol: var.name.to_string(),
+                                        position: var.name_pos,
+                                    },
+                                ));
+                            }
                         },
-                    ));
+                    }
                 }
+                let mut expected_list: Vec<&Option<SymbolType>> = predicate_definition
+                    .variables
+                    .iter()
+                    .map(|x| &x.symbol_type)
+                    .collect();
+                // Assert args have the same arity
+                if &instantiated_vars.len() != &expected_list.len() {
+                    return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                        symbol: instantiated_predicate.name.to_string(),
+                        expected_arity: expected_list.len() as u32,
+                        found_arity: instantiated_vars.len() as u32,
+                        position: instantiated_predicate.name_pos,
+                    }));
+                }
+                for ((var, f), e) in
+                    instantiated_vars.into_iter().zip(expected_list.into_iter())
+                {
+                    if !self.generic_type_checker.is_var_type_consistent(f.as_ref(), e.as_ref()) {
+                        return Err(SemanticErrorType::InconsistentPredicateArgType(
+                            TypeError {
+                                expected: e.as_ref().map(|inner| inner.to_string()),
+                                found: f.as_ref().map(|inner| inner.to_string()),
+                                var_name: var.name.to_string(),
+                                position: var.name_pos,
+                            },
+                        ));
+                    }
+                }
+            }
+            None => {
+                return Err(SemanticErrorType::UndefinedPredicate(
+                    UndefinedSymbolError {
+                        symbol: instantiated_predicate.name.to_string(),
+                        position: instantiated_predicate.name_pos,
+                    },
+                ));
             }
         }
         Ok(())
@@ -154,10 +251,10 @@ impl<'a> DomainTypeChecker<'a> {
         }
         match declared_actions.iter().find(|x| x.name == task.name) {
             Some(definition) => {
-                let expected_types: Vec<Option<&str>> = definition
+                let expected_types: Vec<Option<&SymbolType>> = definition
                     .parameters
                     .iter()
-                    .map(|x| x.symbol_type)
+                    .map(|x| x.symbol_type.as_ref())
                     .collect();
                 if task_terms.len() != expected_types.len() {
                     return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
@@ -172,11 +269,11 @@ impl<'a> DomainTypeChecker<'a> {
                 {
                     if !self
                         .generic_type_checker
-                        .is_var_type_consistent(parameter.symbol_type, *expected_type)
+                        .is_var_type_consistent(parameter.symbol_type.as_ref(), *expected_type)
                     {
                         return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
                             expected: expected_type.map(|inner| inner.to_string()),
-                            found: parameter.symbol_type.map(|inner| inner.to_string()),
+                            found: parameter.symbol_type.as_ref().map(|inner| inner.to_string()),
                             var_name: term.name.to_string(),
                             position: term.name_pos,
                         }));
@@ -186,10 +283,10 @@ impl<'a> DomainTypeChecker<'a> {
             }
             None => match declared_tasks.iter().find(|x| x.name == task.name) {
                 Some(definition) => {
-                    let expected: Vec<Option<&str>> = definition
+                    let expected: Vec<Option<&SymbolType>> = definition
                         .parameters
                         .iter()
-                        .map(|x| x.symbol_type)
+                        .map(|x| x.symbol_type.as_ref())
                         .collect();
                     if found_types.len() != expected.len() {
                         return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
@@ -204,11 +301,11 @@ impl<'a> DomainTypeChecker<'a> {
                     {
                         if !self
                             .generic_type_checker
-                            .is_var_type_consistent(parameter.symbol_type, *expected_type)
+                            .is_var_type_consistent(parameter.symbol_type.as_ref(), *expected_type)
                         {
                             return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
                                 expected: expected_type.map(|inner| inner.to_string()),
-                                found: parameter.symbol_type.map(|inner| inner.to_string()),
+                                found: parameter.symbol_type.as_ref().map(|inner| inner.to_string()),
                                 var_name: term.name.to_string(),
                                 position: term.name_pos,
                             }));
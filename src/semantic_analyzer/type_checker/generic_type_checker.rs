@@ -20,11 +20,13 @@ impl<'a> TypeChecker<'a> {
                     }
                     match &delcared_type.symbol_type {
                         None => {}
-                        Some(parent) => {
-                            if !type_graph.contains_node(parent) {
-                                type_graph.add_node(parent);
+                        Some(parents) => {
+                            for parent in parents.members() {
+                                if !type_graph.contains_node(parent) {
+                                    type_graph.add_node(parent);
+                                }
+                                type_graph.add_edge(delcared_type.name, parent, ());
                             }
-                            type_graph.add_edge(delcared_type.name, parent, ());
                         }
                     }
                 }
@@ -35,24 +37,41 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
-    pub fn verify_type_hierarchy(&self) -> Result<(), SemanticErrorType> {
-        match toposort(&self.type_hierarchy, None) {
-            Ok(_) => Ok(()),
-            Err(_) => {
-                return Err(SemanticErrorType::CyclicTypeDeclaration);
+    // replays the declarations of "types" in order, and reports the first one that
+    // closes a cycle in the hierarchy
+    pub fn verify_type_hierarchy(&self, types: &Option<Vec<Symbol<'a>>>) -> Result<(), SemanticErrorType> {
+        let mut type_graph: GraphMap<&str, (), Directed> = GraphMap::new();
+        for declared_type in types.iter().flatten() {
+            for parent in declared_type.symbol_type.iter().flat_map(|parents| parents.members()) {
+                let closes_cycle = *parent == declared_type.name
+                    || type_graph.contains_node(parent)
+                        && type_graph.contains_node(declared_type.name)
+                        && has_path_connecting(&type_graph, parent, declared_type.name, None);
+                if closes_cycle {
+                    return Err(SemanticErrorType::CyclicTypeDeclaration(declared_type.name_pos));
+                }
+                type_graph.add_edge(declared_type.name, parent, ());
             }
         }
+        Ok(())
     }
 
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
     ) -> Option<SemanticErrorType> {
-        for parameter in parameters.iter() {
-            if let Some(t) = parameter.symbol_type {
-                if !self.type_hierarchy.contains_node(t) {
+        parameters
+            .iter()
+            .find_map(|parameter| self.check_type_declaration(parameter))
+    }
+
+    pub fn check_type_declaration(&self, parameter: &Symbol<'a>) -> Option<SemanticErrorType> {
+        if let Some(t) = &parameter.symbol_type {
+            // every member of a union type must be declared
+            for member in t.members() {
+                if !self.type_hierarchy.contains_node(member) {
                     return Some(SemanticErrorType::UndefinedType(UndefinedSymbolError {
-                        symbol: parameter.symbol_type.unwrap().to_string(),
+                        symbol: member.to_string(),
                         position: parameter.type_pos.unwrap(),
                     }));
                 }
@@ -61,24 +80,19 @@ impl<'a> TypeChecker<'a> {
         None
     }
 
+    // union types are consistent if any of their members are
     pub fn is_var_type_consistent(
         &self,
-        found: Option<&'a str>,
-        expected: Option<&'a str>,
+        found: Option<&SymbolType<'a>>,
+        expected: Option<&SymbolType<'a>>,
     ) -> bool {
         match (found, expected) {
-            (Some(found_typing), Some(defined_typing)) => {
-                // type matches exactly
-                if found_typing == defined_typing {
-                    return true;
-                }
-                // search whether there is a path from current type to a super type
-                if !has_path_connecting(&self.type_hierarchy, found_typing, defined_typing, None) {
-                    return false;
-                } else {
-                    return true;
-                }
-            }
+            (Some(found_typing), Some(defined_typing)) => found_typing.members().iter().any(|f| {
+                defined_typing
+                    .members()
+                    .iter()
+                    .any(|d| self.is_subtype(f, d))
+            }),
             (None, None) => {
                 return true;
             }
@@ -88,4 +102,28 @@ impl<'a> TypeChecker<'a> {
             }
         }
     }
+
+    fn is_subtype(&self, found_typing: &'a str, defined_typing: &'a str) -> bool {
+        // type matches exactly
+        if found_typing == defined_typing {
+            return true;
+        }
+        // search whether there is a path from current type to a super type
+        self.type_hierarchy.contains_node(found_typing)
+            && has_path_connecting(&self.type_hierarchy, found_typing, defined_typing, None)
+    }
 }
+
+// errors in function terms are reported as function errors, since they are checked like predicates
+pub fn as_function_error(error: SemanticErrorType) -> SemanticErrorType {
+    match error {
+        SemanticErrorType::UndefinedPredicate(info) => SemanticErrorType::UndefinedFunction(info),
+        SemanticErrorType::InconsistentPredicateArity(info) => {
+            SemanticErrorType::InconsistentFunctionArity(info)
+        }
+        SemanticErrorType::InconsistentPredicateArgType(info) => {
+            SemanticErrorType::InconsistentFunctionArgType(info)
+        }
+        other => other,
+    }
+}
\ No newline at end of file
@@ -1,13 +1,16 @@
 mod domain_type_checker;
 mod problem_type_checker;
 mod generic_type_checker;
+mod scope;
 
 
-use petgraph::algo::{has_path_connecting, toposort};
+use petgraph::algo::has_path_connecting;
 use petgraph::{prelude::GraphMap, Directed};
 
 use super::*;
 
 pub use domain_type_checker::DomainTypeChecker;
 pub use problem_type_checker::ProblemTypeChecker;
-use generic_type_checker::*;
\ No newline at end of file
+use generic_type_checker::*;
+pub use generic_type_checker::TypeChecker;
+pub use scope::*;
\ No newline at end of file
@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::*;
 
 pub struct ProblemTypeChecker<'a> {
     generic_type_checker: TypeChecker<'a>,
     pub symbol_table: SymbolTable<'a>,
-    objects: HashMap<&'a str, Option<&'a str>>,
+    objects: HashMap<&'a str, Option<&'a SymbolType<'a>>>,
 }
 
 impl<'a> ProblemTypeChecker<'a> {
@@ -15,7 +15,7 @@ impl<'a> ProblemTypeChecker<'a> {
     ) -> ProblemTypeChecker<'a> {
         let mut objects = HashMap::new();
         for object in problem.objects.iter() {
-            objects.insert(object.name, object.symbol_type);
+            objects.insert(object.name, object.symbol_type.as_ref());
         }
         ProblemTypeChecker {
             generic_type_checker: TypeChecker {
@@ -25,19 +25,96 @@ impl<'a> ProblemTypeChecker<'a> {
             objects,
         }
     }
-    pub fn check_type_declarations(
+    pub fn check_type_declaration(&self, parameter: &Symbol<'a>) -> Option<SemanticErrorType> {
+        self.generic_type_checker.check_type_declaration(parameter)
+    }
+
+    // records an error for every ill-typed atom of "goal"
+    pub fn check_goal(&self, goal: &'a Formula<'a>, diagnostics: &mut Diagnostics) {
+        self.check_scoped_goal(goal, &mut Scope::new(&[]), diagnostics)
+    }
+
+    fn check_scoped_goal(
         &self,
-        parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
-        self.generic_type_checker
-            .check_type_declarations(parameters)
+        goal: &'a Formula<'a>,
+        scope: &mut Scope<'a, 'a>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        match goal {
+            Formula::Empty => {}
+            Formula::Atom(predicate) => {
+                if let Err(error) = self.check_predicate_instantiation(predicate, scope) {
+                    diagnostics.add_error(error);
+                }
+            }
+            Formula::Equals(a, b) => {
+                for term in [a, b] {
+                    if scope.get(term.name).is_none()
+                        && !self.objects.contains_key(term.name)
+                        && !self.symbol_table.constants.contains(&term.name)
+                    {
+                        diagnostics.add_error(SemanticErrorType::UndefinedObject(UndefinedSymbolError {
+                            symbol: term.name.to_string(),
+                            position: term.name_pos,
+                        }));
+                    }
+                }
+            }
+            Formula::Not(f) | Formula::Timed(_, f) => self.check_scoped_goal(f, scope, diagnostics),
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                for f in fs {
+                    self.check_scoped_goal(f, scope, diagnostics);
+                }
+            }
+            Formula::Imply(antecedents, consequents) => {
+                for f in antecedents.iter().chain(consequents.iter()) {
+                    self.check_scoped_goal(f, scope, diagnostics);
+                }
+            }
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+                if let Some(undeclared_type) = self.generic_type_checker.check_type_declarations(vars) {
+                    diagnostics.add_error(undeclared_type);
+                }
+                scope.push(vars);
+                self.check_scoped_goal(f, scope, diagnostics);
+                scope.pop();
+            }
+            Formula::Compare(..) | Formula::Assign(..) => {
+                for function in goal.get_functions() {
+                    if let Err(error) = self.check_function_instantiation(function, scope) {
+                        diagnostics.add_error(error);
+                    }
+                }
+            }
+        }
     }
 
+    // "scope" holds the quantified variables that "predicate" may refer to
     pub fn check_predicate_instantiation(
         &self,
         predicate: &'a Predicate<'a>,
+        scope: &Scope<'a, '_>,
+    ) -> Result<(), SemanticErrorType> {
+        self.check_instantiation(predicate, &self.symbol_table.predicates, scope)
+    }
+
+    // function terms are instantiated like predicates, e.g., "(road-length a b)"
+    pub fn check_function_instantiation(
+        &self,
+        function: &'a Predicate<'a>,
+        scope: &Scope<'a, '_>,
+    ) -> Result<(), SemanticErrorType> {
+        self.check_instantiation(function, &self.symbol_table.functions, scope)
+            .map_err(as_function_error)
+    }
+
+    fn check_instantiation(
+        &self,
+        predicate: &'a Predicate<'a>,
+        declarations: &HashSet<&'a Predicate<'a>>,
+        scope: &Scope<'a, '_>,
     ) -> Result<(), SemanticErrorType> {
-        match &self.symbol_table.predicates.get(predicate) {
+        match &declarations.get(predicate) {
             Some(definition) => {
                 if definition.variables.len() != predicate.variables.len() {
                     return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
@@ -49,16 +126,30 @@ impl<'a> ProblemTypeChecker<'a> {
                 }
                 for (expected, found) in definition.variables.iter().zip(predicate.variables.iter())
                 {
+                    if let Some(var) = scope.get(found.name) {
+                        if !self
+                            .generic_type_checker
+                            .is_var_type_consistent(var.symbol_type.as_ref(), expected.symbol_type.as_ref())
+                        {
+                            return Err(SemanticErrorType::InconsistentPredicateArgType(TypeError {
+                                expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                found: var.symbol_type.as_ref().map(|t| t.to_string()),
+                                var_name: found.name.to_string(),
+                                position: found.name_pos,
+                            }));
+                        }
+                        continue;
+                    }
                     match self.objects.get(found.name) {
                         Some(object_type) => {
                             let is_consistent = self
                                 .generic_type_checker
-                                .is_var_type_consistent(*object_type, expected.symbol_type);
+                                .is_var_type_consistent(*object_type, expected.symbol_type.as_ref());
                             if !is_consistent {
                                 return Err(SemanticErrorType::InconsistentPredicateArgType(
                                     TypeError {
-                                        expected: expected.symbol_type.map(String::from),
-                                        found: found.symbol_type.map(String::from),
+                                        expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                        found: found.symbol_type.as_ref().map(|t| t.to_string()),
                                         var_name: predicate.name.to_string(),
                                         position: found.name_pos,
                                     },
@@ -69,14 +160,14 @@ impl<'a> ProblemTypeChecker<'a> {
                             Some(constant) => {
                                 let is_consistent =
                                     self.generic_type_checker.is_var_type_consistent(
-                                        constant.symbol_type,
-                                        expected.symbol_type,
+                                        constant.symbol_type.as_ref(),
+                                        expected.symbol_type.as_ref(),
                                     );
                                 if !is_consistent {
                                     return Err(SemanticErrorType::InconsistentPredicateArgType(
                                         TypeError {
-                                            expected: expected.symbol_type.map(String::from),
-                                            found: constant.symbol_type.map(String::from),
+                                            expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                            found: constant.symbol_type.as_ref().map(|t| t.to_string()),
                                             var_name: predicate.name.to_string(),
                                             position: found.name_pos,
                                         },
@@ -128,11 +219,11 @@ impl<'a> ProblemTypeChecker<'a> {
                     Some(object_type) => {
                         let is_consistent = self
                             .generic_type_checker
-                            .is_var_type_consistent(*object_type, expected.symbol_type);
+                            .is_var_type_consistent(*object_type, expected.symbol_type.as_ref());
                         if !is_consistent {
                             return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
-                                expected: expected.symbol_type.map(String::from),
-                                found: object_type.map(String::from),
+                                expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                found: object_type.map(|t| t.to_string()),
                                 var_name: subtask.task.name.to_string(),
                                 position: found.name_pos,
                             }));
@@ -145,14 +236,14 @@ impl<'a> ProblemTypeChecker<'a> {
                                 Some(param) => {
                                     let is_consistent =
                                         self.generic_type_checker.is_var_type_consistent(
-                                            param.symbol_type,
-                                            expected.symbol_type,
+                                            param.symbol_type.as_ref(),
+                                            expected.symbol_type.as_ref(),
                                         );
                                     if !is_consistent {
                                         return Err(SemanticErrorType::InconsistentTaskArgType(
                                             TypeError {
-                                                expected: expected.symbol_type.map(String::from),
-                                                found: param.symbol_type.map(String::from),
+                                                expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                                found: param.symbol_type.as_ref().map(|t| t.to_string()),
                                                 var_name: subtask.task.name.to_string(),
                                                 position: found.name_pos,
                                             },
@@ -172,8 +263,8 @@ impl<'a> ProblemTypeChecker<'a> {
                                 Some(constant) => {
                                     let is_consistent =
                                         self.generic_type_checker.is_var_type_consistent(
-                                            constant.symbol_type,
-                                            expected.symbol_type,
+                                            constant.symbol_type.as_ref(),
+                                            expected.symbol_type.as_ref(),
                                         );
                                     if !is_consistent {
                                         return Err(
@@ -181,8 +272,9 @@ impl<'a> ProblemTypeChecker<'a> {
                                                 TypeError {
                                                     expected: expected
                                                         .symbol_type
-                                                        .map(String::from),
-                                                    found: constant.symbol_type.map(String::from),
+                                                        .as_ref()
+                                                        .map(|t| t.to_string()),
+                                                    found: constant.symbol_type.as_ref().map(|t| t.to_string()),
                                                     var_name: action.name.to_string(),
                                                     position: found.name_pos,
                                                 },
@@ -219,11 +311,11 @@ impl<'a> ProblemTypeChecker<'a> {
                     Some(object_type) => {
                         let is_consistent = self
                             .generic_type_checker
-                            .is_var_type_consistent(*object_type, expected.symbol_type);
+                            .is_var_type_consistent(*object_type, expected.symbol_type.as_ref());
                         if !is_consistent {
                             return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
-                                expected: expected.symbol_type.map(String::from),
-                                found: object_type.map(String::from),
+                                expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                found: object_type.map(|t| t.to_string()),
                                 var_name: subtask.task.name.to_string(),
                                 position: found.name_pos,
                             }));
@@ -236,14 +328,14 @@ impl<'a> ProblemTypeChecker<'a> {
                                 Some(definition) => {
                                     let is_consistent =
                                         self.generic_type_checker.is_var_type_consistent(
-                                            definition.symbol_type,
-                                            expected.symbol_type,
+                                            definition.symbol_type.as_ref(),
+                                            expected.symbol_type.as_ref(),
                                         );
                                     if !is_consistent {
                                         return Err(SemanticErrorType::InconsistentTaskArgType(
                                             TypeError {
-                                                expected: expected.symbol_type.map(String::from),
-                                                found: definition.symbol_type.map(String::from),
+                                                expected: expected.symbol_type.as_ref().map(|t| t.to_string()),
+                                                found: definition.symbol_type.as_ref().map(|t| t.to_string()),
                                                 var_name: subtask.task.name.to_string(),
                                                 position: found.name_pos,
                                             },
@@ -263,8 +355,8 @@ impl<'a> ProblemTypeChecker<'a> {
                                 Some(constant) => {
                                     let is_consistent =
                                         self.generic_type_checker.is_var_type_consistent(
-                                            constant.symbol_type,
-                                            expected.symbol_type,
+                                            constant.symbol_type.as_ref(),
+                                            expected.symbol_type.as_ref(),
                                         );
                                     if !is_consistent {
                                         return Err(
@@ -272,8 +364,9 @@ impl<'a> ProblemTypeChecker<'a> {
                                                 TypeError {
                                                     expected: expected
                                                         .symbol_type
-                                                        .map(String::from),
-                                                    found: constant.symbol_type.map(String::from),
+                                                        .as_ref()
+                                                        .map(|t| t.to_string()),
+                                                    found: constant.symbol_type.as_ref().map(|t| t.to_string()),
                                                     var_name: task.name.to_string(),
                                                     position: found.name_pos,
                                                 },
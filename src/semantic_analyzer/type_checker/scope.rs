@@ -0,0 +1,72 @@
+use super::*;
+
+// The variables that are visible in a formula. Every quantifier pushes its
+// variables as a new frame, which hides the variables of the outer frames.
+pub struct Scope<'a, 'b> {
+    frames: Vec<&'b [Symbol<'a>]>,
+}
+
+impl<'a, 'b> Scope<'a, 'b> {
+    pub fn new(parameters: &'b [Symbol<'a>]) -> Scope<'a, 'b> {
+        Scope {
+            frames: vec![parameters],
+        }
+    }
+
+    // pushes a new frame, and returns the variables that hide an outer one
+    pub fn push(&mut self, variables: &'b [Symbol<'a>]) -> Vec<&'b Symbol<'a>> {
+        let shadowed = variables
+            .iter()
+            .filter(|var| self.get(var.name).is_some())
+            .collect();
+        self.frames.push(variables);
+        shadowed
+    }
+
+    pub fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    // returns the innermost variable named "name"
+    pub fn get(&self, name: &str) -> Option<&'b Symbol<'a>> {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.iter().find(|var| var.name == name))
+    }
+}
+
+// returns the quantified variables in "formula" that hide a parameter (or
+// another quantified variable) with the same name
+pub fn shadowed_variables<'a, 'b>(formula: &'b Formula<'a>, parameters: &'b [Symbol<'a>]) -> Vec<&'b Symbol<'a>> {
+    let mut shadowed = vec![];
+    find_shadowed_variables(formula, &mut Scope::new(parameters), &mut shadowed);
+    shadowed
+}
+
+fn find_shadowed_variables<'a, 'b>(
+    formula: &'b Formula<'a>,
+    scope: &mut Scope<'a, 'b>,
+    shadowed: &mut Vec<&'b Symbol<'a>>,
+) {
+    match formula {
+        Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => {}
+        Formula::Compare(..) | Formula::Assign(..) => {}
+        Formula::Not(f) | Formula::Timed(_, f) => find_shadowed_variables(f, scope, shadowed),
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                find_shadowed_variables(f, scope, shadowed);
+            }
+        }
+        Formula::Imply(antecedents, consequents) => {
+            for f in antecedents.iter().chain(consequents.iter()) {
+                find_shadowed_variables(f, scope, shadowed);
+            }
+        }
+        Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+            shadowed.extend(scope.push(vars));
+            find_shadowed_variables(f, scope, shadowed);
+            scope.pop();
+        }
+    }
+}
\ No newline at end of file
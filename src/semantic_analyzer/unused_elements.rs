@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// adds the name of every predicate in "formula" (including the quantified ones) to "used"
+pub fn collect_predicates<'a>(formula: &Formula<'a>, used: &mut HashSet<&'a str>) {
+    match formula {
+        Formula::Empty | Formula::Equals(_, _) | Formula::Compare(..) | Formula::Assign(..) => {}
+        Formula::Atom(predicate) => {
+            used.insert(predicate.name);
+        }
+        Formula::Not(f) | Formula::ForAll(_, f) | Formula::Exists(_, f) | Formula::Timed(_, f) => {
+            collect_predicates(f, used);
+        }
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                collect_predicates(f, used);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_predicates(f, used);
+            }
+        }
+    }
+}
+
+// adds the terms (variables and constants) that appear in "formula" to "used"
+pub fn collect_terms<'a>(formula: &Formula<'a>, used: &mut HashSet<&'a str>) {
+    match formula {
+        Formula::Empty => {}
+        Formula::Atom(predicate) => {
+            for var in predicate.variables.iter() {
+                used.insert(var.name);
+            }
+        }
+        Formula::Equals(a, b) => {
+            used.insert(a.name);
+            used.insert(b.name);
+        }
+        Formula::Compare(..) | Formula::Assign(..) => {
+            for function in formula.get_functions() {
+                used.extend(function.variables.iter().map(|var| var.name));
+            }
+        }
+        Formula::Not(f) | Formula::ForAll(_, f) | Formula::Exists(_, f) | Formula::Timed(_, f) => {
+            collect_terms(f, used);
+        }
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                collect_terms(f, used);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_terms(f, used);
+            }
+        }
+    }
+}
+
+// adds the terms of the subtasks and the constraints of "tn" to "used"
+pub fn collect_tn_terms<'a>(tn: &HTN<'a>, used: &mut HashSet<&'a str>) {
+    for subtask in tn.subtasks.iter() {
+        for term in subtask.terms.iter() {
+            used.insert(term.name);
+        }
+    }
+    if let Some(constraints) = &tn.constraints {
+        for constraint in constraints {
+            match constraint {
+                Constraint::Equal(a, b) | Constraint::NotEqual(a, b) => {
+                    used.insert(a);
+                    used.insert(b);
+                }
+            }
+        }
+    }
+}
+
+// returns the parameters that do not appear in "used" (each reported once)
+pub fn unused_parameters<'a, 'b>(
+    parameters: &'b [Symbol<'a>],
+    used: &HashSet<&'a str>,
+) -> Vec<&'b Symbol<'a>> {
+    let mut reported = HashSet::new();
+    parameters
+        .iter()
+        .filter(|param| !used.contains(param.name) && reported.insert(param.name))
+        .collect()
+}
+
+// adds the types of "symbols" (including every member of union types) to "used"
+pub fn collect_types<'a, 'b>(symbols: impl Iterator<Item = &'b Symbol<'a>>, used: &mut HashSet<&'a str>)
+where
+    'a: 'b,
+{
+    for symbol in symbols {
+        if let Some(symbol_type) = &symbol.symbol_type {
+            used.extend(symbol_type.members().iter().copied());
+        }
+    }
+}
+
+// adds the types of the quantified variables in "formula" to "used"
+pub fn collect_quantified_types<'a>(formula: &Formula<'a>, used: &mut HashSet<&'a str>) {
+    match formula {
+        Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => {}
+        Formula::Compare(..) | Formula::Assign(..) => {}
+        Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+            collect_types(vars.iter(), used);
+            collect_quantified_types(f, used);
+        }
+        Formula::Not(f) | Formula::Timed(_, f) => {
+            collect_quantified_types(f, used);
+        }
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+            for f in fs {
+                collect_quantified_types(f, used);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_quantified_types(f, used);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    // the whole file, i.e., its definition followed by the end of file
+    Document,
+    // a parenthesized list that starts with a keyword, e.g., "(:method ...)"
+    Block,
+    // any other parenthesized list, e.g., a formula or a parameter list
+    List,
+}
+
+#[derive(Debug)]
+pub enum SyntaxElement<'a> {
+    Token(LosslessToken<'a>),
+    Node(SyntaxNode<'a>),
+}
+
+// a syntax tree that keeps every character of the source, so that it can be
+// rewritten without losing its comments. Its nodes are the parenthesized lists,
+// and they are related to the AST by the offsets of their tokens (e.g., the name
+// of a method is in the block that defines it).
+#[derive(Debug)]
+pub struct SyntaxNode<'a> {
+    pub kind: SyntaxKind,
+    pub children: Vec<SyntaxElement<'a>>,
+}
+
+impl<'a> SyntaxNode<'a> {
+    // the nodes directly below this one
+    pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode<'a>> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    // all tokens of the node, in order
+    pub fn tokens(&self) -> Vec<&LosslessToken<'a>> {
+        let mut tokens = vec![];
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Token(token) => tokens.push(token),
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+            }
+        }
+        tokens
+    }
+
+    // the keyword that the block starts with (after its '(')
+    pub fn keyword(&self) -> Option<&KeywordName> {
+        match self.children.get(1) {
+            Some(SyntaxElement::Token(LosslessToken {
+                token: Token::Keyword(keyword),
+                ..
+            })) => Some(keyword),
+            _ => None,
+        }
+    }
+
+    // the offsets of the first character of the node, and the one after its last
+    // character (excluding the trivia around them)
+    pub fn span(&self) -> (usize, usize) {
+        let tokens = self.tokens();
+        match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => (first.position.offset, last.position.end),
+            _ => (0, 0),
+        }
+    }
+
+    // the innermost node that contains "offset", e.g., the position of a symbol in the AST
+    pub fn node_at(&self, offset: usize) -> Option<&SyntaxNode<'a>> {
+        self.path_to(offset).pop()
+    }
+
+    // the innermost block that contains "offset", e.g., the definition of a method
+    // from the position of its name
+    pub fn block_at(&self, offset: usize) -> Option<&SyntaxNode<'a>> {
+        self.path_to(offset)
+            .into_iter()
+            .rev()
+            .find(|node| node.kind == SyntaxKind::Block)
+    }
+
+    // the comments of the node (including the ones around its first and last tokens)
+    pub fn comments(&self) -> Vec<Trivia<'a>> {
+        self.tokens()
+            .into_iter()
+            .flat_map(|token| token.comments().copied())
+            .collect()
+    }
+
+    // the source, where "target" (a node of this tree) is replaced with "text". The
+    // trivia before and after the target is kept.
+    pub fn replace(&self, target: &SyntaxNode, text: &str) -> String {
+        let mut output = String::new();
+        self.write(target, text, &mut output);
+        output
+    }
+
+    fn write(&self, target: &SyntaxNode, text: &str, output: &mut String) {
+        if std::ptr::eq(self, target) {
+            let tokens = self.tokens();
+            if let (Some(first), Some(last)) = (tokens.first(), tokens.last()) {
+                first.leading.iter().for_each(|trivia| output.push_str(trivia.text));
+                output.push_str(text);
+                last.trailing.iter().for_each(|trivia| output.push_str(trivia.text));
+            }
+            return;
+        }
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Token(token) => output.push_str(&token.to_string()),
+                SyntaxElement::Node(node) => node.write(target, text, output),
+            }
+        }
+    }
+
+    // the nodes that contain "offset", from this one to the innermost
+    fn path_to(&self, offset: usize) -> Vec<&SyntaxNode<'a>> {
+        let (start, end) = self.span();
+        if self.kind != SyntaxKind::Document && (offset < start || offset >= end) {
+            return vec![];
+        }
+        let mut path = vec![self];
+        if let Some(child_path) = self
+            .nodes()
+            .map(|node| node.path_to(offset))
+            .find(|child_path| !child_path.is_empty())
+        {
+            path.extend(child_path);
+        }
+        path
+    }
+}
+
+// the exact source text of the node
+impl<'a> fmt::Display for SyntaxNode<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for token in self.tokens() {
+            write!(f, "{}", token)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Parser<'a> {
+    // parses the document into a lossless syntax tree. Only the parentheses are
+    // checked, so that it is combined with the AST of "parse".
+    pub fn parse_concrete(&self) -> Result<SyntaxNode<'a>, ParsingError> {
+        // the lists that are open, along with their elements so far
+        let mut lists: Vec<Vec<SyntaxElement>> = vec![vec![]];
+        for token in self.tokenizer.tokenize_lossless() {
+            match token.token {
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    lists.push(vec![SyntaxElement::Token(token)]);
+                }
+                Token::Punctuator(PunctuationType::RParentheses) if lists.len() == 1 => {
+                    let error = SyntacticError {
+                        expected: "end of file".to_string(),
+                        found: token.token.to_string(),
+                        position: token.position,
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+                Token::Punctuator(PunctuationType::RParentheses) => {
+                    let mut children = lists.pop().unwrap();
+                    children.push(SyntaxElement::Token(token));
+                    let mut node = SyntaxNode {
+                        kind: SyntaxKind::List,
+                        children,
+                    };
+                    if node.keyword().is_some() {
+                        node.kind = SyntaxKind::Block;
+                    }
+                    lists.last_mut().unwrap().push(SyntaxElement::Node(node));
+                }
+                Token::EOF if lists.len() > 1 => {
+                    let error = SyntacticError {
+                        expected: "')'".to_string(),
+                        found: token.token.to_string(),
+                        position: token.position,
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+                _ => lists.last_mut().unwrap().push(SyntaxElement::Token(token)),
+            }
+        }
+        Ok(SyntaxNode {
+            kind: SyntaxKind::Document,
+            children: lists.pop().unwrap(),
+        })
+    }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::lexical_analyzer::TokenPosition;
+
 
 pub enum DefinitionType <'a> {
     Domain(&'a str),
@@ -6,5 +8,6 @@ pub enum DefinitionType <'a> {
 
 pub struct ProblemDefinition <'a> {
     pub problem_name: &'a str,
-    pub domain_name: &'a str
+    pub domain_name: &'a str,
+    pub domain_name_pos: TokenPosition
 }
\ No newline at end of file
@@ -60,7 +60,8 @@ impl <'a> Parser <'a> {
             name_pos: task.name_pos,
             parameters: task.parameters,
             preconditions: preconditions,
-            effects: effects
+            effects: effects,
+            duration: None
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,214 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    pub fn parse_durative_action(&'a self) -> Result<Action<'a>, ParsingError> {
+        let task = self.parse_task()?;
+        let mut preconditions = None;
+        let mut effects = None;
+        // Parse Duration
+        let duration = match self.tokenizer.get_token()? {
+            Token::Keyword(KeywordName::Duration) => self.parse_duration()?,
+            token => {
+                let error = SyntacticError {
+                    expected: format!("the duration of {}", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        // Parse Conditions
+        match self.tokenizer.lookahead()? {
+            Token::Keyword(KeywordName::Condition) => {
+                // skip condition keyword
+                let _ = self.tokenizer.get_token();
+                preconditions = Some(self.parse_durative_formula()?);
+            }
+            // the action has no condition
+            Token::Keyword(KeywordName::Effect) | Token::Punctuator(PunctuationType::RParentheses) => {}
+            token => {
+                let error = SyntacticError {
+                    expected: format!("(potentially empty) conditions of {}", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        // Parse Effects
+        match self.tokenizer.lookahead()? {
+            Token::Keyword(KeywordName::Effect) => {
+                // skip effects keyword
+                let _ = self.tokenizer.get_token();
+                effects = Some(self.parse_durative_formula()?);
+            }
+            // action has no effects
+            Token::Punctuator(PunctuationType::RParentheses) => {}
+            token => {
+                let error = SyntacticError {
+                    expected: format!("(potentially empty) effects of {}", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        // skip action block's closing parantheses
+        self.parse_closing_parenthesis(format!("closing the scope of {} using ')'", task.name))?;
+        Ok(Action {
+            name: task.name,
+            name_pos: task.name_pos,
+            parameters: task.parameters,
+            preconditions,
+            effects,
+            duration: Some(duration),
+        })
+    }
+
+    // parses the duration constraint, e.g., "(and (>= ?duration 2) (<= ?duration 5))"
+    fn parse_duration(&'a self) -> Result<Formula<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Empty),
+            Token::Punctuator(PunctuationType::LParentheses) => match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Empty),
+                Token::Operator(OperationType::And) => {
+                    let mut constraints = vec![];
+                    loop {
+                        match self.parse_duration()? {
+                            Formula::Empty => return Ok(Formula::And(constraints)),
+                            constraint => constraints.push(Box::new(constraint)),
+                        }
+                    }
+                }
+                Token::Operator(OperationType::Equal) => self.parse_comparison(ComparisonType::Equal),
+                Token::Operator(OperationType::LessThanOrEqual) => {
+                    self.parse_comparison(ComparisonType::LessThanOrEqual)
+                }
+                Token::Operator(OperationType::GreaterThanOrEqual) => {
+                    self.parse_comparison(ComparisonType::GreaterThanOrEqual)
+                }
+                Token::Operator(OperationType::LessThan) => self.parse_comparison(ComparisonType::LessThan),
+                Token::Operator(OperationType::GreaterThan) => self.parse_comparison(ComparisonType::GreaterThan),
+                token => {
+                    let error = SyntacticError {
+                        expected: "a duration constraint".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+            },
+            token => {
+                let error = SyntacticError {
+                    expected: "'(' to start the duration constraint".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses a condition (or an effect) of a durative action, in which the formulas
+    // are annotated with their time, e.g., "(and (at start (p)) (over all (q)))"
+    fn parse_durative_formula(&'a self) -> Result<Formula<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Empty),
+            Token::Punctuator(PunctuationType::LParentheses) => match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Empty),
+                Token::Operator(OperationType::And) => {
+                    let mut expressions = vec![];
+                    loop {
+                        match self.parse_durative_formula()? {
+                            Formula::Empty => return Ok(Formula::And(expressions)),
+                            formula => expressions.push(Box::new(formula)),
+                        }
+                    }
+                }
+                Token::Operator(OperationType::ForAll) => match self.tokenizer.get_token()? {
+                    Token::Punctuator(PunctuationType::LParentheses) => {
+                        let params = self.parse_args()?;
+                        let expression = Box::new(self.parse_durative_formula()?);
+                        self.parse_closing_parenthesis("')' to close the forall statement".to_string())?;
+                        Ok(Formula::ForAll(params, expression))
+                    }
+                    token => {
+                        let error = SyntacticError {
+                            expected: "'(' after forall keyword".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        Err(ParsingError::Syntactic(error))
+                    }
+                },
+                // Conditional Effect
+                Token::Operator(OperationType::Implication) => {
+                    let antecedent = self.parse_durative_formula()?;
+                    let consequent = self.parse_durative_formula()?;
+                    self.parse_closing_parenthesis("')' to close the conditional statement".to_string())?;
+                    Ok(Formula::Imply(vec![Box::new(antecedent)], vec![Box::new(consequent)]))
+                }
+                Token::Identifier("at") => match self.tokenizer.get_token()? {
+                    Token::Identifier("start") => self.parse_timed_formula(TimeSpecifier::AtStart),
+                    Token::Identifier("end") => self.parse_timed_formula(TimeSpecifier::AtEnd),
+                    token => {
+                        let error = SyntacticError {
+                            expected: "either 'start' or 'end' after 'at'".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        Err(ParsingError::Syntactic(error))
+                    }
+                },
+                Token::Identifier("over") => match self.tokenizer.get_token()? {
+                    Token::Identifier("all") => self.parse_timed_formula(TimeSpecifier::OverAll),
+                    token => {
+                        let error = SyntacticError {
+                            expected: "'all' after 'over'".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        Err(ParsingError::Syntactic(error))
+                    }
+                },
+                token => {
+                    let error = SyntacticError {
+                        expected: "a formula annotated with 'at start', 'at end', or 'over all'".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+            },
+            token => {
+                let error = SyntacticError {
+                    expected: "a (potentially empty) durative formula".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses the formula after its time specifier, e.g., "(at ?v ?l))"
+    fn parse_timed_formula(&'a self, time: TimeSpecifier) -> Result<Formula<'a>, ParsingError> {
+        let formula = self.parse_formula()?;
+        self.parse_closing_parenthesis(format!("')' to close the '{}' formula", time))?;
+        Ok(Formula::Timed(time, Box::new(formula)))
+    }
+
+    fn parse_closing_parenthesis(&self, expected: String) -> Result<(), ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(()),
+            token => {
+                let error = SyntacticError {
+                    expected,
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
\ No newline at end of file
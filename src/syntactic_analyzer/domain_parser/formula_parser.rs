@@ -61,31 +61,57 @@ impl<'a> Parser<'a> {
                             }
                         }
                     }
+                    // Numeric Comparison
+                    Token::Operator(OperationType::Equal)
+                        if !matches!(self.tokenizer.lookahead()?, Token::Identifier(_)) =>
+                    {
+                        self.parse_comparison(ComparisonType::Equal)
+                    }
+                    Token::Operator(OperationType::LessThan) => self.parse_comparison(ComparisonType::LessThan),
+                    Token::Operator(OperationType::GreaterThan) => self.parse_comparison(ComparisonType::GreaterThan),
+                    Token::Operator(OperationType::LessThanOrEqual) => {
+                        self.parse_comparison(ComparisonType::LessThanOrEqual)
+                    }
+                    Token::Operator(OperationType::GreaterThanOrEqual) => {
+                        self.parse_comparison(ComparisonType::GreaterThanOrEqual)
+                    }
+                    // Numeric Effects
+                    Token::Operator(OperationType::Assign) => self.parse_assignment(AssignmentType::Assign),
+                    Token::Operator(OperationType::Increase) => self.parse_assignment(AssignmentType::Increase),
+                    Token::Operator(OperationType::Decrease) => self.parse_assignment(AssignmentType::Decrease),
+                    Token::Operator(OperationType::ScaleUp) => self.parse_assignment(AssignmentType::ScaleUp),
+                    Token::Operator(OperationType::ScaleDown) => self.parse_assignment(AssignmentType::ScaleDown),
                     // Equality
                     Token::Operator(OperationType::Equal) => match self.tokenizer.get_token()? {
-                        Token::Identifier(p1) => match self.tokenizer.get_token()? {
-                            Token::Identifier(p2) => match self.tokenizer.get_token()? {
-                                Token::Punctuator(PunctuationType::RParentheses) => {
-                                    return Ok(Formula::Equals(p1, p2));
+                        Token::Identifier(p1) => {
+                            let p1 = Symbol::new(p1, self.tokenizer.get_last_token_position(), None, None);
+                            match self.tokenizer.get_token()? {
+                                Token::Identifier(p2) => {
+                                    let p2 = Symbol::new(p2, self.tokenizer.get_last_token_position(), None, None);
+                                    match self.tokenizer.get_token()? {
+                                        Token::Punctuator(PunctuationType::RParentheses) => {
+                                            return Ok(Formula::Equals(p1, p2));
+                                        }
+                                        token => {
+                                            let error = SyntacticError {
+                                                expected: "equality's closing parenthesis".to_string(),
+                                                found: token.to_string(),
+                                                position: self.tokenizer.get_last_token_position(),
+                                            };
+                                            return Err(ParsingError::Syntactic(error));
+                                        }
+                                    }
                                 }
                                 token => {
                                     let error = SyntacticError {
-                                        expected: "equality's closing parenthesis".to_string(),
+                                        expected: "right hand side of the equality".to_string(),
                                         found: token.to_string(),
                                         position: self.tokenizer.get_last_token_position(),
                                     };
                                     return Err(ParsingError::Syntactic(error));
                                 }
-                            },
-                            token => {
-                                let error = SyntacticError {
-                                    expected: "right hand side of the equality".to_string(),
-                                    found: token.to_string(),
-                                    position: self.tokenizer.get_last_token_position(),
-                                };
-                                return Err(ParsingError::Syntactic(error));
                             }
-                        },
+                        }
                         token => {
                             let error = SyntacticError {
                                 expected: "left hand side of the equality".to_string(),
@@ -151,6 +177,25 @@ impl<'a> Parser<'a> {
                             return Err(ParsingError::Syntactic(error));
                         }
                     },
+                    // Conditional Effect (or Implication)
+                    Token::Operator(OperationType::Implication) => {
+                        let antecedent = self.parse_formula()?;
+                        let consequent = self.parse_formula()?;
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Imply(
+                                vec![Box::new(antecedent)],
+                                vec![Box::new(consequent)],
+                            )),
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "')' to close the conditional statement".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                Err(ParsingError::Syntactic(error))
+                            }
+                        }
+                    }
                     // Single Atom
                     Token::Identifier(name) => {
                         let predicate = Predicate {
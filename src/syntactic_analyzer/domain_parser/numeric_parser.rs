@@ -0,0 +1,144 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    // parses a numeric expression, e.g., "(* 2 (road-length ?a ?b))"
+    pub fn parse_numeric_expression(&'a self) -> Result<NumericExpression<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Number(number) => Ok(NumericExpression::Number(number)),
+            Token::Identifier("duration") => Ok(NumericExpression::Duration),
+            Token::Punctuator(PunctuationType::LParentheses) => {
+                let operation = match self.tokenizer.get_token()? {
+                    Token::Identifier(name) => {
+                        return Ok(NumericExpression::Function(self.parse_function_term(name)?));
+                    }
+                    Token::Operator(OperationType::Plus) => ArithmeticOperation::Plus,
+                    Token::Punctuator(PunctuationType::Dash) => ArithmeticOperation::Minus,
+                    Token::Operator(OperationType::Times) => ArithmeticOperation::Times,
+                    Token::Operator(OperationType::Divide) => ArithmeticOperation::Divide,
+                    token => {
+                        let error = SyntacticError {
+                            expected: "a function or an arithmetic operation".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
+                };
+                let mut operands = vec![];
+                while self.tokenizer.lookahead()? != Token::Punctuator(PunctuationType::RParentheses) {
+                    operands.push(self.parse_numeric_expression()?);
+                }
+                // skip the closing parenthesis
+                let _ = self.tokenizer.get_token();
+                if operands.is_empty() {
+                    let error = SyntacticError {
+                        expected: format!("the operands of {}", operation),
+                        found: ")".to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+                Ok(NumericExpression::Operation(operation, operands))
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "a numeric expression".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses the arguments of the function term "name" (which is the last token)
+    pub fn parse_function_term(&'a self, name: &'a str) -> Result<Predicate<'a>, ParsingError> {
+        let name_pos = self.tokenizer.get_last_token_position();
+        Ok(Predicate::new(name, name_pos, self.parse_args()?))
+    }
+
+    // parses a comparison of numeric expressions (after its operator)
+    pub fn parse_comparison(&'a self, comparison: ComparisonType) -> Result<Formula<'a>, ParsingError> {
+        let lhs = self.parse_numeric_expression()?;
+        let rhs = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Compare(comparison, lhs, rhs)),
+            token => {
+                let error = SyntacticError {
+                    expected: format!("')' to close the {} comparison", comparison),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses a numeric effect (after its operator), e.g., "(increase (total-cost) 1)"
+    pub fn parse_assignment(&'a self, assignment: AssignmentType) -> Result<Formula<'a>, ParsingError> {
+        let function = match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => match self.tokenizer.get_token()? {
+                Token::Identifier(name) => self.parse_function_term(name)?,
+                token => {
+                    let error = SyntacticError {
+                        expected: format!("the name of the function to {}", assignment),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            },
+            token => {
+                let error = SyntacticError {
+                    expected: format!("'(' before the function to {}", assignment),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let value = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Formula::Assign(assignment, function, value)),
+            token => {
+                let error = SyntacticError {
+                    expected: format!("')' to close the {} effect", assignment),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses the plan metric, e.g., "minimize (total-cost))"
+    pub fn parse_metric(&'a self) -> Result<Metric<'a>, ParsingError> {
+        let optimization = match self.tokenizer.get_token()? {
+            Token::Identifier("minimize") => Optimization::Minimize,
+            Token::Identifier("maximize") => Optimization::Maximize,
+            token => {
+                let error = SyntacticError {
+                    expected: "either minimize or maximize".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let expression = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Metric {
+                optimization,
+                expression,
+            }),
+            token => {
+                let error = SyntacticError {
+                    expected: "')' to close the metric".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
\ No newline at end of file
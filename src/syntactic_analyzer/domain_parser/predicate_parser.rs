@@ -26,6 +26,43 @@ impl<'a> Parser<'a> {
         Ok(predicates)
     }
 
+    // parses function declarations, e.g., "(road-length ?a ?b - location) - number"
+    pub fn parse_functions(&'a self) -> Result<Vec<Predicate<'a>>, ParsingError> {
+        let mut functions = vec![];
+        loop {
+            match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    functions.push(self.parse_predicate_definition()?);
+                }
+                // the type of the preceding functions (only numeric functions are supported)
+                Token::Punctuator(PunctuationType::Dash) if !functions.is_empty() => {
+                    match self.tokenizer.get_token()? {
+                        Token::Identifier("number") => {}
+                        token => {
+                            let error = SyntacticError {
+                                expected: "number as the type of functions".to_string(),
+                                found: token.to_string(),
+                                position: self.tokenizer.get_last_token_position(),
+                            };
+                            return Err(ParsingError::Syntactic(error));
+                        }
+                    }
+                }
+                Token::Punctuator(PunctuationType::RParentheses) => {
+                    return Ok(functions);
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "function definition".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            }
+        }
+    }
+
     // parses a SINGLE predicate definition
     fn parse_predicate_definition(&'a self) -> Result<Predicate<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
@@ -4,9 +4,11 @@ mod tests;
 mod domain_parser;
 mod problem_parser;
 mod syntax_tree;
+mod concrete_syntax_tree;
 
 pub use parser::Parser;
 pub use syntax_tree::*;
+pub use concrete_syntax_tree::*;
 use definition_types::*;
 use crate::output::*;
 use crate::lexical_analyzer::*;
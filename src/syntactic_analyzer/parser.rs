@@ -1,30 +1,62 @@
+use std::cell::RefCell;
+
 use self::definition_types::ProblemDefinition;
 
 use super::*;
 
+// declared requirements, where unknown ones are given by their name
+type Requirements = Vec<(Result<RequirementType, String>, TokenPosition)>;
+
 pub struct Parser<'a> {
     pub tokenizer: LexicalAnalyzer<'a>,
+    // errors that the parser has recovered from
+    errors: RefCell<Vec<ParsingError>>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokenizer: LexicalAnalyzer<'a>) -> Parser<'a> {
-        Parser { tokenizer }
+        Parser {
+            tokenizer,
+            errors: RefCell::new(vec![]),
+        }
     }
+
+    // returns the syntax tree, or the first error in the document
     pub fn parse(&'a self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
+        let (ast, errors) = self.parse_with_recovery();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(ast.unwrap()),
+        }
+    }
+
+    // parses the whole document, and resumes at the next top-level block after
+    // each syntax error. Returns the (potentially partial) syntax tree, along
+    // with all errors found. The tree is None if the document header is malformed.
+    pub fn parse_with_recovery(&'a self) -> (Option<AbstractSyntaxTree<'a>>, Vec<ParsingError>) {
+        let ast = match self.parse_header() {
+            // Domain Definition
+            Ok(DefinitionType::Domain(domain_name)) => {
+                Some(self.parse_domain(domain_name).into())
+            }
+            // Problem Definition
+            Ok(DefinitionType::Problem(problem_definition)) => {
+                Some(self.parse_problem(problem_definition).into())
+            }
+            Err(error) => {
+                self.errors.borrow_mut().push(error);
+                None
+            }
+        };
+        (ast, self.errors.take())
+    }
+
+    fn parse_header(&self) -> Result<DefinitionType<'_>, ParsingError> {
         // match opening '('
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
                 // Determine file type
-                match self.parse_document_type()? {
-                    // Domain Definition
-                    DefinitionType::Domain(domain_name) => {
-                        Ok(self.parse_domain(domain_name)?.into())
-                    }
-                    // Problem Definition
-                    DefinitionType::Problem(problem_definition) => {
-                        Ok(self.parse_problem(problem_definition)?.into())
-                    }
-                }
+                self.parse_document_type()
             }
             token => {
                 let error = SyntacticError {
@@ -37,6 +69,59 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // records "error", and resumes parsing from the next top-level block using
+    // "parse_block". Returns false if the end of file is reached first.
+    pub(super) fn recover<F>(&self, error: ParsingError, mut parse_block: F) -> bool
+    where
+        F: FnMut() -> Result<(), ParsingError>,
+    {
+        let mut error = error;
+        loop {
+            self.errors.borrow_mut().push(error);
+            if !self.synchronize() {
+                return false;
+            }
+            match parse_block() {
+                Ok(_) => return true,
+                Err(new_error) => error = new_error,
+            }
+        }
+    }
+
+    // skips tokens until a '(' that opens a top-level block is consumed.
+    // Returns false if the end of file is reached first.
+    fn synchronize(&self) -> bool {
+        loop {
+            match self.tokenizer.get_token() {
+                Ok(Token::EOF) => return false,
+                Ok(Token::Punctuator(PunctuationType::LParentheses)) => {
+                    if let Ok(Token::Keyword(
+                        KeywordName::Action
+                        | KeywordName::DurativeAction
+                        | KeywordName::Method
+                        | KeywordName::Task
+                        | KeywordName::Predicates
+                        | KeywordName::Types
+                        | KeywordName::Constants
+                        | KeywordName::Requirements
+                        | KeywordName::Objects
+                        | KeywordName::HTN
+                        | KeywordName::Init
+                        | KeywordName::Goal
+                        | KeywordName::Functions
+                        | KeywordName::Metric,
+                    )) = self.tokenizer.lookahead()
+                    {
+                        return true;
+                    }
+                }
+                Ok(_) => {}
+                // lexical errors are independent of the syntactic context
+                Err(error) => self.errors.borrow_mut().push(error.into()),
+            }
+        }
+    }
+
     fn parse_document_type(&self) -> Result<DefinitionType, ParsingError> {
         // match keyword 'define'
         match self.tokenizer.get_token()? {
@@ -126,12 +211,13 @@ impl<'a> Parser<'a> {
                                     Token::Keyword(KeywordName::Domain) => {
                                         match self.tokenizer.get_token()? {
                                             Token::Identifier(domain_name) => {
+                                                let domain_name_pos = self.tokenizer.get_last_token_position();
                                                 match self.tokenizer.get_token()? {
                                                     Token::Punctuator(
                                                         PunctuationType::RParentheses,
                                                     ) => {
                                                         return Ok(DefinitionType::Problem(
-                                                            ProblemDefinition{domain_name, problem_name},
+                                                            ProblemDefinition{domain_name, problem_name, domain_name_pos},
                                                         ));
                                                     }
                                                     token => {
@@ -197,13 +283,27 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_requirements(&self) -> Result<Vec<RequirementType>, ParsingError> {
+    // returns the requirements along with their position
+    // unknown requirements are returned by their name, rather than as a lexical error
+    pub fn parse_requirements(&self) -> Result<Requirements, ParsingError> {
         let mut requirements = vec![];
         let mut finished = false;
         while !finished {
-            match self.tokenizer.get_token()? {
+            let token = match self.tokenizer.get_token() {
+                Ok(token) => token,
+                Err(LexicalError { error_type: LexicalErrorType::InvalidKeyword, lexeme, position }) => {
+                    requirements.push((Err(format!(":{}", lexeme)), position));
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+            match token {
                 Token::Requirement(req) => {
-                    requirements.push(req);
+                    requirements.push((Ok(req), self.tokenizer.get_last_token_position()));
+                }
+                // ":constraints" is lexed as the keyword of method constraints
+                Token::Keyword(KeywordName::Constraints) => {
+                    requirements.push((Ok(RequirementType::Constraints), self.tokenizer.get_last_token_position()));
                 }
                 Token::Punctuator(PunctuationType::RParentheses) => {
                     finished = true;
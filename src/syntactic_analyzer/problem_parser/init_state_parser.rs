@@ -0,0 +1,70 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    // parses the facts of the initial state, and the initial values of numeric fluents
+    pub fn parse_init_state(&'a self) -> Result<(Vec<Predicate<'a>>, Vec<FunctionValue<'a>>), ParsingError> {
+        let mut facts = vec![];
+        let mut values = vec![];
+        loop {
+            match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::LParentheses) => match self.tokenizer.get_token()? {
+                    Token::Identifier(name) => {
+                        facts.push(self.parse_function_term(name)?);
+                    }
+                    Token::Operator(OperationType::Equal) => {
+                        values.push(self.parse_function_value()?);
+                    }
+                    token => {
+                        let error = SyntacticError {
+                            expected: "a fact, or the value of a function".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
+                },
+                Token::Punctuator(PunctuationType::RParentheses) => {
+                    return Ok((facts, values));
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "initial state definition".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            }
+        }
+    }
+
+    // parses "(f o_1 ... o_n) value)" of a function value "(= (f o_1 ... o_n) value)"
+    fn parse_function_value(&'a self) -> Result<FunctionValue<'a>, ParsingError> {
+        let function = match (self.tokenizer.get_token()?, self.tokenizer.get_token()?) {
+            (Token::Punctuator(PunctuationType::LParentheses), Token::Identifier(name)) => {
+                self.parse_function_term(name)?
+            }
+            (_, token) => {
+                let error = SyntacticError {
+                    expected: "a function term".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        match (self.tokenizer.get_token()?, self.tokenizer.get_token()?) {
+            (Token::Number(value), Token::Punctuator(PunctuationType::RParentheses)) => {
+                Ok(FunctionValue { function, value })
+            }
+            (_, token) => {
+                let error = SyntacticError {
+                    expected: format!("the value of {} followed by ')'", function.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -174,12 +174,22 @@ impl<'a> Parser<'a> {
                 match self.tokenizer.get_token()? {
                     Token::Keyword(KeywordName::Constraints) => {
                         constraints = Some(self.parse_constraints()?);
-                        return Ok(HTN {
-                            subtasks,
-                            ordering_pos,
-                            orderings: TaskOrdering::Total,
-                            constraints,
-                        });
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => Ok(HTN {
+                                subtasks,
+                                ordering_pos,
+                                orderings: TaskOrdering::Total,
+                                constraints,
+                            }),
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "closing ')' after task network definition".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                Err(ParsingError::Syntactic(error))
+                            }
+                        }
                     }
                     Token::Punctuator(PunctuationType::RParentheses) => {
                         return Ok(HTN {
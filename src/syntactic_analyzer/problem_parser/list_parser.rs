@@ -15,18 +15,9 @@ impl<'a> Parser<'a> {
                     // match type
                     let object_type = self.tokenizer.get_token()?;
                     let type_pos = self.tokenizer.get_last_token_position();
-                    match object_type {
-                        Token::Identifier(t) => {
-                            for (o, obj_pos) in objects {
-                                result.push(Symbol::new(
-                                    o,
-                                    obj_pos,
-                                    Some(t),
-                                    Some(type_pos),
-                                ));
-                            }
-                            objects = vec![];
-                        }
+                    let symbol_type = match object_type {
+                        Token::Identifier(t) => SymbolType::Simple(t),
+                        Token::Punctuator(PunctuationType::LParentheses) => self.parse_either_type()?,
                         token => {
                             let error = SyntacticError {
                                 expected: format!("The type of objects"),
@@ -35,7 +26,16 @@ impl<'a> Parser<'a> {
                             };
                             return Err(ParsingError::Syntactic(error));
                         }
+                    };
+                    for (o, obj_pos) in objects {
+                        result.push(Symbol::new(
+                            o,
+                            obj_pos,
+                            Some(symbol_type.clone()),
+                            Some(type_pos),
+                        ));
                     }
+                    objects = vec![];
                     token = self.tokenizer.get_token()?;
                 }
                 Token::Punctuator(PunctuationType::RParentheses) => {
@@ -55,4 +55,36 @@ impl<'a> Parser<'a> {
             }
         }
     }
-}
+
+    // parses the members of "(either t1 t2 ...)" (after the opening parenthesis)
+    fn parse_either_type(&'a self) -> Result<SymbolType<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Identifier("either") => {}
+            token => {
+                let error = SyntacticError {
+                    expected: "either".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        let mut types = vec![];
+        loop {
+            match self.tokenizer.get_token()? {
+                Token::Identifier(t) => types.push(t),
+                Token::Punctuator(PunctuationType::RParentheses) if !types.is_empty() => {
+                    return Ok(SymbolType::Either(types));
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "the type of objects".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
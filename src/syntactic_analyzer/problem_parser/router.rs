@@ -1,75 +1,116 @@
 use super::*;
 
 impl <'a> Parser<'a> {
-    pub fn parse_problem(&'a self, meta_data: ProblemDefinition<'a>) -> Result<ProblemAST<'a>, ParsingError> {
-        let mut syntax_tree = ProblemAST::new();
+    // parses the problem body, and records the syntax errors it recovers from
+    pub fn parse_problem(&'a self, meta_data: ProblemDefinition<'a>) -> ProblemAST<'a> {
+        let mut syntax_tree = ProblemAST::new(
+            meta_data.problem_name.to_string(),
+            meta_data.domain_name.to_string(),
+            meta_data.domain_name_pos,
+        );
         loop {
-            match self.tokenizer.get_token()? {
-                Token::Punctuator(PunctuationType::LParentheses) => {
-                    // match declaration type
-                    match self.tokenizer.get_token()? {
-                        // requirement declaration
-                        Token::Keyword(KeywordName::Requirements) => {
-                            let requirements = self.parse_requirements()?;
-                            for requirement in requirements {
-                                syntax_tree.add_requirement(requirement);
-                            }
-                        }
-                        // objects declaration
-                        Token::Keyword(KeywordName::Objects) => {
-                            let objects = self.parse_args()?;
-                            for object in objects {
-                                match object.symbol_type {
-                                    Some(t) => {
-                                        syntax_tree.add_typed_object(
-                                            object.name,
-                                            object.name_pos,
-                                            t,
-                                            object.type_pos.unwrap(),
-                                        );
-                                    }
-                                    None => {
-                                        syntax_tree.add_object(
-                                            object.name,
-                                            object.name_pos,
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        // initial task network declaration
-                        Token::Keyword(KeywordName::HTN) => {
-                            let init_tn = self.parse_initial_tn()?;
-                            syntax_tree.add_init_tn(init_tn);
-                        }
-                        // goal state (optional)
-                        Token::Keyword(KeywordName::Goal) => {
-                            let goal = self.parse_formula()?;
-                            syntax_tree.add_goal(goal)
-                        }
-                        // initial state
-                        Token::Keyword(KeywordName::Init) => {
-                            let init_state = self.parse_predicates()?;
-                            syntax_tree.add_init_state(init_state)
+            let result = match self.tokenizer.get_token() {
+                Ok(Token::Punctuator(PunctuationType::LParentheses)) => {
+                    self.parse_problem_block(&mut syntax_tree)
+                }
+                Ok(Token::EOF | Token::Punctuator(PunctuationType::RParentheses)) => {
+                    return syntax_tree;
+                }
+                Ok(token) => {
+                    let error = SyntacticError {
+                        expected: format!("either ')' to close the definition of {}, or '(' to start defining new components", meta_data.problem_name),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+                Err(error) => Err(error.into()),
+            };
+            if let Err(error) = result {
+                if !self.recover(error, || self.parse_problem_block(&mut syntax_tree)) {
+                    return syntax_tree;
+                }
+            }
+        }
+    }
+
+    // parses a top-level block of the problem (after its opening '(')
+    fn parse_problem_block(&'a self, syntax_tree: &mut ProblemAST<'a>) -> Result<(), ParsingError> {
+        // match declaration type
+        match self.tokenizer.get_token()? {
+            // requirement declaration
+            Token::Keyword(KeywordName::Requirements) => {
+                let requirements = self.parse_requirements()?;
+                for (requirement, position) in requirements {
+                    match requirement {
+                        Ok(requirement) => syntax_tree.add_requirement(requirement),
+                        Err(name) => syntax_tree.add_unknown_requirement(name, position),
+                    }
+                }
+            }
+            // objects declaration
+            Token::Keyword(KeywordName::Objects) => {
+                let objects = self.parse_args()?;
+                for object in objects {
+                    match object.symbol_type {
+                        Some(t) => {
+                            syntax_tree.add_typed_object(
+                                object.name,
+                                object.name_pos,
+                                t,
+                                object.type_pos.unwrap(),
+                            );
                         }
-                        token => {
-                            let error = SyntacticError {
-                                expected: "a keyword for block definition"
-                                    .to_string(),
-                                found: token.to_string(),
-                                position: self.tokenizer.get_last_token_position(),
-                            };
-                            return Err(ParsingError::Syntactic(error));
+                        None => {
+                            syntax_tree.add_object(
+                                object.name,
+                                object.name_pos,
+                            );
                         }
                     }
                 }
-                Token::EOF | Token::Punctuator(PunctuationType::RParentheses) => {
-                    return Ok(syntax_tree);
-                }
-                err => {
-                    panic!("unexpected token {:?}", err)
+            }
+            // initial task network declaration
+            Token::Keyword(KeywordName::HTN) => {
+                let init_tn = self.parse_initial_tn()?;
+                syntax_tree.add_init_tn(init_tn);
+            }
+            // goal state (optional)
+            Token::Keyword(KeywordName::Goal) => {
+                let goal = self.parse_formula()?;
+                syntax_tree.add_goal(goal);
+                match self.tokenizer.get_token()? {
+                    Token::Punctuator(PunctuationType::RParentheses) => {}
+                    token => {
+                        let error = SyntacticError {
+                            expected: "')' to close the goal definition".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
                 }
             }
+            // initial state
+            Token::Keyword(KeywordName::Init) => {
+                let (init_state, init_values) = self.parse_init_state()?;
+                syntax_tree.add_init_state(init_state);
+                syntax_tree.add_init_values(init_values);
+            }
+            // plan metric (optional)
+            Token::Keyword(KeywordName::Metric) => {
+                let metric = self.parse_metric()?;
+                syntax_tree.add_metric(metric)
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "a keyword for block definition".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
         }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use crate::lexical_analyzer::TokenPosition;
+
 use super::*;
 
 #[derive(Debug)]
@@ -6,7 +10,12 @@ pub struct DomainAST<'a> {
     pub types: Option<Vec<Symbol<'a>>>,
     pub constants: Option<Vec<Symbol<'a>>>,
     pub requirements: Vec<RequirementType>,
+    // where each requirement is declared (in order, including duplicates)
+    pub requirement_positions: HashMap<RequirementType, Vec<TokenPosition>>,
+    // requirements that are not part of HDDL (along with their position)
+    pub unknown_requirements: Vec<(String, TokenPosition)>,
     pub predicates: Vec<Predicate<'a>>,
+    pub functions: Vec<Predicate<'a>>,
     pub compound_tasks: Vec<Task<'a>>,
     pub methods: Vec<Method<'a>>,
     pub actions: Vec<Action<'a>>,
@@ -19,21 +28,33 @@ impl<'a> DomainAST<'a> {
             types: None,
             constants: None,
             requirements: vec![],
+            requirement_positions: HashMap::new(),
+            unknown_requirements: vec![],
             predicates: vec![],
+            functions: vec![],
             compound_tasks: vec![],
             methods: vec![],
             actions: vec![],
         }
     }
 
-    pub fn add_requirement(&mut self, req: RequirementType) {
+    pub fn add_requirement(&mut self, req: RequirementType, position: TokenPosition) {
         self.requirements.push(req);
+        self.requirement_positions.entry(req).or_default().push(position);
+    }
+
+    pub fn add_unknown_requirement(&mut self, name: String, position: TokenPosition) {
+        self.unknown_requirements.push((name, position));
     }
 
     pub fn add_predicate(&mut self, predicate: Predicate<'a>) {
         self.predicates.push(predicate);
     }
 
+    pub fn add_function(&mut self, function: Predicate<'a>) {
+        self.functions.push(function);
+    }
+
     pub fn add_compound_task(&mut self, task: Task<'a>) {
         self.compound_tasks.push(task);
     }
@@ -1,18 +1,18 @@
 use super::*;
 
 pub enum AbstractSyntaxTree<'a>{
-    Domain(DomainAST<'a>),
-    Problem(ProblemAST<'a>)
+    Domain(Box<DomainAST<'a>>),
+    Problem(Box<ProblemAST<'a>>)
 }
 
 impl <'a> From<DomainAST<'a>> for AbstractSyntaxTree<'a> {
     fn from(d: domain::DomainAST<'a>) -> Self { 
-        AbstractSyntaxTree::Domain(d)
+        AbstractSyntaxTree::Domain(Box::new(d))
      }
 }
 
 impl <'a> From<ProblemAST<'a>> for AbstractSyntaxTree<'a> {
     fn from(p: problem::ProblemAST<'a>) -> Self { 
-        AbstractSyntaxTree::Problem(p)
+        AbstractSyntaxTree::Problem(Box::new(p))
      }
 }
\ No newline at end of file
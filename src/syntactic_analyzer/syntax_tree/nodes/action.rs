@@ -10,8 +10,17 @@ pub struct Action<'a> {
     pub name: &'a str,
     pub name_pos: TokenPosition,
     pub parameters: Vec<Symbol<'a>>,
+    // the precondition (or the ":condition" of durative actions)
     pub preconditions: Option<Formula<'a>>,
-    pub effects: Option<Formula<'a>>
+    pub effects: Option<Formula<'a>>,
+    // duration constraint of durative actions (None for instantaneous ones)
+    pub duration: Option<Formula<'a>>
+}
+
+impl <'a> Action<'a> {
+    pub fn is_durative(&self) -> bool {
+        self.duration.is_some()
+    }
 }
 
 impl <'a> Hash for Action<'a> {
@@ -18,7 +18,13 @@ pub enum Formula<'a> {
     // ∀vars: formula
     ForAll(Vec<Symbol<'a>>, Box<Formula<'a>>),
     // formula = formula'
-    Equals(&'a str, &'a str),
+    Equals(Symbol<'a>, Symbol<'a>),
+    // comparison of numeric expressions, e.g., "(> (fuel ?v) 0)"
+    Compare(ComparisonType, NumericExpression<'a>, NumericExpression<'a>),
+    // numeric effect, e.g., "(increase (total-cost) 1)"
+    Assign(AssignmentType, Predicate<'a>, NumericExpression<'a>),
+    // condition or effect of a durative action, e.g., "(at end (at ?v ?l))"
+    Timed(TimeSpecifier, Box<Formula<'a>>),
 }
 
 impl<'a> Formula<'a> {
@@ -29,7 +35,7 @@ impl<'a> Formula<'a> {
             Formula::Atom(predicate) => {
                 predicates.push(predicate);
             }
-            Formula::Not(new_formula) => {
+            Formula::Not(new_formula) | Formula::Timed(_, new_formula) => {
                 predicates.extend(new_formula.get_propositional_predicates().iter());
             }
             Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
@@ -48,10 +54,44 @@ impl<'a> Formula<'a> {
             Formula::Equals(_, _) => {}
             // not propositional
             Formula::ForAll(_, _) | Formula::Exists(_, _) => {}
+            Formula::Compare(..) | Formula::Assign(..) => {}
         }
         return predicates;
     }
 
+    // the function terms of numeric conditions and effects in the formula
+    pub fn get_functions(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => vec![],
+            Formula::Not(f) | Formula::ForAll(_, f) | Formula::Exists(_, f) | Formula::Timed(_, f) => {
+                f.get_functions()
+            }
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                fs.iter().flat_map(|f| f.get_functions()).collect()
+            }
+            Formula::Imply(ps, qs) => ps.iter().chain(qs.iter()).flat_map(|f| f.get_functions()).collect(),
+            Formula::Compare(_, lhs, rhs) => {
+                let mut functions = lhs.get_functions();
+                functions.extend(rhs.get_functions());
+                functions
+            }
+            Formula::Assign(_, function, value) => {
+                let mut functions = vec![function];
+                functions.extend(value.get_functions());
+                functions
+            }
+        }
+    }
+
+    // the time-annotated subformulas of a durative condition (or effect)
+    pub fn get_timed_formulas(&self) -> Vec<(TimeSpecifier, &Formula<'a>)> {
+        match self {
+            Formula::Timed(time, f) => vec![(*time, &**f)],
+            Formula::And(fs) => fs.iter().flat_map(|f| f.get_timed_formulas()).collect(),
+            _ => vec![],
+        }
+    }
+
     pub fn to_cnf(&self) -> Formula<'a> {
         return self.simplify().to_nnf().distribute_disjunction();
     }
@@ -59,7 +99,7 @@ impl<'a> Formula<'a> {
     fn simplify(&self) -> Formula<'a> {
         match self {
             Formula::Empty => Formula::Empty,
-            Formula::Atom(_) => self.clone(),
+            Formula::Atom(_) | Formula::Compare(..) | Formula::Assign(..) | Formula::Timed(..) => self.clone(),
             Formula::Not(f) => {
                 match &**f {
                     Formula::Not(sub_f) => {
@@ -67,8 +107,8 @@ impl<'a> Formula<'a> {
                     },
                     Formula::Equals(a, b) => {
                         Formula::Xor(vec![
-                            Box::new(Formula::Atom(Predicate::new_dummy(a))),
-                            Box::new(Formula::Atom(Predicate::new_dummy(b))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(a.name))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(b.name))),
                         ]).simplify()
                     }
                     _ => {
@@ -145,10 +185,10 @@ impl<'a> Formula<'a> {
             Formula::Equals(a, b) => {
                 // a = b -> (a ^ b) v (~a ^ ~b)
                 let pred_a = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&a))
+                    Formula::Atom(Predicate::new_dummy(a.name))
                 );
                 let pred_b = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&b))
+                    Formula::Atom(Predicate::new_dummy(b.name))
                 );
                 let pos_conjunct = Formula::And(vec![pred_a.clone(), pred_b.clone()]);
                 let not_a = Box::new(Formula::Not(pred_a));
@@ -162,10 +202,11 @@ impl<'a> Formula<'a> {
     fn to_nnf(&self) -> Formula<'a> {
         match self {
             Formula::Empty => Formula::Empty,
-            Formula::Atom(_) => self.clone(),
+            Formula::Atom(_) | Formula::Compare(..) | Formula::Assign(..) | Formula::Timed(..) => self.clone(),
             Formula::Not(f) => match &**f {
                 Formula::Empty => self.clone(),
                 Formula::Atom(p) => self.clone(),
+                Formula::Compare(..) | Formula::Assign(..) | Formula::Timed(..) => self.clone(),
                 Formula::Not(g) => g.to_nnf(),
                 Formula::And(fs) => Formula::Or(
                     fs.iter()
@@ -203,6 +244,7 @@ impl<'a> Formula<'a> {
     fn distribute_disjunction(&self) -> Formula<'a> {
         match self {
             Formula::Empty | Formula::Atom(_) | Formula::Not(_) => self.clone(),
+            Formula::Compare(..) | Formula::Assign(..) | Formula::Timed(..) => self.clone(),
             Formula::And(fs) => Formula::And(
                 fs.iter()
                     .map(|f| Box::new(f.distribute_disjunction()))
@@ -247,39 +289,58 @@ impl<'a> Formula<'a> {
         }
     }
 
+    // drops the subformulas that are not propositional (i.e., quantified, numeric, and
+    // timed ones), by replacing them with Empty, which leaves them unconstrained
     fn drop_quantifiers(&self) -> Formula<'a> {
         match self {
             Formula::Empty => {},
             Formula::Atom(_) => {},
             Formula::Not(f) => {
-                return Formula::Not(Box::new(f.drop_quantifiers()))
+                return match f.drop_quantifiers() {
+                    Formula::Empty => Formula::Empty,
+                    g => Formula::Not(Box::new(g)),
+                }
             }
             Formula::And(fs) => {
-                return Formula::And(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
-            }
-            Formula::Or(fs) => {
-                return Formula::Or(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
+                // an unconstrained conjunct does not affect the conjunction
+                let conjuncts: Vec<Box<Formula<'a>>> = fs.iter().map(|f| {
+                    f.drop_quantifiers()
+                }).filter(|f| !matches!(f, Formula::Empty)).map(Box::new).collect();
+                if conjuncts.is_empty() && !fs.is_empty() {
+                    return Formula::Empty;
+                }
+                return Formula::And(conjuncts);
             }
-            Formula::Xor(fs) => {
-                return Formula::Xor(fs.iter().map(|f| {
+            Formula::Or(fs) | Formula::Xor(fs) => {
+                let new_fs: Vec<Box<Formula<'a>>> = fs.iter().map(|f| {
                     Box::new(f.drop_quantifiers())
-                }).collect());
+                }).collect();
+                // an unconstrained disjunct makes the disjunction unconstrained
+                if new_fs.iter().any(|f| matches!(**f, Formula::Empty)) {
+                    return Formula::Empty;
+                }
+                return match self {
+                    Formula::Or(_) => Formula::Or(new_fs),
+                    _ => Formula::Xor(new_fs),
+                };
             }
             Formula::Imply(ps, qs) => {
-                let new_ps = ps.iter().map(|p| {
+                let new_ps: Vec<Box<Formula<'a>>> = ps.iter().map(|p| {
                     Box::new(p.drop_quantifiers())
                 }).collect();
-                let new_qs = qs.iter().map(|q| {
+                let new_qs: Vec<Box<Formula<'a>>> = qs.iter().map(|q| {
                     Box::new(q.drop_quantifiers())
                 }).collect();
+                if new_ps.iter().chain(new_qs.iter()).any(|f| matches!(**f, Formula::Empty)) {
+                    return Formula::Empty;
+                }
                 return Formula::Imply(new_ps, new_qs);
             }
             Formula::Equals(_, _) => {}
             Formula::ForAll(_, _) | Formula::Exists(_, _) => {return Formula::Empty}
+            Formula::Compare(..) | Formula::Assign(..) => {return Formula::Empty}
+            // time points of durative actions are not encoded (see "get_timed_formulas")
+            Formula::Timed(..) => {return Formula::Empty}
         }
         self.clone()
     }
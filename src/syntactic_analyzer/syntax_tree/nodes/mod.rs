@@ -5,6 +5,8 @@ mod htn;
 mod method;
 mod action;
 mod formula;
+mod numeric;
+mod temporal;
 
 pub use predicate::Predicate;
 pub use task::Task;
@@ -12,4 +14,6 @@ pub use symbol::*;
 pub use htn::*;
 pub use method::*;
 pub use action::*;
-pub use formula::*;
\ No newline at end of file
+pub use formula::*;
+pub use numeric::*;
+pub use temporal::*;
\ No newline at end of file
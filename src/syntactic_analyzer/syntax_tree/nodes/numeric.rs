@@ -0,0 +1,159 @@
+use std::fmt;
+
+use super::*;
+
+// a numeric expression over numeric fluents, e.g., "(+ (total-cost) (road-length ?a ?b))"
+#[derive(Clone, Debug)]
+pub enum NumericExpression<'a> {
+    Number(f64),
+    // the "?duration" of a durative action
+    Duration,
+    // a function term, e.g., "(road-length ?a ?b)"
+    Function(Predicate<'a>),
+    // an operation on its operands ("-" with a single operand is negation)
+    Operation(ArithmeticOperation, Vec<NumericExpression<'a>>),
+}
+
+impl<'a> NumericExpression<'a> {
+    // the function terms that appear in the expression
+    pub fn get_functions(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            NumericExpression::Number(_) | NumericExpression::Duration => vec![],
+            NumericExpression::Function(function) => vec![function],
+            NumericExpression::Operation(_, operands) => {
+                operands.iter().flat_map(|operand| operand.get_functions()).collect()
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for NumericExpression<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericExpression::Number(number) => write!(f, "{}", number),
+            NumericExpression::Duration => write!(f, "?duration"),
+            NumericExpression::Function(function) => {
+                write!(f, "({}", function.name)?;
+                for var in function.variables.iter() {
+                    write!(f, " {}", var.name)?;
+                }
+                write!(f, ")")
+            }
+            NumericExpression::Operation(operation, operands) => {
+                write!(f, "({}", operation)?;
+                for operand in operands {
+                    write!(f, " {}", operand)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticOperation {
+    Plus,
+    Minus,
+    Times,
+    Divide,
+}
+
+impl fmt::Display for ArithmeticOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticOperation::Plus => write!(f, "+"),
+            ArithmeticOperation::Minus => write!(f, "-"),
+            ArithmeticOperation::Times => write!(f, "*"),
+            ArithmeticOperation::Divide => write!(f, "/"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonType {
+    Equal,
+    LessThan,
+    GreaterThan,
+    LessThanOrEqual,
+    GreaterThanOrEqual,
+}
+
+impl fmt::Display for ComparisonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparisonType::Equal => write!(f, "="),
+            ComparisonType::LessThan => write!(f, "<"),
+            ComparisonType::GreaterThan => write!(f, ">"),
+            ComparisonType::LessThanOrEqual => write!(f, "<="),
+            ComparisonType::GreaterThanOrEqual => write!(f, ">="),
+        }
+    }
+}
+
+// the effect of an action on a numeric fluent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssignmentType {
+    Assign,
+    Increase,
+    Decrease,
+    ScaleUp,
+    ScaleDown,
+}
+
+impl fmt::Display for AssignmentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssignmentType::Assign => write!(f, "assign"),
+            AssignmentType::Increase => write!(f, "increase"),
+            AssignmentType::Decrease => write!(f, "decrease"),
+            AssignmentType::ScaleUp => write!(f, "scale-up"),
+            AssignmentType::ScaleDown => write!(f, "scale-down"),
+        }
+    }
+}
+
+// the value of a numeric fluent in the initial state, e.g., "(= (road-length a b) 7)"
+#[derive(Clone, Debug)]
+pub struct FunctionValue<'a> {
+    pub function: Predicate<'a>,
+    pub value: f64,
+}
+
+impl<'a> fmt::Display for FunctionValue<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(= {} {})",
+            NumericExpression::Function(self.function.clone()),
+            self.value
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Optimization {
+    Minimize,
+    Maximize,
+}
+
+impl fmt::Display for Optimization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Optimization::Minimize => write!(f, "minimize"),
+            Optimization::Maximize => write!(f, "maximize"),
+        }
+    }
+}
+
+// the plan metric of a problem, e.g., "(:metric minimize (total-cost))"
+#[derive(Clone, Debug)]
+pub struct Metric<'a> {
+    pub optimization: Optimization,
+    pub expression: NumericExpression<'a>,
+}
+
+impl<'a> fmt::Display for Metric<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(:metric {} {})", self.optimization, self.expression)
+    }
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::{borrow::Borrow, hash::Hash};
+use std::{borrow::Borrow, fmt, hash::Hash};
 
 use crate::TokenPosition;
 
@@ -6,12 +6,38 @@ use crate::TokenPosition;
 pub struct Symbol<'a> {
     pub name: &'a str,
     pub name_pos: TokenPosition,
-    pub symbol_type: Option<&'a str>,
+    pub symbol_type: Option<SymbolType<'a>>,
     pub type_pos: Option<TokenPosition>
 }
 
+// the type of a symbol is either a single type, or a union "(either t1 t2 ...)"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SymbolType<'a> {
+    Simple(&'a str),
+    Either(Vec<&'a str>),
+}
+
+impl <'a> SymbolType<'a> {
+    // the types that make up this type
+    pub fn members(&self) -> &[&'a str] {
+        match self {
+            SymbolType::Simple(t) => std::slice::from_ref(t),
+            SymbolType::Either(types) => types,
+        }
+    }
+}
+
+impl <'a> fmt::Display for SymbolType<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymbolType::Simple(t) => write!(fmt, "{}", t),
+            SymbolType::Either(types) => write!(fmt, "(either {})", types.join(" ")),
+        }
+    }
+}
+
 impl <'a> Symbol<'a> {
-    pub fn new(name: &'a str, name_pos: TokenPosition, symbol_type: Option<&'a str>, type_pos: Option<TokenPosition>) -> Symbol<'a> {
+    pub fn new(name: &'a str, name_pos: TokenPosition, symbol_type: Option<SymbolType<'a>>, type_pos: Option<TokenPosition>) -> Symbol<'a> {
         Symbol {
             name,
             name_pos,
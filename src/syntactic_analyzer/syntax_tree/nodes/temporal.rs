@@ -0,0 +1,19 @@
+use std::fmt;
+
+// the time point (or interval) of a durative action that a condition or effect refers to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeSpecifier {
+    AtStart,
+    AtEnd,
+    OverAll,
+}
+
+impl fmt::Display for TimeSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSpecifier::AtStart => write!(f, "at start"),
+            TimeSpecifier::AtEnd => write!(f, "at end"),
+            TimeSpecifier::OverAll => write!(f, "over all"),
+        }
+    }
+}
\ No newline at end of file
@@ -2,20 +2,35 @@ use super::*;
 use crate::TokenPosition;
 #[derive(Debug)]
 pub struct ProblemAST<'a> {
+    pub name: String,
+    // the domain that the problem refers to
+    pub domain_name: String,
+    pub domain_name_pos: TokenPosition,
     pub requirements: Vec<RequirementType>,
+    // requirements that are not part of HDDL (along with their position)
+    pub unknown_requirements: Vec<(String, TokenPosition)>,
     pub init_tn: Option<InitialTaskNetwork<'a>>,
     pub init_state: Vec<Predicate<'a>>,
+    // the initial values of numeric fluents
+    pub init_values: Vec<FunctionValue<'a>>,
     pub goal: Option<Formula<'a>>,
+    pub metric: Option<Metric<'a>>,
     pub objects: Vec<Symbol<'a>>,
 }
 
 impl <'a> ProblemAST<'a> {
-    pub fn new() -> ProblemAST<'a> {
+    pub fn new(name: String, domain_name: String, domain_name_pos: TokenPosition) -> ProblemAST<'a> {
         ProblemAST {
+            name,
+            domain_name,
+            domain_name_pos,
             requirements: vec![],
+            unknown_requirements: vec![],
             init_tn: None,
             init_state: vec![],
+            init_values: vec![],
             goal: None,
+            metric: None,
             objects: vec![]
         }
     }
@@ -23,7 +38,7 @@ impl <'a> ProblemAST<'a> {
         let object = Symbol::new(name, object_pos, None, None);
         self.objects.push(object);
     }
-    pub fn add_typed_object(&mut self, name: &'a str, name_pos: TokenPosition, object_type: &'a str, type_pos: TokenPosition) {
+    pub fn add_typed_object(&mut self, name: &'a str, name_pos: TokenPosition, object_type: SymbolType<'a>, type_pos: TokenPosition) {
         let object = Symbol::new(name, name_pos, Some(object_type), Some(type_pos));
         self.objects.push(object);
     }
@@ -33,10 +48,20 @@ impl <'a> ProblemAST<'a> {
     pub fn add_init_state(&mut self, state: Vec<Predicate<'a>>) {
         self.init_state = state;
     }
+    pub fn add_init_values(&mut self, values: Vec<FunctionValue<'a>>) {
+        self.init_values = values;
+    }
     pub fn add_goal(&mut self, goal: Formula<'a>) {
         self.goal = Some(goal);
     }
+    pub fn add_metric(&mut self, metric: Metric<'a>) {
+        self.metric = Some(metric);
+    }
     pub fn add_requirement(&mut self, req: RequirementType) {
         self.requirements.push(req);
     }
+
+    pub fn add_unknown_requirement(&mut self, name: String, position: TokenPosition) {
+        self.unknown_requirements.push((name, position));
+    }
 }
\ No newline at end of file
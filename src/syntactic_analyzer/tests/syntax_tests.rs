@@ -39,19 +39,19 @@ mod tests {
             Ok(AbstractSyntaxTree::Problem(symbols)) => {
                 assert_eq!(symbols.objects[0].name, "a");
                 assert_eq!(symbols.objects[0].name_pos.line, 2);
-                assert_eq!(symbols.objects[0].symbol_type.unwrap(), "d");
+                assert_eq!(symbols.objects[0].symbol_type, Some(SymbolType::Simple("d")));
                 assert_eq!(symbols.objects[0].type_pos.unwrap().line, 4);
                 assert_eq!(symbols.objects[1].name, "b");
                 assert_eq!(symbols.objects[1].name_pos.line, 3);
-                assert_eq!(symbols.objects[1].symbol_type.unwrap(), "d");
+                assert_eq!(symbols.objects[1].symbol_type, Some(SymbolType::Simple("d")));
                 assert_eq!(symbols.objects[1].type_pos.unwrap().line, 4);
                 assert_eq!(symbols.objects[2].name, "c");
                 assert_eq!(symbols.objects[2].name_pos.line, 3);
-                assert_eq!(symbols.objects[2].symbol_type.unwrap(), "d");
+                assert_eq!(symbols.objects[2].symbol_type, Some(SymbolType::Simple("d")));
                 assert_eq!(symbols.objects[2].type_pos.unwrap().line, 4);
                 assert_eq!(symbols.objects[3].name, "s");
                 assert_eq!(symbols.objects[3].name_pos.line, 5);
-                assert_eq!(symbols.objects[3].symbol_type.unwrap(), "f");
+                assert_eq!(symbols.objects[3].symbol_type, Some(SymbolType::Simple("f")));
                 assert_eq!(symbols.objects[3].type_pos.unwrap().line, 5);
                 assert_eq!(symbols.objects[4].name, "t");
                 assert_eq!(symbols.objects[4].name_pos.line, 5);
@@ -140,18 +140,18 @@ mod tests {
             Ok(AbstractSyntaxTree::Domain(symbols)) => {
                 assert_eq!(symbols.predicates.len(), 3);
                 for predicate in symbols.predicates {
-                    let items: Vec<(&str, Option<&str>)> = predicate
+                    let items: Vec<(&str, Option<SymbolType>)> = predicate
                         .variables
                         .iter()
-                        .map(|x| (x.name, x.symbol_type))
+                        .map(|x| (x.name, x.symbol_type.clone()))
                         .collect();
                     if predicate.name == "pred_1" {
                         assert_eq!(
                             items,
                             vec![
-                                ("a_1", Some("t_1")),
-                                ("a_2", Some("t_1")),
-                                ("a_3", Some("t_2"))
+                                ("a_1", Some(SymbolType::Simple("t_1"))),
+                                ("a_2", Some(SymbolType::Simple("t_1"))),
+                                ("a_3", Some(SymbolType::Simple("t_2")))
                             ]
                         );
                         assert_eq!(predicate.name_pos.line, 3);
@@ -160,10 +160,10 @@ mod tests {
                         assert_eq!(predicate.name_pos.line, 4);
                     } else if predicate.name == "pred_3" {
                         assert_eq!(predicate.name_pos.line, 5);
-                        let items: Vec<(&str, Option<&str>)> = predicate
+                        let items: Vec<(&str, Option<SymbolType>)> = predicate
                             .variables
                             .iter()
-                            .map(|x| (x.name, x.symbol_type))
+                            .map(|x| (x.name, x.symbol_type.clone()))
                             .collect();
                         assert_eq!(items, vec![("a_1", None), ("a_2", None)]);
                     } else {
@@ -292,10 +292,10 @@ mod tests {
                                 let neq = &*predicates[2];
                                 match neq {
                                     Formula::Not(equality) => {
-                                        match **equality {
+                                        match &**equality {
                                             Formula::Equals(a, b) => {
-                                                assert_eq!(a, "l1");
-                                                assert_eq!(b, "l2");
+                                                assert_eq!(a.name, "l1");
+                                                assert_eq!(b.name, "l2");
                                             }
                                             _ => { panic!("equality constraint not parsed successfully")}
                                         }
@@ -352,23 +352,23 @@ mod tests {
                             Formula::ForAll(params, exp) => {
                                 assert_eq!(params.len(), 2);
                                 assert_eq!(params[0].name, "l1");
-                                match params[0].symbol_type {
+                                match &params[0].symbol_type {
                                     Some(x) => {
-                                        assert_eq!(x, "loc");
+                                        assert_eq!(x.to_string(), "loc");
                                     }
                                     _ => { panic!("wrong parameter type") }
                                 }
                                 assert_eq!(params[1].name, "l2");
-                                match params[1].symbol_type {
+                                match &params[1].symbol_type {
                                     Some(x) => {
-                                        assert_eq!(x, "loc");
+                                        assert_eq!(x.to_string(), "loc");
                                     }
                                     _ => { panic!("wrong parameter type") }
                                 }
-                                match **exp {
+                                match &**exp {
                                     Formula::Equals(a,b ) => {
-                                        assert_eq!(a, "l1");
-                                        assert_eq!(b, "l2");
+                                        assert_eq!(a.name, "l1");
+                                        assert_eq!(b.name, "l2");
                                     }
                                     _ => {
                                         panic!("wrong expression parsing")
@@ -588,10 +588,10 @@ mod tests {
                 assert_eq!(c_1.name_pos.line, 3);
                 let c1_term_names: Vec<&str> =
                     c_1.parameters.iter().map(|x| x.name).collect();
-                let c1_term_types: Vec<&str> = c_1
+                let c1_term_types: Vec<String> = c_1
                     .parameters
                     .iter()
-                    .map(|x| x.symbol_type.unwrap())
+                    .map(|x| x.symbol_type.as_ref().unwrap().to_string())
                     .collect();
                 assert_eq!(c1_term_names, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(c1_term_types, vec!["t1", "t1", "t2"]);
@@ -622,10 +622,10 @@ mod tests {
                 assert_eq!(action.name, "a_1");
                 let a1_vars: Vec<&str> =
                     action.parameters.iter().map(|x| x.name).collect();
-                let a1_var_types: Vec<&str> = action
+                let a1_var_types: Vec<String> = action
                     .parameters                    
                     .iter()
-                    .map(|x| x.symbol_type.unwrap())
+                    .map(|x| x.symbol_type.as_ref().unwrap().to_string())
                     .collect();
                 assert_eq!(a1_vars, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(a1_var_types, vec!["t1", "t1", "t2"]);
@@ -699,10 +699,10 @@ mod tests {
                 assert_eq!(action.name, "a_1");
                 let a1_vars: Vec<&str> =
                     action.parameters.iter().map(|x| x.name).collect();
-                let a1_var_types: Vec<&str> = action
+                let a1_var_types: Vec<String> = action
                     .parameters                    
                     .iter()
-                    .map(|x| x.symbol_type.unwrap())
+                    .map(|x| x.symbol_type.as_ref().unwrap().to_string())
                     .collect();
                 assert_eq!(a1_vars, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(a1_var_types, vec!["t1", "t1", "t2"]);
@@ -796,10 +796,10 @@ mod tests {
                 assert_eq!(action.name, "a_1");
                 let a1_vars: Vec<&str> =
                     action.parameters.iter().map(|x| x.name).collect();
-                let a1_var_types: Vec<&str> = action
+                let a1_var_types: Vec<String> = action
                     .parameters                    
                     .iter()
-                    .map(|x| x.symbol_type.unwrap())
+                    .map(|x| x.symbol_type.as_ref().unwrap().to_string())
                     .collect();
                 assert_eq!(a1_vars, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(a1_var_types, vec!["t1", "t1", "t2"]);
@@ -851,21 +851,21 @@ mod tests {
                 let types = ast.types.unwrap();
                 assert_eq!(types.len(), 8);
                 assert_eq!(types[0].name, "Port");
-                assert_eq!(types[0].symbol_type.unwrap(), "Object");
+                assert_eq!(types[0].symbol_type, Some(SymbolType::Simple("Object")));
                 assert_eq!(types[1].name, "AbstractDevice");
-                assert_eq!(types[1].symbol_type.unwrap(), "Object");
+                assert_eq!(types[1].symbol_type, Some(SymbolType::Simple("Object")));
                 assert_eq!(types[2].name, "AbstractCable");
-                assert_eq!(types[2].symbol_type.unwrap(), "AbstractDevice");
+                assert_eq!(types[2].symbol_type, Some(SymbolType::Simple("AbstractDevice")));
                 assert_eq!(types[3].name, "Device");
-                assert_eq!(types[3].symbol_type.unwrap(), "AbstractDevice");
+                assert_eq!(types[3].symbol_type, Some(SymbolType::Simple("AbstractDevice")));
                 assert_eq!(types[4].name, "PlugType");
-                assert_eq!(types[4].symbol_type.unwrap(), "Enum");
+                assert_eq!(types[4].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(types[5].name, "PlugFace");
-                assert_eq!(types[5].symbol_type.unwrap(), "Enum");
+                assert_eq!(types[5].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(types[6].name, "PlugDirection");
-                assert_eq!(types[6].symbol_type.unwrap(), "Enum");
+                assert_eq!(types[6].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(types[7].name, "SignalType");
-                assert_eq!(types[7].symbol_type.unwrap(), "Enum");
+                assert_eq!(types[7].symbol_type, Some(SymbolType::Simple("Enum")));
             }
             _ => panic!("parsing erro")
         }
@@ -893,10 +893,10 @@ mod tests {
                 assert_eq!(c_1.name, "c_1");
                 let c1_term_names: Vec<&str> =
                     c_1.parameters.iter().map(|x| x.name).collect();
-                let c1_term_types: Vec<&str> = c_1
+                let c1_term_types: Vec<String> = c_1
                     .parameters
                     .iter()
-                    .map(|x| x.symbol_type.unwrap())
+                    .map(|x| x.symbol_type.as_ref().unwrap().to_string())
                     .collect();
                 assert_eq!(c1_term_names, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(c1_term_types, vec!["t1", "t1", "t2"]);
@@ -923,21 +923,21 @@ mod tests {
                 let constants = ast.constants.unwrap();
                 assert_eq!(constants.len(), 8);
                 assert_eq!(constants[0].name, "Port");
-                assert_eq!(constants[0].symbol_type.unwrap(), "Object");
+                assert_eq!(constants[0].symbol_type, Some(SymbolType::Simple("Object")));
                 assert_eq!(constants[1].name, "AbstractDevice");
-                assert_eq!(constants[1].symbol_type.unwrap(), "Object");
+                assert_eq!(constants[1].symbol_type, Some(SymbolType::Simple("Object")));
                 assert_eq!(constants[2].name, "AbstractCable");
-                assert_eq!(constants[2].symbol_type.unwrap(), "AbstractDevice");
+                assert_eq!(constants[2].symbol_type, Some(SymbolType::Simple("AbstractDevice")));
                 assert_eq!(constants[3].name, "Device");
-                assert_eq!(constants[3].symbol_type.unwrap(), "AbstractDevice");
+                assert_eq!(constants[3].symbol_type, Some(SymbolType::Simple("AbstractDevice")));
                 assert_eq!(constants[4].name, "PlugType");
-                assert_eq!(constants[4].symbol_type.unwrap(), "Enum");
+                assert_eq!(constants[4].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(constants[5].name, "PlugFace");
-                assert_eq!(constants[5].symbol_type.unwrap(), "Enum");
+                assert_eq!(constants[5].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(constants[6].name, "PlugDirection");
-                assert_eq!(constants[6].symbol_type.unwrap(), "Enum");
+                assert_eq!(constants[6].symbol_type, Some(SymbolType::Simple("Enum")));
                 assert_eq!(constants[7].name, "SignalType");
-                assert_eq!(constants[7].symbol_type.unwrap(), "Enum");
+                assert_eq!(constants[7].symbol_type, Some(SymbolType::Simple("Enum")));
             }
             _ => panic!("parsing erro")
         }
@@ -986,4 +986,376 @@ mod tests {
             Err(token) => panic!("{:?}", token)
         }
     }
-}
+
+    #[test]
+    pub fn error_recovery_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:predicates (at ?x) (hold ?x))
+                (:action a_1
+                 :parameters (?x)
+                 :precondition (at ?x)
+                 :effect (hold ?x) (at ?x)
+                )
+                (:task t_1 :parameters (?x))
+                (:action a_2
+                 :parameters ?x)
+                 :effect (at ?x)
+                )
+                (:method m_1
+                 :parameters (?x)
+                 :task (t_1 ?x)
+                 :subtasks (a_1 ?x)
+                )
+             )",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let (ast, errors) = parser.parse_with_recovery();
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 6),
+            error => panic!("{:?}", error),
+        }
+        match &errors[1] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 10),
+            error => panic!("{:?}", error),
+        }
+        match ast {
+            Some(AbstractSyntaxTree::Domain(ast)) => {
+                assert_eq!(ast.predicates.len(), 2);
+                assert_eq!(ast.compound_tasks.len(), 1);
+                assert_eq!(ast.methods.len(), 1);
+                assert_eq!(ast.actions.len(), 0);
+            }
+            _ => panic!("expected a partial domain"),
+        }
+    }
+
+    #[test]
+    pub fn problem_error_recovery_test() {
+        let program = String::from(
+            "(define (problem p1) (domain bal)
+                (:objects a b - t)
+                dangling
+                (:init (at a) (?x@ b))
+                (:goal (at b))
+             )",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let (ast, errors) = parser.parse_with_recovery();
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 3),
+            error => panic!("{:?}", error),
+        }
+        match &errors[1] {
+            ParsingError::Lexiacal(error) => assert_eq!(error.lexeme, "x@"),
+            error => panic!("{:?}", error),
+        }
+        match ast {
+            Some(AbstractSyntaxTree::Problem(ast)) => {
+                assert_eq!(ast.objects.len(), 2);
+                assert!(ast.goal.is_some());
+            }
+            _ => panic!("expected a partial problem"),
+        }
+        // the first error is still reported by "parse"
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Err(ParsingError::Syntactic(error)) => assert_eq!(error.position.line, 3),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    pub fn conditional_effect_parsing_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:action a_1
+                 :parameters (?t)
+                 :effect (forall (?p - package)
+                    (when (in ?p ?t) (and (not (in ?p ?t)) (at ?p))))
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => match ast.actions[0].effects.as_ref().unwrap() {
+                Formula::ForAll(vars, effect) => {
+                    assert_eq!(vars[0].name, "p");
+                    assert_eq!(vars[0].symbol_type, Some(SymbolType::Simple("package")));
+                    match effect.as_ref() {
+                        Formula::Imply(conditions, effects) => {
+                            assert_eq!(conditions.len(), 1);
+                            assert_eq!(conditions[0].get_propositional_predicates()[0].name, "in");
+                            assert_eq!(effects[0].get_propositional_predicates().len(), 2);
+                        }
+                        formula => panic!("{:?}", formula),
+                    }
+                }
+                formula => panic!("{:?}", formula),
+            },
+            _ => panic!("parsing errors"),
+        }
+    }
+
+    #[test]
+    pub fn either_type_parsing_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:types truck plane - vehicle seaplane - (either plane ship))
+                (:predicates (at ?v - (either truck plane) ?l))
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => {
+                let types = ast.types.unwrap();
+                assert_eq!(types[2].name, "seaplane");
+                assert_eq!(types[2].symbol_type, Some(SymbolType::Either(vec!["plane", "ship"])));
+                assert_eq!(types[2].type_pos.unwrap().line, 2);
+                let variables = &ast.predicates[0].variables;
+                assert_eq!(variables[0].symbol_type, Some(SymbolType::Either(vec!["truck", "plane"])));
+                assert_eq!(variables[0].symbol_type.as_ref().unwrap().to_string(), "(either truck plane)");
+                assert_eq!(variables[1].symbol_type, None);
+            }
+            _ => panic!("parsing errors"),
+        }
+        // the union must have at least one member
+        let program = String::from("(define (problem p) (:domain bal) (:objects a - (either))) ").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Err(ParsingError::Syntactic(error)) => assert_eq!(error.found, ")"),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+    #[test]
+    pub fn numeric_fluents_parsing_test() {
+        let program = String::from(
+            "(define (domain transport)
+                (:requirements :typing :numeric-fluents :action-costs)
+                (:types location)
+                (:predicates (at ?l - location))
+                (:functions (road-length ?a ?b - location) - number (total-cost))
+                (:action drive
+                    :parameters (?a ?b - location)
+                    :precondition (and (at ?a) (<= (road-length ?a ?b) 10))
+                    :effect (and (at ?b) (increase (total-cost) (* 2 (road-length ?a ?b))))
+                )
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => {
+                assert_eq!(ast.functions.len(), 2);
+                assert_eq!(ast.functions[0].name, "road-length");
+                assert_eq!(ast.functions[0].variables.len(), 2);
+                assert_eq!(ast.functions[1].name, "total-cost");
+                let action = &ast.actions[0];
+                match action.preconditions.as_ref().unwrap() {
+                    Formula::And(fs) => match &*fs[1] {
+                        Formula::Compare(ComparisonType::LessThanOrEqual, left, right) => {
+                            assert_eq!(left.to_string(), "(road-length a b)");
+                            assert_eq!(right.to_string(), "10");
+                        }
+                        f => panic!("{:?}", f),
+                    },
+                    f => panic!("{:?}", f),
+                }
+                match action.effects.as_ref().unwrap() {
+                    Formula::And(fs) => match &*fs[1] {
+                        Formula::Assign(AssignmentType::Increase, function, value) => {
+                            assert_eq!(function.name, "total-cost");
+                            assert_eq!(value.to_string(), "(* 2 (road-length a b))");
+                        }
+                        f => panic!("{:?}", f),
+                    },
+                    f => panic!("{:?}", f),
+                }
+                assert_eq!(action.effects.as_ref().unwrap().get_functions().len(), 2);
+            }
+            _ => panic!("parsing errors"),
+        }
+        let program = String::from(
+            "(define (problem p) (:domain transport)
+                (:objects a b - location)
+                (:init (at a) (= (road-length a b) 7) (= (total-cost) 0))
+                (:metric minimize (total-cost))
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Problem(ast)) => {
+                assert_eq!(ast.init_state.len(), 1);
+                assert_eq!(ast.init_values.len(), 2);
+                assert_eq!(ast.init_values[0].function.name, "road-length");
+                assert_eq!(ast.init_values[0].value, 7.0);
+                assert_eq!(ast.init_values[1].to_string(), "(= (total-cost) 0)");
+                let metric = ast.metric.unwrap();
+                assert_eq!(metric.optimization, Optimization::Minimize);
+                assert_eq!(metric.expression.to_string(), "(total-cost)");
+            }
+            _ => panic!("parsing errors"),
+        }
+    }
+    #[test]
+    pub fn durative_action_parsing_test() {
+        let program = String::from(
+            "(define (domain transport)
+                (:requirements :durative-actions)
+                (:predicates (at ?v ?l) (road ?a ?b))
+                (:durative-action drive
+                    :parameters (?v ?a ?b)
+                    :duration (and (>= ?duration 2) (<= ?duration 5))
+                    :condition (and (at start (at ?v ?a)) (over all (road ?a ?b)))
+                    :effect (and (at start (not (at ?v ?a))) (at end (at ?v ?b)))
+                )
+                (:action wait :parameters (?v))
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => {
+                assert!(ast.requirements.contains(&RequirementType::DurativeActions));
+                let drive = &ast.actions[0];
+                assert!(drive.is_durative());
+                assert!(!ast.actions[1].is_durative());
+                match drive.duration.as_ref().unwrap() {
+                    Formula::And(constraints) => {
+                        assert_eq!(constraints.len(), 2);
+                        match &*constraints[1] {
+                            Formula::Compare(ComparisonType::LessThanOrEqual, lhs, rhs) => {
+                                assert_eq!(lhs.to_string(), "?duration");
+                                assert_eq!(rhs.to_string(), "5");
+                            }
+                            f => panic!("{:?}", f),
+                        }
+                    }
+                    f => panic!("{:?}", f),
+                }
+                let conditions = drive.preconditions.as_ref().unwrap().get_timed_formulas();
+                assert_eq!(conditions.len(), 2);
+                assert_eq!(conditions[0].0, TimeSpecifier::AtStart);
+                assert_eq!(conditions[1].0, TimeSpecifier::OverAll);
+                match conditions[1].1 {
+                    Formula::Atom(predicate) => assert_eq!(predicate.name, "road"),
+                    f => panic!("{:?}", f),
+                }
+                let effects = drive.effects.as_ref().unwrap().get_timed_formulas();
+                assert_eq!(effects[1].0.to_string(), "at end");
+                assert!(matches!(effects[0].1, Formula::Not(_)));
+            }
+            _ => panic!("parsing errors"),
+        }
+        // the formulas of durative actions must be annotated with their time
+        let program = String::from(
+            "(define (domain d)
+                (:durative-action a :parameters () :duration () :condition (at ?x))
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Err(ParsingError::Syntactic(error)) => assert_eq!(error.found, "Identifier x"),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+    #[test]
+    pub fn block_closing_parsing_test() {
+        // a totally ordered method with constraints, followed by another method
+        let program = String::from(
+            "(define (domain d)
+                (:method m1 :parameters (?a ?b) :task (t ?a)
+                    :ordered-subtasks (and (a1 ?a) (a2 ?b))
+                    :constraints (not (= ?a ?b)))
+                (:method m2 :parameters (?a) :task (t ?a) :ordered-subtasks ())
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => {
+                assert_eq!(ast.methods.len(), 2);
+                assert_eq!(ast.methods[0].tn.subtasks.len(), 2);
+                assert!(ast.methods[0].tn.constraints.is_some());
+                assert_eq!(ast.methods[1].name.name, "m2");
+            }
+            _ => panic!("parsing errors"),
+        }
+        // the blocks after the goal
+        let program = String::from(
+            "(define (problem p) (:domain d)
+                (:init (at a))
+                (:goal (at b))
+                (:metric minimize (total-cost))
+            ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Problem(ast)) => {
+                assert!(ast.goal.is_some());
+                assert!(ast.metric.is_some());
+            }
+            _ => panic!("parsing errors"),
+        }
+    }
+    #[test]
+    pub fn concrete_syntax_tree_test() {
+        let source = "; a domain
+(define (domain d)
+    (:predicates (p ?x)) ; unary
+    ; the only action
+    (:action a :parameters (?x)
+        :precondition (p ?x) ; holds
+        :effect (not (p ?x)))
+)
+";
+        let program = String::from(source).into_bytes();
+        let parser = Parser::new(LexicalAnalyzer::new(&program));
+        let document = match parser.parse_concrete() {
+            Ok(document) => document,
+            Err(error) => panic!("{:?}", error),
+        };
+        assert_eq!(document.to_string(), source);
+        let comments: Vec<&str> = document.comments().iter().map(|comment| comment.text).collect();
+        assert_eq!(comments, vec!["; a domain", "; unary", "; the only action", "; holds"]);
+        // the nodes of the AST are found by their positions
+        let parser = Parser::new(LexicalAnalyzer::new(&program));
+        let ast = match parser.parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => ast,
+            _ => panic!("parsing errors"),
+        };
+        let action = document.block_at(ast.actions[0].name_pos.offset).unwrap();
+        assert_eq!(action.keyword(), Some(&KeywordName::Action));
+        assert!(action.to_string().trim_start().starts_with("; the only action\n    (:action a"));
+        let precondition = document.node_at(ast.actions[0].parameters[0].name_pos.offset).unwrap();
+        assert_eq!(precondition.kind, SyntaxKind::List);
+        assert_eq!(precondition.span(), (source.find("(?x)").unwrap(), source.find("(?x)").unwrap() + 4));
+        // rewriting a node keeps the comments around it
+        let rewritten = document.replace(action, "(:action b :parameters ())");
+        assert_eq!(rewritten, "; a domain
+(define (domain d)
+    (:predicates (p ?x)) ; unary
+    ; the only action
+    (:action b :parameters ())
+)
+");
+        // unbalanced parentheses
+        let program = String::from("(define (domain d) (:predicates (p ?x))").into_bytes();
+        let parser = Parser::new(LexicalAnalyzer::new(&program));
+        match parser.parse_concrete() {
+            Err(ParsingError::Syntactic(error)) => assert_eq!(error.found, "End of file"),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+}
\ No newline at end of file
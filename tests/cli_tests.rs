@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const DOMAIN: &str = "(define (domain transport)
+    (:requirements :typing :hierarchy)
+    (:types location vehicle - object)
+    (:predicates (road ?a ?b - location) (at ?v - vehicle ?l - location))
+    (:task get-to :parameters (?v - vehicle ?l - location))
+    (:method m_drive
+        :parameters (?v - vehicle ?a ?b - location)
+        :task (get-to ?v ?b)
+        :ordered-subtasks (and (drive ?v ?a ?b))
+    )
+    (:action drive
+        :parameters (?v - vehicle ?a ?b - location)
+        :precondition (and (at ?v ?a) (road ?a ?b))
+        :effect (and (not (at ?v ?a)) (at ?v ?b))
+    )
+)";
+
+const PROBLEM: &str = "(define (problem p) (:domain transport)
+    (:objects a b - location t1 - vehicle)
+    (:htn :parameters () :subtasks (and (get-to t1 b)))
+    (:init (road a b) (at t1 a))
+    (:goal (at t1 b))
+)";
+
+// writes the files of a test into its own directory, and returns their paths
+fn write_files(test: &str, files: &[(&str, &str)]) -> Vec<String> {
+    let dir: PathBuf = std::env::temp_dir().join(format!("hddl_analyzer_{}_{}", test, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    files
+        .iter()
+        .map(|(name, content)| {
+            let path = dir.join(name);
+            fs::write(&path, content).unwrap();
+            path.to_string_lossy().into_owned()
+        })
+        .collect()
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_hddl_analyzer"))
+        .args(["--color", "never"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn validate_plan_exit_status_test() {
+    let paths = write_files(
+        "validate",
+        &[
+            ("domain.hddl", DOMAIN),
+            ("problem.hddl", PROBLEM),
+            ("valid.plan", "==>\n0 (drive t1 a b)\nroot 1\n1 (get-to t1 b) -> m_drive 0\n<=="),
+            ("invalid.plan", "==>\n0 (drive t1 b a)\nroot 1\n1 (get-to t1 a) -> m_drive 0\n<=="),
+        ],
+    );
+    let output = run(&["validate-plan", &paths[0], &paths[2], "-p", &paths[1]]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let output = run(&["validate-plan", &paths[0], &paths[3], "-p", &paths[1]]);
+    assert_eq!(output.status.code(), Some(1));
+    // the exit status does not depend on the output format
+    let output = run(&["validate-plan", &paths[0], &paths[3], "-p", &paths[1], "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn simulate_exit_status_test() {
+    let paths = write_files(
+        "simulate",
+        &[
+            ("domain.hddl", DOMAIN),
+            ("problem.hddl", PROBLEM),
+            ("valid.plan", "(drive t1 a b)"),
+            ("inapplicable.plan", "(drive t1 b a)"),
+            ("unfinished.plan", ""),
+        ],
+    );
+    let output = run(&["simulate", &paths[0], &paths[2], "-p", &paths[1]]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    // the action is not applicable in the initial state
+    let output = run(&["simulate", &paths[0], &paths[3], "-p", &paths[1]]);
+    assert_eq!(output.status.code(), Some(1));
+    // the goal does not hold in the initial state
+    let output = run(&["simulate", &paths[0], &paths[4], "-p", &paths[1], "--format", "json"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn ground_exit_status_test() {
+    let paths = write_files(
+        "ground",
+        &[
+            ("domain.hddl", DOMAIN),
+            ("problem.hddl", PROBLEM),
+            ("undefined.hddl", &PROBLEM.replace("(at t1 b)", "(in t1 b)")),
+        ],
+    );
+    let output = run(&["ground", &paths[0], "-p", &paths[1]]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    // the goal refers to an undefined predicate
+    let output = run(&["ground", &paths[0], "-p", &paths[2]]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn unreadable_plan_exit_status_test() {
+    let paths = write_files("unreadable", &[("domain.hddl", DOMAIN), ("problem.hddl", PROBLEM)]);
+    let missing = paths[0].replace("domain.hddl", "missing.plan");
+    let output = run(&["validate-plan", &paths[0], &missing, "-p", &paths[1]]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = run(&["simulate", &paths[0], &missing, "-p", &paths[1]]);
+    assert_eq!(output.status.code(), Some(1));
+}